@@ -2,7 +2,9 @@ use tauri::State;
 
 use crate::database::models::{Button, Folder};
 use crate::database::repository;
-use super::DbConnection;
+use crate::database::Database;
+use super::{FileWatchManagerState, ScheduleManagerState};
+use crate::executor::runner::validate_retry_policy;
 use serde::Deserialize;
 
 #[derive(Deserialize)]
@@ -15,35 +17,41 @@ pub struct PositionUpdate {
 #[tauri::command]
 pub async fn create_button(
     button: Button,
-    db: State<'_, DbConnection>,
+    db: State<'_, Database>,
 ) -> Result<String, String> {
-    let conn = db.0.lock().map_err(|e| e.to_string())?;
-
-    repository::create_button(&conn, &button)
-        .map_err(|e| format!("Failed to create button: {}", e))
+    if let Some(policy) = &button.retry_policy {
+        validate_retry_policy(policy)?;
+    }
+    db.write(move |conn| {
+        repository::create_button(conn, &button)
+            .map_err(|e| format!("Failed to create button: {}", e))
+    })
+    .await
 }
 
 /// Get all buttons
 #[tauri::command]
 pub async fn get_all_buttons(
-    db: State<'_, DbConnection>,
+    db: State<'_, Database>,
 ) -> Result<Vec<Button>, String> {
-    let conn = db.0.lock().map_err(|e| e.to_string())?;
-
-    repository::get_all_buttons(&conn)
-        .map_err(|e| format!("Failed to get buttons: {}", e))
+    db.read(move |conn| {
+        repository::get_all_buttons(conn)
+            .map_err(|e| format!("Failed to get buttons: {}", e))
+    })
+    .await
 }
 
 /// Get a single button by ID
 #[tauri::command]
 pub async fn get_button(
     id: String,
-    db: State<'_, DbConnection>,
+    db: State<'_, Database>,
 ) -> Result<Button, String> {
-    let conn = db.0.lock().map_err(|e| e.to_string())?;
-
-    repository::get_button_by_id(&conn, &id)
-        .map_err(|e| format!("Failed to get button: {}", e))
+    db.read(move |conn| {
+        repository::get_button_by_id(conn, &id)
+            .map_err(|e| format!("Failed to get button: {}", e))
+    })
+    .await
 }
 
 /// Update an existing button
@@ -51,87 +59,107 @@ pub async fn get_button(
 pub async fn update_button(
     id: String,
     button: Button,
-    db: State<'_, DbConnection>,
+    db: State<'_, Database>,
 ) -> Result<(), String> {
-    let conn = db.0.lock().map_err(|e| e.to_string())?;
-
-    repository::update_button(&conn, &id, &button)
-        .map_err(|e| format!("Failed to update button: {}", e))
+    if let Some(policy) = &button.retry_policy {
+        validate_retry_policy(policy)?;
+    }
+    db.write(move |conn| {
+        repository::update_button(conn, &id, &button)
+            .map_err(|e| format!("Failed to update button: {}", e))
+    })
+    .await
 }
 
-/// Delete a button by ID
+/// Delete a button by ID, stopping the schedules and file triggers that ran it
 #[tauri::command]
 pub async fn delete_button(
     id: String,
-    db: State<'_, DbConnection>,
+    db: State<'_, Database>,
+    schedules: State<'_, ScheduleManagerState>,
+    file_triggers: State<'_, FileWatchManagerState>,
 ) -> Result<(), String> {
-    let conn = db.0.lock().map_err(|e| e.to_string())?;
-
-    repository::delete_button(&conn, &id)
-        .map_err(|e| format!("Failed to delete button: {}", e))
+    let deleted = db
+        .write(move |conn| {
+            repository::delete_button(conn, &id)
+                .map_err(|e| format!("Failed to delete button: {}", e))
+        })
+        .await?;
+
+    for schedule_id in &deleted.schedule_ids {
+        schedules.0.stop_schedule(schedule_id).await;
+    }
+    for trigger_id in &deleted.file_trigger_ids {
+        file_triggers.0.stop_trigger(trigger_id).await;
+    }
+    Ok(())
 }
 
 /// Get buttons by folder ID
 #[tauri::command]
 pub async fn get_buttons_by_folder(
     folder_id: Option<String>,
-    db: State<'_, DbConnection>,
+    db: State<'_, Database>,
 ) -> Result<Vec<Button>, String> {
-    let conn = db.0.lock().map_err(|e| e.to_string())?;
-
-    repository::get_buttons_by_folder(&conn, folder_id.as_deref())
-        .map_err(|e| format!("Failed to get buttons by folder: {}", e))
+    db.read(move |conn| {
+        repository::get_buttons_by_folder(conn, folder_id.as_deref())
+            .map_err(|e| format!("Failed to get buttons by folder: {}", e))
+    })
+    .await
 }
 
 /// Update multiple button positions at once
 #[tauri::command]
 pub async fn update_button_positions(
     updates: Vec<PositionUpdate>,
-    db: State<'_, DbConnection>,
+    db: State<'_, Database>,
 ) -> Result<(), String> {
-    let conn = db.0.lock().map_err(|e| e.to_string())?;
-
     let updates_vec: Vec<(String, i32)> = updates
         .into_iter()
         .map(|u| (u.id, u.position))
         .collect();
 
-    repository::update_button_positions(&conn, &updates_vec)
-        .map_err(|e| format!("Failed to update button positions: {}", e))
+    db.write(move |conn| {
+        repository::update_button_positions(conn, &updates_vec)
+            .map_err(|e| format!("Failed to update button positions: {}", e))
+    })
+    .await
 }
 
 /// Update multiple monitor positions at once
 #[tauri::command]
 pub async fn update_monitor_positions(
     updates: Vec<PositionUpdate>,
-    db: State<'_, DbConnection>,
+    db: State<'_, Database>,
 ) -> Result<(), String> {
-    let conn = db.0.lock().map_err(|e| e.to_string())?;
-
     let updates_vec: Vec<(String, i32)> = updates
         .into_iter()
         .map(|u| (u.id, u.position))
         .collect();
 
-    repository::update_monitor_positions(&conn, &updates_vec)
-        .map_err(|e| format!("Failed to update monitor positions: {}", e))
+    db.write(move |conn| {
+        repository::update_monitor_positions(conn, &updates_vec)
+            .map_err(|e| format!("Failed to update monitor positions: {}", e))
+    })
+    .await
 }
 
 /// Update multiple folder positions at once
 #[tauri::command]
 pub async fn update_folder_positions(
     updates: Vec<PositionUpdate>,
-    db: State<'_, DbConnection>,
+    db: State<'_, Database>,
 ) -> Result<(), String> {
-    let conn = db.0.lock().map_err(|e| e.to_string())?;
-
     let updates_vec: Vec<(String, i32)> = updates
         .into_iter()
         .map(|u| (u.id, u.position))
         .collect();
 
-    repository::update_folder_positions(&conn, &updates_vec)
-        .map_err(|e| format!("Failed to update folder positions: {}", e))
+    db.write(move |conn| {
+        repository::update_folder_positions(conn, &updates_vec)
+            .map_err(|e| format!("Failed to update folder positions: {}", e))
+    })
+    .await
 }
 
 // ============================================================================
@@ -142,35 +170,38 @@ pub async fn update_folder_positions(
 #[tauri::command]
 pub async fn create_folder(
     folder: Folder,
-    db: State<'_, DbConnection>,
+    db: State<'_, Database>,
 ) -> Result<String, String> {
-    let conn = db.0.lock().map_err(|e| e.to_string())?;
-
-    repository::create_folder(&conn, &folder)
-        .map_err(|e| format!("Failed to create folder: {}", e))
+    db.write(move |conn| {
+        repository::create_folder(conn, &folder)
+            .map_err(|e| format!("Failed to create folder: {}", e))
+    })
+    .await
 }
 
 /// Get all folders
 #[tauri::command]
 pub async fn get_all_folders(
-    db: State<'_, DbConnection>,
+    db: State<'_, Database>,
 ) -> Result<Vec<Folder>, String> {
-    let conn = db.0.lock().map_err(|e| e.to_string())?;
-
-    repository::get_all_folders(&conn)
-        .map_err(|e| format!("Failed to get folders: {}", e))
+    db.read(move |conn| {
+        repository::get_all_folders(conn)
+            .map_err(|e| format!("Failed to get folders: {}", e))
+    })
+    .await
 }
 
 /// Get a single folder by ID
 #[tauri::command]
 pub async fn get_folder(
     id: String,
-    db: State<'_, DbConnection>,
+    db: State<'_, Database>,
 ) -> Result<Folder, String> {
-    let conn = db.0.lock().map_err(|e| e.to_string())?;
-
-    repository::get_folder_by_id(&conn, &id)
-        .map_err(|e| format!("Failed to get folder: {}", e))
+    db.read(move |conn| {
+        repository::get_folder_by_id(conn, &id)
+            .map_err(|e| format!("Failed to get folder: {}", e))
+    })
+    .await
 }
 
 /// Update an existing folder
@@ -178,22 +209,60 @@ pub async fn get_folder(
 pub async fn update_folder(
     id: String,
     folder: Folder,
-    db: State<'_, DbConnection>,
+    db: State<'_, Database>,
 ) -> Result<(), String> {
-    let conn = db.0.lock().map_err(|e| e.to_string())?;
-
-    repository::update_folder(&conn, &id, &folder)
-        .map_err(|e| format!("Failed to update folder: {}", e))
+    db.write(move |conn| {
+        repository::update_folder(conn, &id, &folder)
+            .map_err(|e| format!("Failed to update folder: {}", e))
+    })
+    .await
 }
 
 /// Delete a folder by ID
 #[tauri::command]
 pub async fn delete_folder(
     id: String,
-    db: State<'_, DbConnection>,
+    db: State<'_, Database>,
 ) -> Result<(), String> {
-    let conn = db.0.lock().map_err(|e| e.to_string())?;
+    db.write(move |conn| {
+        repository::delete_folder(conn, &id)
+            .map_err(|e| format!("Failed to delete folder: {}", e))
+    })
+    .await
+}
 
-    repository::delete_folder(&conn, &id)
-        .map_err(|e| format!("Failed to delete folder: {}", e))
+#[cfg(test)]
+mod tests {
+    use crate::database::{insert_button, repository, TestDb};
+
+    #[test]
+    fn test_delete_button_removes_its_automations() {
+        let db = TestDb::open();
+        let conn = db.conn().unwrap();
+        for button in ["b1", "b2"] {
+            insert_button(&conn, button, button);
+        }
+        for (id, button) in [("s1", "b1"), ("s2", "b2")] {
+            conn.execute(
+                "INSERT INTO schedules (id, button_id, cron_expression, created_at, updated_at)
+                 VALUES (?1, ?2, '0 * * * *', 0, 0)",
+                [id, button],
+            )
+            .unwrap();
+        }
+        conn.execute(
+            "INSERT INTO file_triggers (id, button_id, paths, created_at, updated_at) VALUES ('t1', 'b1', '[]', 0, 0)",
+            [],
+        )
+        .unwrap();
+
+        let deleted = repository::delete_button(&conn, "b1").unwrap();
+        assert_eq!(deleted.schedule_ids, ["s1"]);
+        assert_eq!(deleted.file_trigger_ids, ["t1"]);
+
+        let schedules: Vec<String> = repository::get_all_schedules(&conn).unwrap().into_iter().map(|s| s.id).collect();
+        assert_eq!(schedules, ["s2"]);
+        assert!(repository::get_all_file_triggers(&conn).unwrap().is_empty());
+        assert!(repository::get_button_by_id(&conn, "b1").is_err());
+    }
 }
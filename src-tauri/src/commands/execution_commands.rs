@@ -1,96 +1,132 @@
-use tauri::{AppHandle, Emitter, Manager, State};
-use tokio::sync::mpsc;
+use std::collections::HashMap;
+use tauri::{AppHandle, State};
 
-use crate::database::models::LogEntry;
+use serde::Serialize;
+
+use crate::database::models::{ExecutionRecord, LogEntry};
 use crate::database::repository;
-use crate::executor::{ShellExecutor, PythonExecutor, JsExecutor};
-use super::DbConnection;
+use crate::database::Database;
+use crate::executor::runner::{self, Trigger};
+use crate::executor::{build_plan, EnvChange, ExecutionPlan, PreflightReport, SecretMode};
+
+/// What a run would do, without spawning anything
+#[derive(Debug, Serialize)]
+pub struct ExecutionPreview {
+    pub button_id: String,
+    pub interpreter: String,
+    pub argv: Vec<String>,
+    pub working_dir: String,
+    pub env: Vec<EnvChange>, // Variables set on top of the inherited environment
+    pub script: String,      // Rendered script, ${secret:...} references left unresolved
+}
+
+impl From<(String, ExecutionPlan)> for ExecutionPreview {
+    fn from((button_id, plan): (String, ExecutionPlan)) -> Self {
+        Self {
+            button_id,
+            argv: plan.argv(),
+            env: plan.env_diff(),
+            interpreter: plan.interpreter,
+            working_dir: plan.working_dir,
+            script: plan.script,
+        }
+    }
+}
+
+/// Check whether a button needs confirmation before running with `params` and why
+#[tauri::command]
+pub async fn preflight_script(
+    button_id: String,
+    params: Option<HashMap<String, String>>,
+    db: State<'_, Database>,
+) -> Result<PreflightReport, String> {
+    let button = db
+        .read(move |conn| {
+            repository::get_button_by_id(conn, &button_id)
+                .map_err(|e| format!("Failed to get button: {}", e))
+        })
+        .await?;
+
+    Ok(PreflightReport::for_button(&button, &params.unwrap_or_default()))
+}
+
+/// Render the final command for a button without executing it (dry run)
+#[tauri::command]
+pub async fn preview_execution(
+    button_id: String,
+    params: Option<HashMap<String, String>>,
+    db: State<'_, Database>,
+) -> Result<ExecutionPreview, String> {
+    let button = db
+        .read(move |conn| {
+            repository::get_button_by_id(conn, &button_id)
+                .map_err(|e| format!("Failed to get button: {}", e))
+        })
+        .await?;
+
+    // Secrets stay as references so the preview never exposes their values
+    let plan = build_plan("preview", &button, &params.unwrap_or_default(), SecretMode::Keep)?;
+    Ok(ExecutionPreview::from((button.id, plan)))
+}
 
 /// Execute a script from a button
 #[tauri::command]
 pub async fn execute_script(
     button_id: String,
+    confirmed: Option<bool>,
+    params: Option<HashMap<String, String>>,
     app_handle: AppHandle,
-    db: State<'_, DbConnection>,
+    db: State<'_, Database>,
 ) -> Result<String, String> {
     // Get button from database
-    let button = {
-        let conn = db.0.lock().map_err(|e| e.to_string())?;
-        repository::get_button_by_id(&conn, &button_id)
-            .map_err(|e| format!("Failed to get button: {}", e))?
-    };
-
-    // Generate execution ID
-    let execution_id = uuid::Uuid::new_v4().to_string();
+    let button = db
+        .read(move |conn| {
+            repository::get_button_by_id(conn, &button_id)
+                .map_err(|e| format!("Failed to get button: {}", e))
+        })
+        .await?;
 
-    // Create a channel for log streaming
-    let (log_tx, mut log_rx) = mpsc::channel::<LogEntry>(100);
-
-    // Clone app_handle for the log receiver task
-    let app_handle_clone = app_handle.clone();
-
-    // Spawn a task to receive logs and emit them to frontend
-    tokio::spawn(async move {
-        while let Some(log_entry) = log_rx.recv().await {
-            // Save log to database using app_handle's managed state
-            if let Some(db_state) = app_handle_clone.try_state::<DbConnection>() {
-                if let Ok(conn) = db_state.0.lock() {
-                    let _ = repository::create_log(&conn, &log_entry);
-                }
-            }
-
-            // Emit log to frontend
-            let _ = app_handle_clone.emit("log-entry", &log_entry);
-        }
-    });
-
-    // Execute based on script type
-    match button.script_type.as_str() {
-        "shell" => {
-            let executor = ShellExecutor::new(
-                execution_id.clone(),
-                button.id.clone(),
-                button.name.clone(),
-                button.script_content.clone(),
-            );
-
-            // Spawn execution in background
-            tokio::spawn(async move {
-                let _ = executor.execute(log_tx).await;
-            });
-
-            Ok(execution_id)
-        }
-        "python" => {
-            let executor = PythonExecutor::new(
-                execution_id.clone(),
-                button.id.clone(),
-                button.name.clone(),
-                button.script_content.clone(),
-            );
+    // Refuse to run buttons that need confirmation unless the caller confirmed;
+    // parameters are part of the check, so a risky value needs confirmation too
+    let params = params.unwrap_or_default();
+    let preflight = PreflightReport::for_button(&button, &params);
+    if preflight.requires_confirmation && !confirmed.unwrap_or(false) {
+        return Err(format!(
+            "Execution of '{}' requires confirmation (call preflight_script for details)",
+            button.name
+        ));
+    }
 
-            // Spawn execution in background
-            tokio::spawn(async move {
-                let _ = executor.execute(log_tx).await;
-            });
+    runner::start_execution(&app_handle, button, &params, Trigger::manual())
+        .map(|started| started.execution_id)
+}
 
-            Ok(execution_id)
-        }
-        "javascript" => {
-            let executor = JsExecutor::new(
-                execution_id.clone(),
-                button.id.clone(),
-                button.name.clone(),
-                button.script_content.clone(),
-            );
+/// Get recent executions of a button (newest first)
+#[tauri::command]
+pub async fn get_button_executions(
+    button_id: String,
+    limit: Option<u32>,
+    db: State<'_, Database>,
+) -> Result<Vec<ExecutionRecord>, String> {
+    db.read(move |conn| {
+        repository::get_executions_by_button(conn, &button_id, limit.unwrap_or(50))
+            .map_err(|e| format!("Failed to get executions: {}", e))
+    })
+    .await
+}
 
-            // Spawn execution in background
-            tokio::spawn(async move {
-                let _ = executor.execute(log_tx).await;
-            });
+/// Get all logs of one execution, including every retry attempt
+#[tauri::command]
+pub async fn get_execution_logs(
+    execution_id: String,
+    db: State<'_, Database>,
+) -> Result<Vec<LogEntry>, String> {
+    // The last lines of a run that just finished may still be queued for the writer
+    db.flush().await?;
 
-            Ok(execution_id)
-        }
-        _ => Err(format!("Unknown script type: {}", button.script_type)),
-    }
+    db.read(move |conn| {
+        repository::get_logs_by_execution(conn, &execution_id)
+            .map_err(|e| format!("Failed to get execution logs: {}", e))
+    })
+    .await
 }
@@ -0,0 +1,142 @@
+use std::sync::Arc;
+use tauri::{AppHandle, State};
+
+use crate::database::models::FileTrigger;
+use crate::database::repository;
+use crate::database::Database;
+use crate::executor::preflight;
+use crate::file_watch::{validate_trigger, FileWatchManager};
+
+/// Shared file watch manager state
+pub struct FileWatchManagerState(pub Arc<FileWatchManager>);
+
+/// List all file triggers
+#[tauri::command]
+pub async fn list_file_triggers(db: State<'_, Database>) -> Result<Vec<FileTrigger>, String> {
+    db.read(move |conn| {
+        repository::get_all_file_triggers(conn)
+            .map_err(|e| format!("Failed to get file triggers: {}", e))
+    })
+    .await
+}
+
+/// Create a file trigger and start watching if enabled.
+/// Buttons needing confirmation must be confirmed here, since nobody confirms the triggered runs.
+#[tauri::command]
+pub async fn create_file_trigger(
+    trigger: FileTrigger,
+    confirmed: Option<bool>,
+    app_handle: AppHandle,
+    db: State<'_, Database>,
+    manager: State<'_, FileWatchManagerState>,
+) -> Result<FileTrigger, String> {
+    validate_trigger(&trigger)?;
+
+    let now = chrono::Utc::now().timestamp();
+    let new_trigger = FileTrigger {
+        id: uuid::Uuid::new_v4().to_string(),
+        created_at: now,
+        updated_at: now,
+        ..trigger
+    };
+
+    let new_trigger = db
+        .write(move |conn| {
+            let button = repository::get_button_by_id(conn, &new_trigger.button_id)
+                .map_err(|e| format!("Failed to get button: {}", e))?;
+            let stored = FileTrigger {
+                confirmed_script: preflight::confirm_unattended(&button, confirmed.unwrap_or(false), None)?,
+                ..new_trigger
+            };
+            repository::create_file_trigger(conn, &stored)
+                .map_err(|e| format!("Failed to create file trigger: {}", e))?;
+            Ok(stored)
+        })
+        .await?;
+
+    if new_trigger.enabled {
+        manager.0.start_trigger(new_trigger.clone(), app_handle).await?;
+    }
+
+    Ok(new_trigger)
+}
+
+/// Update a file trigger and restart or stop its watch.
+/// The earlier confirmation is kept while it still covers the script of the button.
+#[tauri::command]
+pub async fn update_file_trigger(
+    id: String,
+    trigger: FileTrigger,
+    confirmed: Option<bool>,
+    app_handle: AppHandle,
+    db: State<'_, Database>,
+    manager: State<'_, FileWatchManagerState>,
+) -> Result<FileTrigger, String> {
+    validate_trigger(&trigger)?;
+
+    let trigger_id = id.clone();
+    let updated = db
+        .write(move |conn| {
+            let existing = repository::get_file_trigger_by_id(conn, &trigger_id)
+                .map_err(|e| format!("Failed to get file trigger: {}", e))?;
+            let button = repository::get_button_by_id(conn, &trigger.button_id)
+                .map_err(|e| format!("Failed to get button: {}", e))?;
+            let previous = existing.confirmed_script.filter(|_| existing.button_id == trigger.button_id);
+            let updated = FileTrigger {
+                id: trigger_id.clone(),
+                confirmed_script: preflight::confirm_unattended(&button, confirmed.unwrap_or(false), previous)?,
+                updated_at: chrono::Utc::now().timestamp(),
+                ..trigger
+            };
+            repository::update_file_trigger(conn, &trigger_id, &updated)
+                .map_err(|e| format!("Failed to update file trigger: {}", e))?;
+            repository::get_file_trigger_by_id(conn, &trigger_id)
+                .map_err(|e| format!("Failed to get file trigger: {}", e))
+        })
+        .await?;
+
+    if updated.enabled {
+        manager.0.start_trigger(updated.clone(), app_handle).await?;
+    } else {
+        manager.0.stop_trigger(&id).await;
+    }
+
+    Ok(updated)
+}
+
+/// Delete a file trigger and stop its watch
+#[tauri::command]
+pub async fn delete_file_trigger(
+    id: String,
+    db: State<'_, Database>,
+    manager: State<'_, FileWatchManagerState>,
+) -> Result<(), String> {
+    manager.0.stop_trigger(&id).await;
+
+    db.write(move |conn| {
+        repository::delete_file_trigger(conn, &id)
+            .map_err(|e| format!("Failed to delete file trigger: {}", e))
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::database::{insert_button, repository, TestDb};
+
+    #[test]
+    fn test_unreadable_pattern_lists_are_reported() {
+        let db = TestDb::open();
+        let conn = db.conn().unwrap();
+        insert_button(&conn, "b1", "Build");
+        conn.execute(
+            "INSERT INTO file_triggers (id, button_id, paths, exclude_patterns, created_at, updated_at)
+             VALUES ('t1', 'b1', '[\"src\"]', 'target/**', 0, 0)",
+            [],
+        )
+        .unwrap();
+
+        assert!(repository::get_file_trigger_by_id(&conn, "t1").is_err());
+        assert!(repository::get_all_file_triggers(&conn).is_err());
+    }
+}
@@ -1,39 +1,256 @@
-use tauri::State;
+use rusqlite::Connection;
+use tauri::{AppHandle, State};
 
-use crate::database::models::LogEntry;
+use crate::database::models::{LogCursor, LogEntry, LogSearchPage, LogSearchQuery};
 use crate::database::repository;
-use super::DbConnection;
+use crate::database::Database;
+use crate::log_export::{self, ExportFormat};
+use crate::retention::{self, PruneReport, RetentionConfig};
 
 /// Get all logs
 #[tauri::command]
 pub async fn get_logs(
-    db: State<'_, DbConnection>,
+    db: State<'_, Database>,
 ) -> Result<Vec<LogEntry>, String> {
-    let conn = db.0.lock().map_err(|e| e.to_string())?;
-
-    repository::get_all_logs(&conn)
-        .map_err(|e| format!("Failed to get logs: {}", e))
+    db.read(move |conn| {
+        repository::get_all_logs(conn)
+            .map_err(|e| format!("Failed to get logs: {}", e))
+    })
+    .await
 }
 
 /// Get logs for a specific button
 #[tauri::command]
 pub async fn get_logs_by_button(
     button_id: String,
-    db: State<'_, DbConnection>,
+    db: State<'_, Database>,
 ) -> Result<Vec<LogEntry>, String> {
-    let conn = db.0.lock().map_err(|e| e.to_string())?;
+    db.read(move |conn| {
+        repository::get_logs_by_button(conn, &button_id)
+            .map_err(|e| format!("Failed to get logs by button: {}", e))
+    })
+    .await
+}
+
+const DEFAULT_SEARCH_LIMIT: u32 = 100;
+const MAX_SEARCH_LIMIT: u32 = 500;
 
-    repository::get_logs_by_button(&conn, &button_id)
-        .map_err(|e| format!("Failed to get logs by button: {}", e))
+/// Search logs by message text and filters, one page at a time (newest first)
+#[tauri::command]
+pub async fn search_logs(
+    db: State<'_, Database>,
+    query: LogSearchQuery,
+) -> Result<LogSearchPage, String> {
+    // Include entries still queued for the writer
+    db.flush().await?;
+    db.read(move |conn| search_logs_page(conn, &query)).await
+}
+
+/// Validate the query and fetch one page; one extra row tells whether another page follows
+fn search_logs_page(conn: &Connection, query: &LogSearchQuery) -> Result<LogSearchPage, String> {
+    let limit = query.limit.unwrap_or(DEFAULT_SEARCH_LIMIT);
+    if limit == 0 || limit > MAX_SEARCH_LIMIT {
+        return Err(format!("limit must be between 1 and {}", MAX_SEARCH_LIMIT));
+    }
+    if let (Some(from), Some(to)) = (query.from, query.to) {
+        if from > to {
+            return Err("from must not be later than to".to_string());
+        }
+    }
+    let cursor = match &query.cursor {
+        Some(cursor) => Some(LogCursor::parse(cursor).ok_or_else(|| format!("Invalid cursor: {}", cursor))?),
+        None => None,
+    };
+
+    let mut entries = repository::search_logs(conn, query, cursor.as_ref(), limit + 1)
+        .map_err(|e| format!("Failed to search logs: {}", e))?;
+    let next_cursor = if entries.len() > limit as usize {
+        entries.truncate(limit as usize);
+        entries.last().map(|log| LogCursor::after(log).encode())
+    } else {
+        None
+    };
+
+    Ok(LogSearchPage { entries, next_cursor })
+}
+
+/// Export the logs matching `filter` to a file; returns the number of exported logs.
+/// An existing file is only replaced with `overwrite`.
+#[tauri::command]
+pub async fn export_logs(
+    db: State<'_, Database>,
+    filter: LogSearchQuery,
+    format: ExportFormat,
+    path: String,
+    overwrite: Option<bool>,
+) -> Result<u64, String> {
+    let path = std::path::PathBuf::from(path);
+    if !path.is_absolute() {
+        return Err(format!("Export path must be absolute: {}", path.display()));
+    }
+
+    db.flush().await?;
+    let overwrite = overwrite.unwrap_or(false);
+    db.read(move |conn| log_export::export_logs(conn, &filter, format, &path, overwrite)).await
 }
 
 /// Clear all logs
 #[tauri::command]
 pub async fn clear_logs(
-    db: State<'_, DbConnection>,
+    db: State<'_, Database>,
 ) -> Result<(), String> {
-    let conn = db.0.lock().map_err(|e| e.to_string())?;
+    db.write(move |conn| {
+        repository::clear_all_logs(conn)
+            .map_err(|e| format!("Failed to clear logs: {}", e))
+    })
+    .await
+}
+
+/// Get the log retention limits
+#[tauri::command]
+pub async fn get_log_retention_config(
+    db: State<'_, Database>,
+) -> Result<RetentionConfig, String> {
+    db.read(|conn| Ok(retention::load_config(conn))).await
+}
+
+/// Validate and save the log retention limits, then apply them right away
+#[tauri::command]
+pub async fn update_log_retention_config(
+    app_handle: AppHandle,
+    db: State<'_, Database>,
+    config: RetentionConfig,
+) -> Result<PruneReport, String> {
+    retention::validate_config(&config)?;
+
+    let json = serde_json::to_string(&config).map_err(|e| e.to_string())?;
+    db.write(move |conn| {
+        repository::set_setting(conn, retention::SETTING_RETENTION_CONFIG, &json)
+            .map_err(|e| format!("Failed to save retention config: {}", e))
+    })
+    .await?;
+
+    retention::run_pruning(&app_handle, &db).await
+}
+
+/// Prune logs now with the saved retention limits
+#[tauri::command]
+pub async fn prune_logs(
+    app_handle: AppHandle,
+    db: State<'_, Database>,
+) -> Result<PruneReport, String> {
+    retention::run_pruning(&app_handle, &db).await
+}
+
+/// Convert the database so `incremental` vacuum can release free pages. Rewrites the whole file once;
+/// returns false if it was already converted.
+#[tauri::command]
+pub async fn enable_incremental_vacuum(
+    db: State<'_, Database>,
+) -> Result<bool, String> {
+    db.write(retention::enable_incremental_vacuum).await
+}
+
+/// Get the result of the last pruning run
+#[tauri::command]
+pub async fn get_last_prune_report(
+    db: State<'_, Database>,
+) -> Result<Option<PruneReport>, String> {
+    db.read(|conn| Ok(retention::load_last_report(conn))).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::{insert_log, TestDb};
+
+    fn search(conn: &Connection, query: LogSearchQuery) -> LogSearchPage {
+        search_logs_page(conn, &query).unwrap()
+    }
+
+    #[test]
+    fn test_search_logs_by_text_level_and_time() {
+        let db = TestDb::open();
+        let conn = db.conn().unwrap();
+
+        insert_log(&conn, None, "error", "Connection refused by upstream", 100);
+        insert_log(&conn, None, "info", "connection established", 200);
+        insert_log(&conn, None, "error", "❌ 执行失败: 磁盘空间不足", 300);
+        insert_log(&conn, None, "warning", "retry 2/3 (AND NOT \"quoted\")", 400);
+
+        let text = |text: &str| LogSearchQuery {
+            text: Some(text.to_string()),
+            ..Default::default()
+        };
+        assert_eq!(search(&conn, text("CONNECTION")).entries.len(), 2);
+        assert_eq!(search(&conn, text("connection refused")).entries.len(), 1);
+        assert_eq!(search(&conn, text("磁盘空间")).entries.len(), 1);
+        // Short terms and FTS5 syntax are matched literally
+        assert_eq!(search(&conn, text("执行")).entries.len(), 1);
+        assert_eq!(search(&conn, text("2/3 AND \"quoted")).entries.len(), 1);
+        assert_eq!(search(&conn, text("100%")).entries.len(), 0);
+
+        let errors = search(&conn, LogSearchQuery {
+            text: Some("connection".to_string()),
+            levels: vec!["error".to_string()],
+            ..Default::default()
+        });
+        assert_eq!(errors.entries.len(), 1);
+        assert_eq!(errors.entries[0].timestamp, 100);
+
+        let ranged = search(&conn, LogSearchQuery {
+            from: Some(200),
+            to: Some(300),
+            ..Default::default()
+        });
+        assert_eq!(ranged.entries.iter().map(|log| log.timestamp).collect::<Vec<_>>(), vec![300, 200]);
+
+        // Deleted logs disappear from the index
+        repository::delete_logs_before(&conn, 150).unwrap();
+        assert_eq!(search(&conn, text("refused")).entries.len(), 0);
+    }
+
+    #[test]
+    fn test_search_logs_pages_with_cursor() {
+        let db = TestDb::open();
+        let conn = db.conn().unwrap();
+
+        // Several entries share a timestamp so the id breaks ties
+        for i in 0..7 {
+            insert_log(&conn, None, "info", &format!("line {}", i), 1000 + i / 3);
+        }
+
+        let mut seen = Vec::new();
+        let mut cursor = None;
+        loop {
+            let page = search(&conn, LogSearchQuery {
+                text: Some("line".to_string()),
+                cursor: cursor.clone(),
+                limit: Some(3),
+                ..Default::default()
+            });
+            assert!(page.entries.len() <= 3);
+            seen.extend(page.entries.into_iter().map(|log| (log.timestamp, log.id)));
+            cursor = page.next_cursor;
+            if cursor.is_none() {
+                break;
+            }
+        }
+
+        assert_eq!(seen.len(), 7);
+        let mut expected = seen.clone();
+        expected.sort_by(|a, b| b.cmp(a));
+        assert_eq!(seen, expected);
 
-    repository::clear_all_logs(&conn)
-        .map_err(|e| format!("Failed to clear logs: {}", e))
+        assert!(search_logs_page(&conn, &LogSearchQuery {
+            cursor: Some("not-a-cursor".to_string()),
+            ..Default::default()
+        })
+        .is_err());
+        assert!(search_logs_page(&conn, &LogSearchQuery {
+            limit: Some(0),
+            ..Default::default()
+        })
+        .is_err());
+    }
 }
@@ -1,22 +1,38 @@
 mod button_commands;
 mod log_commands;
 mod execution_commands;
+mod file_trigger_commands;
 mod monitor_commands;
+mod notification_commands;
+mod schedule_commands;
+mod secret_commands;
+mod settings_commands;
 
 use tauri::State;
 
+use crate::database::Database;
+
 // Shared state types
-pub use monitor_commands::{DbConnection, MonitorManagerState};
+pub use file_trigger_commands::FileWatchManagerState;
+pub use monitor_commands::MonitorManagerState;
+pub use schedule_commands::ScheduleManagerState;
+pub use secret_commands::SecretStoreState;
+pub use settings_commands::RedactorState;
 
 // Re-export commands
 pub use button_commands::*;
 pub use log_commands::*;
 pub use execution_commands::*;
+pub use file_trigger_commands::*;
 pub use monitor_commands::*;
+pub use notification_commands::*;
+pub use schedule_commands::*;
+pub use secret_commands::*;
+pub use settings_commands::*;
 
 #[tauri::command]
-pub fn test_db_connection(db: State<DbConnection>) -> Result<String, String> {
-    let conn = db.0.lock().map_err(|e| e.to_string())?;
+pub fn test_db_connection(db: State<Database>) -> Result<String, String> {
+    let conn = db.conn()?;
 
     // 测试查询
     let count: i32 = conn
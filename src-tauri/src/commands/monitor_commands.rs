@@ -1,70 +1,142 @@
-use tauri::{State, Manager};
-use std::sync::{Arc, Mutex};
+use tauri::State;
+use std::sync::Arc;
 use rusqlite::Connection;
 
-use crate::database::models::Monitor;
+use crate::database::models::{Monitor, MonitorCheck, MonitorCheckHistory, MonitorStats};
 use crate::database::repository;
-use crate::monitor::MonitorManager;
-
-/// Shared database connection state
-pub struct DbConnection(pub Mutex<Connection>);
+use crate::database::Database;
+use crate::executor::preflight;
+use crate::monitor::{prepare_monitor, MonitorError, MonitorManager};
 
 /// Shared monitor manager state (using Arc for async access)
 pub struct MonitorManagerState(pub Arc<MonitorManager>);
 
-/// Create a new monitor
+/// Make sure the remediation button of a monitor exists and may run unattended.
+/// Returns the confirmation to store: the current script if the user confirmed the link,
+/// otherwise the previous confirmation if it still applies.
+fn confirm_remediation(
+    conn: &Connection,
+    monitor: &Monitor,
+    confirmed: bool,
+    previous: Option<String>,
+) -> Result<Option<String>, String> {
+    let Some(button_id) = &monitor.remediation_button_id else {
+        return Ok(None);
+    };
+    let button = repository::get_button_by_id(conn, button_id)
+        .map_err(|e| format!("Failed to get remediation button: {}", e))?;
+    preflight::confirm_unattended(&button, confirmed, previous)
+}
+
+const DEFAULT_CHECK_LIMIT: u32 = 100;
+const MAX_CHECK_LIMIT: u32 = 1000;
+const DEFAULT_HISTORY_POINTS: u32 = 200;
+const MAX_HISTORY_POINTS: u32 = 2000;
+
+/// Make sure a time range is not reversed
+fn validate_range(from: i64, to: i64) -> Result<(), String> {
+    if from > to {
+        return Err("from must not be later than to".to_string());
+    }
+    Ok(())
+}
+
+/// Smallest bucket size that splits [from, to] into at most `max_points` buckets
+fn bucket_secs(from: i64, to: i64, max_points: u32) -> i64 {
+    let span = to - from + 1;
+    ((span + i64::from(max_points) - 1) / i64::from(max_points)).max(1)
+}
+
+/// Persist whether a monitor is running
+async fn set_monitor_active(db: &Database, id: String, active: bool) -> Result<(), String> {
+    db.write(move |conn| {
+        let mut monitor = repository::get_monitor_by_id(conn, &id)
+            .map_err(|e| format!("Failed to get monitor: {}", e))?;
+        monitor.is_active = active;
+        repository::update_monitor(conn, &id, &monitor)
+            .map_err(|e| format!("Failed to update monitor status: {}", e))
+    })
+    .await
+}
+
+/// Create a new monitor; validation errors list the invalid fields.
+/// A remediation button needing confirmation must be confirmed with `remediation_confirmed`.
 #[tauri::command]
 pub async fn create_monitor(
-    db: State<'_, DbConnection>,
+    db: State<'_, Database>,
     monitor: Monitor,
-) -> Result<Monitor, String> {
-    let conn = db.0.lock().map_err(|e| e.to_string())?;
-
+    remediation_confirmed: Option<bool>,
+) -> Result<Monitor, MonitorError> {
     // Generate a new UUID for the monitor
     let mut new_monitor = monitor;
     new_monitor.id = uuid::Uuid::new_v4().to_string();
+    prepare_monitor(&mut new_monitor)?;
 
-    repository::create_monitor(&conn, &new_monitor)
-        .map_err(|e| format!("Failed to create monitor: {}", e))?;
-
-    Ok(new_monitor)
+    let monitor = db
+        .write(move |conn| {
+            new_monitor.remediation_confirmed_script =
+                confirm_remediation(conn, &new_monitor, remediation_confirmed.unwrap_or(false), None)?;
+            repository::create_monitor(conn, &new_monitor)
+                .map_err(|e| format!("Failed to create monitor: {}", e))?;
+            Ok(new_monitor)
+        })
+        .await?;
+    Ok(monitor)
 }
 
 /// Get all monitors
 #[tauri::command]
-pub async fn get_all_monitors(db: State<'_, DbConnection>) -> Result<Vec<Monitor>, String> {
-    let conn = db.0.lock().map_err(|e| e.to_string())?;
-
-    repository::get_all_monitors(&conn)
-        .map_err(|e| format!("Failed to get monitors: {}", e))
+pub async fn get_all_monitors(db: State<'_, Database>) -> Result<Vec<Monitor>, String> {
+    db.read(move |conn| {
+        repository::get_all_monitors(conn)
+            .map_err(|e| format!("Failed to get monitors: {}", e))
+    })
+    .await
 }
 
 /// Get a single monitor by ID
 #[tauri::command]
-pub async fn get_monitor(db: State<'_, DbConnection>, id: String) -> Result<Monitor, String> {
-    let conn = db.0.lock().map_err(|e| e.to_string())?;
-
-    repository::get_monitor_by_id(&conn, &id)
-        .map_err(|e| format!("Failed to get monitor: {}", e))
+pub async fn get_monitor(db: State<'_, Database>, id: String) -> Result<Monitor, String> {
+    db.read(move |conn| {
+        repository::get_monitor_by_id(conn, &id)
+            .map_err(|e| format!("Failed to get monitor: {}", e))
+    })
+    .await
 }
 
-/// Update an existing monitor
+/// Update an existing monitor and return it as stored.
+/// The earlier remediation confirmation is kept while it still covers the script of the button.
 #[tauri::command]
 pub async fn update_monitor(
-    db: State<'_, DbConnection>,
+    db: State<'_, Database>,
     id: String,
     monitor: Monitor,
-) -> Result<(), String> {
-    let conn = db.0.lock().map_err(|e| e.to_string())?;
+    remediation_confirmed: Option<bool>,
+) -> Result<Monitor, MonitorError> {
+    let mut monitor = monitor;
+    prepare_monitor(&mut monitor)?;
 
-    repository::update_monitor(&conn, &id, &monitor)
-        .map_err(|e| format!("Failed to update monitor: {}", e))
+    let monitor = db
+        .write(move |conn| {
+            let existing = repository::get_monitor_by_id(conn, &id)
+                .map_err(|e| format!("Failed to get monitor: {}", e))?;
+            let previous = existing
+                .remediation_confirmed_script
+                .filter(|_| existing.remediation_button_id == monitor.remediation_button_id);
+            monitor.remediation_confirmed_script =
+                confirm_remediation(conn, &monitor, remediation_confirmed.unwrap_or(false), previous)?;
+            repository::update_monitor(conn, &id, &monitor)
+                .map_err(|e| format!("Failed to update monitor: {}", e))?;
+            Ok(Monitor { id, ..monitor })
+        })
+        .await?;
+    Ok(monitor)
 }
 
 /// Delete a monitor
 #[tauri::command]
 pub async fn delete_monitor(
-    db: State<'_, DbConnection>,
+    db: State<'_, Database>,
     manager: State<'_, MonitorManagerState>,
     id: String,
 ) -> Result<(), String> {
@@ -72,80 +144,285 @@ pub async fn delete_monitor(
     let _ = manager.0.stop_monitor(&id).await;
 
     // Delete from database
-    let conn = db.0.lock().map_err(|e| e.to_string())?;
-    repository::delete_monitor(&conn, &id)
-        .map_err(|e| format!("Failed to delete monitor: {}", e))
+    db.write(move |conn| {
+        repository::delete_monitor(conn, &id)
+            .map_err(|e| format!("Failed to delete monitor: {}", e))
+    })
+    .await
 }
 
 /// Start a monitor
 #[tauri::command]
 pub async fn start_monitor(
-    db: State<'_, DbConnection>,
+    db: State<'_, Database>,
     manager: State<'_, MonitorManagerState>,
     app_handle: tauri::AppHandle,
     id: String,
 ) -> Result<(), String> {
     // Get monitor from database
-    let monitor = {
-        let conn = db.0.lock().map_err(|e| e.to_string())?;
-        repository::get_monitor_by_id(&conn, &id)
-            .map_err(|e| format!("Failed to get monitor: {}", e))?
-    };
-
-    // Get database path
-    let db_path = app_handle
-        .path()
-        .app_data_dir()
-        .map_err(|e| format!("Failed to get app data dir: {}", e))?
-        .join("dev-tools.db")
-        .to_string_lossy()
-        .to_string();
+    let monitor_id = id.clone();
+    let monitor = db
+        .read(move |conn| {
+            repository::get_monitor_by_id(conn, &monitor_id)
+                .map_err(|e| format!("Failed to get monitor: {}", e))
+        })
+        .await?;
 
     // Start the monitor
-    manager.0.start_monitor(monitor, app_handle, db_path).await?;
+    manager.0.start_monitor(monitor, app_handle).await?;
 
     // Update is_active in database
-    {
-        let conn = db.0.lock().map_err(|e| e.to_string())?;
-        let mut updated_monitor = repository::get_monitor_by_id(&conn, &id)
-            .map_err(|e| format!("Failed to get monitor: {}", e))?;
-        updated_monitor.is_active = true;
-        repository::update_monitor(&conn, &id, &updated_monitor)
-            .map_err(|e| format!("Failed to update monitor status: {}", e))?;
-    }
-
-    Ok(())
+    set_monitor_active(&db, id, true).await
 }
 
 /// Stop a monitor
 #[tauri::command]
 pub async fn stop_monitor(
-    db: State<'_, DbConnection>,
+    db: State<'_, Database>,
     manager: State<'_, MonitorManagerState>,
     id: String,
 ) -> Result<(), String> {
-    // Stop the monitor
-    manager.0.stop_monitor(&id).await?;
+    // Stop the monitor; it may be active without running when auto-start is off
+    let _ = manager.0.stop_monitor(&id).await;
 
     // Update is_active in database
-    let conn = db.0.lock().map_err(|e| e.to_string())?;
-    let mut monitor = repository::get_monitor_by_id(&conn, &id)
-        .map_err(|e| format!("Failed to get monitor: {}", e))?;
-    monitor.is_active = false;
-    repository::update_monitor(&conn, &id, &monitor)
-        .map_err(|e| format!("Failed to update monitor status: {}", e))?;
-
-    Ok(())
+    set_monitor_active(&db, id, false).await
 }
 
 /// Get logs for a specific monitor
 #[tauri::command]
 pub async fn get_monitor_logs(
-    db: State<'_, DbConnection>,
+    db: State<'_, Database>,
     monitor_id: String,
 ) -> Result<Vec<crate::database::models::LogEntry>, String> {
-    let conn = db.0.lock().map_err(|e| e.to_string())?;
+    db.read(move |conn| {
+        repository::get_logs_by_monitor(conn, &monitor_id)
+            .map_err(|e| format!("Failed to get monitor logs: {}", e))
+    })
+    .await
+}
+
+/// Get uptime, incident count and latency of a monitor over a time range
+#[tauri::command]
+pub async fn get_monitor_stats(
+    db: State<'_, Database>,
+    monitor_id: String,
+    from: i64,
+    to: i64,
+) -> Result<MonitorStats, String> {
+    validate_range(from, to)?;
+    db.read(move |conn| {
+        repository::get_monitor_stats(conn, &monitor_id, from, to)
+            .map_err(|e| format!("Failed to get monitor stats: {}", e))
+    })
+    .await
+}
+
+/// Get individual checks of a monitor over a time range, newest first
+#[tauri::command]
+pub async fn get_monitor_checks(
+    db: State<'_, Database>,
+    monitor_id: String,
+    from: i64,
+    to: i64,
+    limit: Option<u32>,
+) -> Result<Vec<MonitorCheck>, String> {
+    validate_range(from, to)?;
+    let limit = limit.unwrap_or(DEFAULT_CHECK_LIMIT);
+    if limit == 0 || limit > MAX_CHECK_LIMIT {
+        return Err(format!("limit must be between 1 and {}", MAX_CHECK_LIMIT));
+    }
+    db.read(move |conn| {
+        repository::get_monitor_checks(conn, &monitor_id, from, to, limit)
+            .map_err(|e| format!("Failed to get monitor checks: {}", e))
+    })
+    .await
+}
+
+/// Get the check history of a monitor downsampled to at most `max_points` buckets
+#[tauri::command]
+pub async fn get_monitor_check_history(
+    db: State<'_, Database>,
+    monitor_id: String,
+    from: i64,
+    to: i64,
+    max_points: Option<u32>,
+) -> Result<MonitorCheckHistory, String> {
+    validate_range(from, to)?;
+    let max_points = max_points.unwrap_or(DEFAULT_HISTORY_POINTS);
+    if max_points == 0 || max_points > MAX_HISTORY_POINTS {
+        return Err(format!("max_points must be between 1 and {}", MAX_HISTORY_POINTS));
+    }
+    let bucket_secs = bucket_secs(from, to, max_points);
+    db.read(move |conn| {
+        let buckets = repository::get_monitor_check_buckets(conn, &monitor_id, from, to, bucket_secs)
+            .map_err(|e| format!("Failed to get monitor check history: {}", e))?;
+        Ok(MonitorCheckHistory { bucket_secs, buckets })
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::models::LogEntry;
+    use crate::database::{log_entry, TestDb, DB_FILE_NAME};
+    use crate::monitor::MonitorConfig;
+
+    const MONITOR: &str = "m1";
+
+    fn open_db() -> TestDb {
+        let db = TestDb::open();
+        db.conn()
+            .unwrap()
+            .execute(
+                "INSERT INTO monitors (id, name, monitor_type, target, check_interval, alert_on_failure, is_active, created_at)
+                 VALUES (?1, 'Staging API', 'api', 'http://localhost', 60, 0, 0, 0)",
+                [MONITOR],
+            )
+            .unwrap();
+        db
+    }
+
+    #[test]
+    fn test_delete_monitor_keeps_its_logs() {
+        let db = open_db();
+        let conn = db.conn().unwrap();
+        repository::create_monitor_check(&conn, MONITOR, 1000, false, None, Some("timeout")).unwrap();
+        let log = LogEntry {
+            monitor_id: Some(MONITOR.to_string()),
+            ..log_entry(None, "error", "Monitor 'Staging API' check failed: timeout", 1000)
+        };
+        repository::create_log(&conn, &log).unwrap();
+
+        repository::delete_monitor(&conn, MONITOR).unwrap();
+
+        assert!(repository::get_monitor_by_id(&conn, MONITOR).is_err());
+        let logs = repository::get_all_logs(&conn).unwrap();
+        assert_eq!(logs.len(), 1);
+        assert_eq!(logs[0].monitor_id, None);
+        let checks: i64 = conn
+            .query_row("SELECT COUNT(*) FROM monitor_checks WHERE monitor_id = ?1", [MONITOR], |row| row.get(0))
+            .unwrap();
+        assert_eq!(checks, 0);
+    }
+
+    #[test]
+    fn test_monitor_stats() {
+        let db = open_db();
+        let conn = db.conn().unwrap();
+        // Two incidents: checks 3-4 and check 8 failed
+        let healthy = [true, true, false, false, true, true, true, false, true, true];
+        for (i, ok) in healthy.iter().enumerate() {
+            let latency = (*ok).then_some((i as i64 + 1) * 10);
+            repository::create_monitor_check(&conn, MONITOR, 1000 + i as i64, *ok, latency, None).unwrap();
+        }
+
+        let stats = repository::get_monitor_stats(&conn, MONITOR, 1000, 1009).unwrap();
+        assert_eq!(stats.total_checks, 10);
+        assert_eq!(stats.healthy_checks, 7);
+        assert_eq!(stats.uptime_percent, Some(70.0));
+        assert_eq!(stats.incidents, 2);
+        // Latencies 10, 20, 50, 60, 70, 90, 100
+        assert_eq!(stats.avg_latency_ms, Some(400.0 / 7.0));
+        assert_eq!(stats.p95_latency_ms, Some(100));
 
-    repository::get_logs_by_monitor(&conn, &monitor_id)
-        .map_err(|e| format!("Failed to get monitor logs: {}", e))
+        // A range starting inside an outage counts it as an incident
+        let partial = repository::get_monitor_stats(&conn, MONITOR, 1003, 1005).unwrap();
+        assert_eq!(partial.incidents, 1);
+
+        let empty = repository::get_monitor_stats(&conn, MONITOR, 0, 10).unwrap();
+        assert_eq!(empty.uptime_percent, None);
+        assert_eq!(empty.p95_latency_ms, None);
+
+        // Checks are removed together with their monitor
+        repository::delete_monitor(&conn, MONITOR).unwrap();
+        assert_eq!(repository::get_monitor_checks(&conn, MONITOR, 0, i64::MAX, 10).unwrap().len(), 0);
+    }
+
+    #[test]
+    fn test_check_history_buckets() {
+        let db = open_db();
+        let conn = db.conn().unwrap();
+        for i in 0..100 {
+            repository::create_monitor_check(&conn, MONITOR, i, i % 10 != 0, Some(i), None).unwrap();
+        }
+
+        let bucket = bucket_secs(0, 99, 10);
+        assert_eq!(bucket, 10);
+        let buckets = repository::get_monitor_check_buckets(&conn, MONITOR, 0, 99, bucket).unwrap();
+        assert_eq!(buckets.len(), 10);
+        assert_eq!(buckets[3].start, 30);
+        assert_eq!(buckets[3].total_checks, 10);
+        assert_eq!(buckets[3].healthy_checks, 9);
+        assert_eq!(buckets[3].max_latency_ms, Some(39));
+
+        // Short ranges are not split below one second
+        assert_eq!(bucket_secs(0, 9, 200), 1);
+        assert_eq!(bucket_secs(0, 100, 10), 11);
+    }
+
+    #[test]
+    fn test_legacy_targets_are_migrated() {
+        let test_db = open_db();
+
+        // Opening the database again converts the target string inserted without a config
+        let db = Database::open(&test_db.dir().join(DB_FILE_NAME)).unwrap();
+        let conn = db.conn().unwrap();
+        let stored: String = conn
+            .query_row("SELECT config FROM monitors WHERE id = ?1", [MONITOR], |row| row.get(0))
+            .unwrap();
+        let config: MonitorConfig = serde_json::from_str(&stored).unwrap();
+        assert_eq!(config.summary(), "http://localhost");
+        assert_eq!(config.monitor_type(), "api");
+
+        let mut monitor = repository::get_monitor_by_id(&conn, MONITOR).unwrap();
+        assert_eq!(monitor.config, Some(config));
+
+        // Invalid changes are rejected field by field
+        monitor.config = Some(serde_json::from_value(serde_json::json!({"type": "port", "host": "", "port": 0})).unwrap());
+        monitor.check_interval = 0;
+        let error = prepare_monitor(&mut monitor).unwrap_err();
+        let fields: Vec<&str> = error.fields.iter().map(|f| f.field.as_str()).collect();
+        assert_eq!(fields, vec!["check_interval", "config.host", "config.port"]);
+    }
+
+    #[test]
+    fn test_unreadable_configs_are_reported() {
+        let test_db = open_db();
+        let conn = test_db.conn().unwrap();
+        let target = serde_json::json!({"url": "http://a", "headers": [{"key": "X-Id", "value": 7}]});
+        conn.execute(
+            "INSERT INTO monitors (id, name, monitor_type, target, check_interval, alert_on_failure, is_active, created_at)
+             VALUES ('m2', 'Legacy', 'api', ?1, 60, 0, 0, 0)",
+            [target.to_string()],
+        )
+        .unwrap();
+
+        // The row is left as it was and saving it points at the config
+        repository::migrate_monitor_configs(&conn).unwrap();
+        let config: Option<String> = conn
+            .query_row("SELECT config FROM monitors WHERE id = 'm2'", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(config, None);
+        let mut monitor = repository::get_monitor_by_id(&conn, "m2").unwrap();
+        assert_eq!(monitor.config, None);
+        let error = prepare_monitor(&mut monitor).unwrap_err();
+        assert_eq!(error.fields[0].field, "config");
+
+        // So is a row of an unknown type
+        conn.execute(
+            "INSERT INTO monitors (id, name, monitor_type, target, check_interval, alert_on_failure, is_active, created_at)
+             VALUES ('m3', 'Ping', 'ping', '10.0.0.1', 60, 0, 0, 0)",
+            [],
+        )
+        .unwrap();
+        repository::migrate_monitor_configs(&conn).unwrap();
+        assert_eq!(repository::get_monitor_by_id(&conn, "m3").unwrap().config, None);
+
+        // A stored config that cannot be read is an error instead of a silent fallback
+        conn.execute("UPDATE monitors SET config = '{\"type\": \"api\"' WHERE id = ?1", [MONITOR])
+            .unwrap();
+        assert!(repository::get_monitor_by_id(&conn, MONITOR).is_err());
+    }
 }
@@ -0,0 +1,99 @@
+use tauri::{AppHandle, State};
+
+use crate::database::models::NotificationChannel;
+use crate::database::repository;
+use crate::database::Database;
+use crate::notifications::{self, channels, ChannelMessage};
+
+/// List all notification channels
+#[tauri::command]
+pub async fn list_notification_channels(db: State<'_, Database>) -> Result<Vec<NotificationChannel>, String> {
+    db.read(|conn| {
+        repository::get_all_notification_channels(conn)
+            .map_err(|e| format!("Failed to get notification channels: {}", e))
+    })
+    .await
+}
+
+/// Create a notification channel
+#[tauri::command]
+pub async fn create_notification_channel(
+    channel: NotificationChannel,
+    db: State<'_, Database>,
+) -> Result<NotificationChannel, String> {
+    channels::validate_channel(&channel)?;
+
+    let now = chrono::Utc::now().timestamp();
+    let new_channel = NotificationChannel {
+        id: uuid::Uuid::new_v4().to_string(),
+        created_at: now,
+        updated_at: now,
+        ..channel
+    };
+
+    let stored = new_channel.clone();
+    db.write(move |conn| {
+        repository::create_notification_channel(conn, &stored)
+            .map_err(|e| format!("Failed to create notification channel: {}", e))
+    })
+    .await?;
+
+    Ok(new_channel)
+}
+
+/// Update a notification channel
+#[tauri::command]
+pub async fn update_notification_channel(
+    id: String,
+    channel: NotificationChannel,
+    db: State<'_, Database>,
+) -> Result<NotificationChannel, String> {
+    channels::validate_channel(&channel)?;
+
+    let updated = NotificationChannel {
+        id: id.clone(),
+        updated_at: chrono::Utc::now().timestamp(),
+        ..channel
+    };
+    db.write(move |conn| {
+        repository::update_notification_channel(conn, &id, &updated)
+            .map_err(|e| format!("Failed to update notification channel: {}", e))?;
+        repository::get_notification_channel_by_id(conn, &id)
+            .map_err(|e| format!("Failed to get notification channel: {}", e))
+    })
+    .await
+}
+
+/// Delete a notification channel
+#[tauri::command]
+pub async fn delete_notification_channel(id: String, db: State<'_, Database>) -> Result<(), String> {
+    db.write(move |conn| {
+        repository::delete_notification_channel(conn, &id)
+            .map_err(|e| format!("Failed to delete notification channel: {}", e))
+    })
+    .await
+}
+
+/// Send a test message to a channel, returning the number of attempts it took
+#[tauri::command]
+pub async fn test_notification_channel(
+    id: String,
+    app_handle: AppHandle,
+    db: State<'_, Database>,
+) -> Result<u32, String> {
+    let channel = db
+        .read(move |conn| {
+            repository::get_notification_channel_by_id(conn, &id)
+                .map_err(|e| format!("Failed to get notification channel: {}", e))
+        })
+        .await?;
+
+    let message = ChannelMessage {
+        event: "test".to_string(),
+        title: "DevTools test notification".to_string(),
+        message: format!("Channel '{}' is set up correctly", channel.name),
+        source: channel.name.clone(),
+        timestamp: chrono::Utc::now().timestamp(),
+    };
+    notifications::send_to_channel(&app_handle, &channel, &message).await
+}
@@ -0,0 +1,166 @@
+use serde::Serialize;
+use std::sync::Arc;
+use tauri::{AppHandle, State};
+
+use crate::database::models::Schedule;
+use crate::database::repository;
+use crate::database::Database;
+use crate::executor::preflight;
+use crate::scheduler::{CronSchedule, ScheduleManager};
+
+/// Shared schedule manager state
+pub struct ScheduleManagerState(pub Arc<ScheduleManager>);
+
+/// Schedule with its computed next run time
+#[derive(Debug, Serialize)]
+pub struct ScheduleInfo {
+    #[serde(flatten)]
+    pub schedule: Schedule,
+    pub next_run_at: Option<i64>, // None when disabled or the expression never fires again
+    pub error: Option<String>,    // Parse error of the stored expression, if any
+}
+
+impl ScheduleInfo {
+    fn new(schedule: Schedule) -> Self {
+        let (next_run_at, error) = match CronSchedule::parse(&schedule.cron_expression, &schedule.timezone) {
+            Ok(cron) if schedule.enabled => (cron.next_after(chrono::Utc::now()).map(|t| t.timestamp()), None),
+            Ok(_) => (None, None),
+            Err(e) => (None, Some(e)),
+        };
+        Self {
+            schedule,
+            next_run_at,
+            error,
+        }
+    }
+}
+
+/// List all schedules with their next run times
+#[tauri::command]
+pub async fn list_schedules(db: State<'_, Database>) -> Result<Vec<ScheduleInfo>, String> {
+    let schedules = db
+        .read(|conn| {
+            repository::get_all_schedules(conn)
+                .map_err(|e| format!("Failed to get schedules: {}", e))
+        })
+        .await?;
+
+    Ok(schedules.into_iter().map(ScheduleInfo::new).collect())
+}
+
+/// Validate a cron expression and return its next fire times
+#[tauri::command]
+pub async fn preview_cron_expression(
+    cron_expression: String,
+    timezone: String,
+    count: Option<usize>,
+) -> Result<Vec<i64>, String> {
+    let cron = CronSchedule::parse(&cron_expression, &timezone)?;
+
+    Ok(cron
+        .upcoming(chrono::Utc::now(), count.unwrap_or(5).min(50))
+        .into_iter()
+        .map(|t| t.timestamp())
+        .collect())
+}
+
+/// Create a schedule and start it if enabled.
+/// Buttons needing confirmation must be confirmed here, since nobody confirms the scheduled runs.
+#[tauri::command]
+pub async fn create_schedule(
+    schedule: Schedule,
+    confirmed: Option<bool>,
+    app_handle: AppHandle,
+    db: State<'_, Database>,
+    manager: State<'_, ScheduleManagerState>,
+) -> Result<ScheduleInfo, String> {
+    CronSchedule::parse(&schedule.cron_expression, &schedule.timezone)?;
+
+    let now = chrono::Utc::now().timestamp();
+    let new_schedule = Schedule {
+        id: uuid::Uuid::new_v4().to_string(),
+        last_run_at: None,
+        created_at: now,
+        updated_at: now,
+        ..schedule
+    };
+
+    let new_schedule = db
+        .write(move |conn| {
+            let button = repository::get_button_by_id(conn, &new_schedule.button_id)
+                .map_err(|e| format!("Failed to get button: {}", e))?;
+            let stored = Schedule {
+                confirmed_script: preflight::confirm_unattended(&button, confirmed.unwrap_or(false), None)?,
+                ..new_schedule
+            };
+            repository::create_schedule(conn, &stored)
+                .map_err(|e| format!("Failed to create schedule: {}", e))?;
+            Ok(stored)
+        })
+        .await?;
+
+    if new_schedule.enabled {
+        manager.0.start_schedule(new_schedule.clone(), app_handle).await?;
+    }
+
+    Ok(ScheduleInfo::new(new_schedule))
+}
+
+/// Update a schedule and restart or stop its task.
+/// The earlier confirmation is kept while it still covers the script of the button.
+#[tauri::command]
+pub async fn update_schedule(
+    id: String,
+    schedule: Schedule,
+    confirmed: Option<bool>,
+    app_handle: AppHandle,
+    db: State<'_, Database>,
+    manager: State<'_, ScheduleManagerState>,
+) -> Result<ScheduleInfo, String> {
+    CronSchedule::parse(&schedule.cron_expression, &schedule.timezone)?;
+
+    let schedule_id = id.clone();
+    let updated = db
+        .write(move |conn| {
+            let existing = repository::get_schedule_by_id(conn, &schedule_id)
+                .map_err(|e| format!("Failed to get schedule: {}", e))?;
+            let button = repository::get_button_by_id(conn, &schedule.button_id)
+                .map_err(|e| format!("Failed to get button: {}", e))?;
+            let previous = existing.confirmed_script.filter(|_| existing.button_id == schedule.button_id);
+            let updated = Schedule {
+                id: schedule_id.clone(),
+                confirmed_script: preflight::confirm_unattended(&button, confirmed.unwrap_or(false), previous)?,
+                updated_at: chrono::Utc::now().timestamp(),
+                ..schedule
+            };
+            repository::update_schedule(conn, &schedule_id, &updated)
+                .map_err(|e| format!("Failed to update schedule: {}", e))?;
+            repository::get_schedule_by_id(conn, &schedule_id)
+                .map_err(|e| format!("Failed to get schedule: {}", e))
+        })
+        .await?;
+
+    if updated.enabled {
+        manager.0.start_schedule(updated.clone(), app_handle).await?;
+    } else {
+        manager.0.stop_schedule(&id).await;
+    }
+
+    Ok(ScheduleInfo::new(updated))
+}
+
+/// Delete a schedule and stop its task
+#[tauri::command]
+pub async fn delete_schedule(
+    id: String,
+    db: State<'_, Database>,
+    manager: State<'_, ScheduleManagerState>,
+) -> Result<(), String> {
+    manager.0.stop_schedule(&id).await;
+
+    db.write(move |conn| {
+        repository::delete_schedule(conn, &id)
+            .map_err(|e| format!("Failed to delete schedule: {}", e))
+    })
+    .await
+}
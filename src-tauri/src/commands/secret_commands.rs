@@ -0,0 +1,76 @@
+use std::sync::Arc;
+use tauri::State;
+
+use crate::database::models::SecretInfo;
+use crate::database::repository;
+use crate::database::Database;
+use crate::secrets::{SecretStatus, SecretStore};
+
+/// Shared secret store state
+pub struct SecretStoreState(pub Arc<SecretStore>);
+
+/// List secret names (values are never returned)
+#[tauri::command]
+pub async fn list_secrets(db: State<'_, Database>) -> Result<Vec<SecretInfo>, String> {
+    db.read(move |conn| {
+        repository::list_secrets(conn)
+            .map_err(|e| format!("Failed to list secrets: {}", e))
+    })
+    .await
+}
+
+/// Create or update a secret
+#[tauri::command]
+pub async fn set_secret(
+    db: State<'_, Database>,
+    secrets: State<'_, SecretStoreState>,
+    name: String,
+    value: String,
+) -> Result<(), String> {
+    let store = secrets.0.clone();
+    db.write(move |conn| store.set_secret(conn, &name, &value)).await
+}
+
+/// Delete a secret
+#[tauri::command]
+pub async fn delete_secret(
+    db: State<'_, Database>,
+    secrets: State<'_, SecretStoreState>,
+    name: String,
+) -> Result<(), String> {
+    let store = secrets.0.clone();
+    db.write(move |conn| store.delete_secret(conn, &name)).await
+}
+
+/// Get lock state and key source of the secret store
+#[tauri::command]
+pub async fn get_secret_status(
+    secrets: State<'_, SecretStoreState>,
+) -> Result<SecretStatus, String> {
+    Ok(secrets.0.status())
+}
+
+/// Unlock a password protected secret store
+#[tauri::command]
+pub async fn unlock_secrets(
+    db: State<'_, Database>,
+    secrets: State<'_, SecretStoreState>,
+    password: String,
+) -> Result<SecretStatus, String> {
+    // Key derivation is slow on purpose, keep it off the async runtime
+    let store = secrets.0.clone();
+    db.read(move |conn| store.unlock(conn, &password)).await?;
+    Ok(secrets.0.status())
+}
+
+/// Protect the secret store with a master password
+#[tauri::command]
+pub async fn set_master_password(
+    db: State<'_, Database>,
+    secrets: State<'_, SecretStoreState>,
+    password: String,
+) -> Result<SecretStatus, String> {
+    let store = secrets.0.clone();
+    db.write(move |conn| store.set_master_password(conn, &password)).await?;
+    Ok(secrets.0.status())
+}
@@ -0,0 +1,79 @@
+use std::sync::Arc;
+use tauri::State;
+
+use crate::database::repository;
+use crate::database::Database;
+use crate::monitor::{auto_start_enabled, SETTING_AUTO_START_MONITORS};
+use crate::notifications::{notifications_enabled, SETTING_NOTIFICATIONS_ENABLED};
+use crate::redaction::{self, RedactionConfig, Redactor};
+
+/// Shared log redactor state
+pub struct RedactorState(pub Arc<Redactor>);
+
+/// Get the log redaction configuration
+#[tauri::command]
+pub async fn get_redaction_config(
+    db: State<'_, Database>,
+) -> Result<RedactionConfig, String> {
+    db.read(|conn| Ok(redaction::load_config(conn))).await
+}
+
+/// Validate, save and apply a new log redaction configuration
+#[tauri::command]
+pub async fn update_redaction_config(
+    db: State<'_, Database>,
+    redactor: State<'_, RedactorState>,
+    config: RedactionConfig,
+) -> Result<(), String> {
+    redaction::validate_config(&config)?;
+
+    let json = serde_json::to_string(&config).map_err(|e| e.to_string())?;
+    db.write(move |conn| {
+        repository::set_setting(conn, redaction::SETTING_REDACTION_CONFIG, &json)
+            .map_err(|e| format!("Failed to save redaction config: {}", e))
+    })
+    .await?;
+
+    redactor.0.reload(&config)
+}
+
+/// Preview how a line would be redacted with the active rules
+#[tauri::command]
+pub async fn preview_redaction(
+    redactor: State<'_, RedactorState>,
+    text: String,
+) -> Result<String, String> {
+    Ok(redactor.0.redact(&text))
+}
+
+/// Whether active monitors are restarted when the app launches
+#[tauri::command]
+pub async fn get_auto_start_monitors(db: State<'_, Database>) -> Result<bool, String> {
+    db.read(|conn| Ok(auto_start_enabled(conn))).await
+}
+
+/// Enable or disable restarting active monitors at launch
+#[tauri::command]
+pub async fn set_auto_start_monitors(db: State<'_, Database>, enabled: bool) -> Result<(), String> {
+    db.write(move |conn| {
+        repository::set_setting(conn, SETTING_AUTO_START_MONITORS, if enabled { "true" } else { "false" })
+            .map_err(|e| format!("Failed to save setting: {}", e))
+    })
+    .await
+}
+
+/// Whether desktop notifications are shown
+#[tauri::command]
+pub async fn get_notifications_enabled(db: State<'_, Database>) -> Result<bool, String> {
+    db.read(|conn| Ok(notifications_enabled(conn))).await
+}
+
+/// Enable or disable desktop notifications
+#[tauri::command]
+pub async fn set_notifications_enabled(db: State<'_, Database>, enabled: bool) -> Result<(), String> {
+    db.write(move |conn| {
+        repository::set_setting(conn, SETTING_NOTIFICATIONS_ENABLED, if enabled { "true" } else { "false" })
+            .map_err(|e| format!("Failed to save setting: {}", e))
+    })
+    .await
+}
@@ -2,22 +2,29 @@ use rusqlite::{Connection, Result};
 use tauri::Manager;
 
 pub mod models;
+mod pool;
+mod writer;
 pub mod repository;
+#[cfg(test)]
+mod test_db;
 
-pub fn init_database(app_handle: &tauri::AppHandle) -> Result<Connection> {
+pub use pool::Database;
+#[cfg(test)]
+pub use test_db::{insert_button, insert_log, log_entry, TestDb};
+
+/// Database file inside the app data directory
+pub const DB_FILE_NAME: &str = "devtools.db";
+
+pub fn init_database(app_handle: &tauri::AppHandle) -> std::result::Result<Database, String> {
     let app_dir = app_handle
         .path()
         .app_data_dir()
-        .expect("Failed to get app data dir");
-
-    std::fs::create_dir_all(&app_dir).expect("Failed to create app data directory");
+        .map_err(|e| format!("Failed to get app data dir: {}", e))?;
 
-    let db_path = app_dir.join("devtools.db");
-    let conn = Connection::open(db_path)?;
+    std::fs::create_dir_all(&app_dir)
+        .map_err(|e| format!("Failed to create app data directory: {}", e))?;
 
-    create_tables(&conn)?;
-
-    Ok(conn)
+    Database::open(&app_dir.join(DB_FILE_NAME))
 }
 
 fn create_tables(conn: &Connection) -> Result<()> {
@@ -33,6 +40,9 @@ fn create_tables(conn: &Connection) -> Result<()> {
             position INTEGER NOT NULL,
             created_at INTEGER NOT NULL,
             updated_at INTEGER NOT NULL,
+            requires_confirmation BOOLEAN NOT NULL DEFAULT 0,
+            retry_policy TEXT,
+            notifications_muted BOOLEAN NOT NULL DEFAULT 0,
             FOREIGN KEY (folder_id) REFERENCES folders(id)
         )",
         [],
@@ -67,15 +77,38 @@ fn create_tables(conn: &Connection) -> Result<()> {
             folder_id TEXT,
             position INTEGER NOT NULL DEFAULT 0,
             created_at INTEGER NOT NULL,
+            remediation_button_id TEXT,
+            remediation_threshold INTEGER NOT NULL DEFAULT 3,
+            remediation_cooldown_secs INTEGER NOT NULL DEFAULT 300,
+            degraded_threshold_ms INTEGER,
+            alert_threshold INTEGER NOT NULL DEFAULT 1,
+            alert_renotify_secs INTEGER,
+            notifications_muted BOOLEAN NOT NULL DEFAULT 0,
+            config TEXT,
+            remediation_confirmed_script TEXT,
             FOREIGN KEY (folder_id) REFERENCES folders(id)
         )",
         [],
     )?;
 
     // 迁移：为旧表添加新字段
+    let _ = conn.execute("ALTER TABLE buttons ADD COLUMN requires_confirmation BOOLEAN NOT NULL DEFAULT 0", []);
+    let _ = conn.execute("ALTER TABLE buttons ADD COLUMN retry_policy TEXT", []);
     let _ = conn.execute("ALTER TABLE monitors ADD COLUMN icon TEXT", []);
     let _ = conn.execute("ALTER TABLE monitors ADD COLUMN folder_id TEXT REFERENCES folders(id)", []);
     let _ = conn.execute("ALTER TABLE monitors ADD COLUMN position INTEGER NOT NULL DEFAULT 0", []);
+    let _ = conn.execute("ALTER TABLE monitors ADD COLUMN remediation_button_id TEXT", []);
+    let _ = conn.execute("ALTER TABLE monitors ADD COLUMN remediation_threshold INTEGER NOT NULL DEFAULT 3", []);
+    let _ = conn.execute("ALTER TABLE monitors ADD COLUMN remediation_cooldown_secs INTEGER NOT NULL DEFAULT 300", []);
+    let _ = conn.execute("ALTER TABLE monitors ADD COLUMN degraded_threshold_ms INTEGER", []);
+    let _ = conn.execute("ALTER TABLE monitors ADD COLUMN alert_threshold INTEGER NOT NULL DEFAULT 1", []);
+    let _ = conn.execute("ALTER TABLE monitors ADD COLUMN alert_renotify_secs INTEGER", []);
+    let _ = conn.execute("ALTER TABLE buttons ADD COLUMN notifications_muted BOOLEAN NOT NULL DEFAULT 0", []);
+    let _ = conn.execute("ALTER TABLE monitors ADD COLUMN notifications_muted BOOLEAN NOT NULL DEFAULT 0", []);
+    let _ = conn.execute("ALTER TABLE monitors ADD COLUMN config TEXT", []);
+    let _ = conn.execute("ALTER TABLE monitors ADD COLUMN remediation_confirmed_script TEXT", []);
+    // Convert the target strings of monitors created before typed configs
+    repository::migrate_monitor_configs(conn)?;
 
     // logs 表
     conn.execute(
@@ -86,11 +119,188 @@ fn create_tables(conn: &Connection) -> Result<()> {
             level TEXT NOT NULL,
             message TEXT NOT NULL,
             timestamp INTEGER NOT NULL,
+            execution_id TEXT,
             FOREIGN KEY (button_id) REFERENCES buttons(id),
             FOREIGN KEY (monitor_id) REFERENCES monitors(id)
         )",
         [],
     )?;
+    let _ = conn.execute("ALTER TABLE logs ADD COLUMN execution_id TEXT", []);
+    // Used by log retention pruning
+    conn.execute("CREATE INDEX IF NOT EXISTS idx_logs_timestamp ON logs(timestamp)", [])?;
+    conn.execute("CREATE INDEX IF NOT EXISTS idx_logs_button ON logs(button_id, timestamp)", [])?;
+    conn.execute("CREATE INDEX IF NOT EXISTS idx_logs_monitor ON logs(monitor_id, timestamp)", [])?;
+
+    // logs_fts 表（logs.message 的全文索引，由触发器保持同步）
+    // trigram 分词支持中文及任意子串搜索
+    let fts_exists: bool = conn.query_row(
+        "SELECT EXISTS(SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = 'logs_fts')",
+        [],
+        |row| row.get(0),
+    )?;
+    conn.execute(
+        "CREATE VIRTUAL TABLE IF NOT EXISTS logs_fts USING fts5(
+            message,
+            content = 'logs',
+            content_rowid = 'rowid',
+            tokenize = 'trigram'
+        )",
+        [],
+    )?;
+    conn.execute_batch(
+        "CREATE TRIGGER IF NOT EXISTS logs_fts_insert AFTER INSERT ON logs BEGIN
+            INSERT INTO logs_fts (rowid, message) VALUES (new.rowid, new.message);
+         END;
+         CREATE TRIGGER IF NOT EXISTS logs_fts_delete AFTER DELETE ON logs BEGIN
+            INSERT INTO logs_fts (logs_fts, rowid, message) VALUES ('delete', old.rowid, old.message);
+         END;
+         CREATE TRIGGER IF NOT EXISTS logs_fts_update AFTER UPDATE OF message ON logs BEGIN
+            INSERT INTO logs_fts (logs_fts, rowid, message) VALUES ('delete', old.rowid, old.message);
+            INSERT INTO logs_fts (rowid, message) VALUES (new.rowid, new.message);
+         END;",
+    )?;
+    if !fts_exists {
+        // Index the logs written before the search table existed
+        repository::rebuild_log_search_index(conn)?;
+    }
+
+    // monitor_checks 表（每次监控检查的结果，用于可用率统计）
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS monitor_checks (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            monitor_id TEXT NOT NULL,
+            timestamp INTEGER NOT NULL,
+            healthy BOOLEAN NOT NULL,
+            latency_ms INTEGER,
+            detail TEXT,
+            FOREIGN KEY (monitor_id) REFERENCES monitors(id) ON DELETE CASCADE
+        )",
+        [],
+    )?;
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_monitor_checks_monitor ON monitor_checks(monitor_id, timestamp)",
+        [],
+    )?;
+    conn.execute("CREATE INDEX IF NOT EXISTS idx_monitor_checks_timestamp ON monitor_checks(timestamp)", [])?;
+
+    // monitor_alert_states 表（告警状态机，重启后继续）
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS monitor_alert_states (
+            monitor_id TEXT PRIMARY KEY,
+            state TEXT NOT NULL,
+            updated_at INTEGER NOT NULL,
+            FOREIGN KEY (monitor_id) REFERENCES monitors(id) ON DELETE CASCADE
+        )",
+        [],
+    )?;
+
+    // monitor_remediation_states 表（自动修复的失败计数和冷却，重启后继续）
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS monitor_remediation_states (
+            monitor_id TEXT PRIMARY KEY,
+            state TEXT NOT NULL,
+            updated_at INTEGER NOT NULL,
+            FOREIGN KEY (monitor_id) REFERENCES monitors(id) ON DELETE CASCADE
+        )",
+        [],
+    )?;
+
+    // notification_channels 表（接收告警和运行失败的 Webhook）
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS notification_channels (
+            id TEXT PRIMARY KEY,
+            name TEXT NOT NULL,
+            channel_type TEXT NOT NULL,
+            url TEXT NOT NULL,
+            template TEXT,
+            enabled BOOLEAN NOT NULL DEFAULT 1,
+            created_at INTEGER NOT NULL,
+            updated_at INTEGER NOT NULL
+        )",
+        [],
+    )?;
+
+    // executions 表（一次按钮运行，包含所有重试）
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS executions (
+            id TEXT PRIMARY KEY,
+            button_id TEXT NOT NULL,
+            trigger TEXT NOT NULL,
+            trigger_detail TEXT,
+            status TEXT NOT NULL,
+            attempts INTEGER NOT NULL DEFAULT 0,
+            exit_code INTEGER,
+            error TEXT,
+            started_at INTEGER NOT NULL,
+            finished_at INTEGER,
+            duration_ms INTEGER
+        )",
+        [],
+    )?;
+    let _ = conn.execute("ALTER TABLE executions ADD COLUMN trigger_detail TEXT", []);
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_executions_button ON executions(button_id, started_at)",
+        [],
+    )?;
+
+    // schedules 表（按钮的定时运行）
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS schedules (
+            id TEXT PRIMARY KEY,
+            button_id TEXT NOT NULL,
+            cron_expression TEXT NOT NULL,
+            timezone TEXT NOT NULL DEFAULT 'UTC',
+            enabled BOOLEAN NOT NULL DEFAULT 1,
+            last_run_at INTEGER,
+            confirmed_script TEXT,
+            created_at INTEGER NOT NULL,
+            updated_at INTEGER NOT NULL,
+            FOREIGN KEY (button_id) REFERENCES buttons(id)
+        )",
+        [],
+    )?;
+    let _ = conn.execute("ALTER TABLE schedules ADD COLUMN confirmed_script TEXT", []);
+
+    // file_triggers 表（文件变更触发按钮运行）
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS file_triggers (
+            id TEXT PRIMARY KEY,
+            button_id TEXT NOT NULL,
+            paths TEXT NOT NULL,
+            include_patterns TEXT NOT NULL DEFAULT '[]',
+            exclude_patterns TEXT NOT NULL DEFAULT '[]',
+            debounce_ms INTEGER NOT NULL DEFAULT 500,
+            enabled BOOLEAN NOT NULL DEFAULT 1,
+            confirmed_script TEXT,
+            created_at INTEGER NOT NULL,
+            updated_at INTEGER NOT NULL,
+            FOREIGN KEY (button_id) REFERENCES buttons(id)
+        )",
+        [],
+    )?;
+    let _ = conn.execute("ALTER TABLE file_triggers ADD COLUMN confirmed_script TEXT", []);
+
+    // settings 表（后端持久化的键值配置）
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS settings (
+            key TEXT PRIMARY KEY,
+            value TEXT NOT NULL,
+            updated_at INTEGER NOT NULL
+        )",
+        [],
+    )?;
+
+    // secrets 表（加密存储的凭据）
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS secrets (
+            name TEXT PRIMARY KEY,
+            nonce BLOB NOT NULL,
+            ciphertext BLOB NOT NULL,
+            created_at INTEGER NOT NULL,
+            updated_at INTEGER NOT NULL
+        )",
+        [],
+    )?;
 
     Ok(())
 }
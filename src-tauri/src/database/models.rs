@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+use crate::monitor::MonitorConfig;
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Button {
     pub id: String,
@@ -11,6 +13,27 @@ pub struct Button {
     pub position: i32,
     pub created_at: i64,
     pub updated_at: i64,
+    #[serde(default)]
+    pub requires_confirmation: bool,
+    #[serde(default)]
+    pub retry_policy: Option<RetryPolicy>,
+    #[serde(default)]
+    pub notifications_muted: bool, // No desktop notification when a long run finishes
+}
+
+/// How failed runs of a button are retried
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RetryPolicy {
+    pub max_attempts: u32,          // Total attempts including the first run
+    pub backoff_ms: u64,            // Delay before the first retry
+    #[serde(default = "default_backoff_multiplier")]
+    pub backoff_multiplier: f64,    // Delay growth per retry (1.0 = fixed delay)
+    #[serde(default)]
+    pub retry_exit_codes: Vec<i32>, // Only retry on these exit codes (empty = any failure)
+}
+
+fn default_backoff_multiplier() -> f64 {
+    2.0
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -38,6 +61,36 @@ pub struct Monitor {
     pub folder_id: Option<String>,
     pub position: i32,
     pub created_at: i64,
+    #[serde(default)]
+    pub remediation_button_id: Option<String>, // Button run automatically when the target stays down
+    #[serde(default = "default_remediation_threshold")]
+    pub remediation_threshold: u32,            // Consecutive failed checks before remediating
+    #[serde(default = "default_remediation_cooldown_secs")]
+    pub remediation_cooldown_secs: u64,        // Minimum time between two remediation runs
+    #[serde(default)]
+    pub remediation_confirmed_script: Option<String>, // Fingerprint of the remediation script confirmed to run unattended
+    #[serde(default)]
+    pub degraded_threshold_ms: Option<u64>,    // Healthy checks slower than this are reported as degraded
+    #[serde(default = "default_alert_threshold")]
+    pub alert_threshold: u32,                  // Consecutive failed checks before alerting
+    #[serde(default)]
+    pub alert_renotify_secs: Option<u64>,      // Repeat the alert while still down (None = alert once)
+    #[serde(default)]
+    pub notifications_muted: bool,             // Alerts only go to the app, not the desktop
+    #[serde(default)]
+    pub config: Option<MonitorConfig>,         // What is checked (None = derive from monitor_type and target)
+}
+
+fn default_remediation_threshold() -> u32 {
+    3
+}
+
+fn default_remediation_cooldown_secs() -> u64 {
+    300
+}
+
+fn default_alert_threshold() -> u32 {
+    1
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -48,4 +101,180 @@ pub struct LogEntry {
     pub level: String,
     pub message: String,
     pub timestamp: i64,
+    #[serde(default)]
+    pub execution_id: Option<String>,
+}
+
+/// Result of one monitor check
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MonitorCheck {
+    pub id: i64,
+    pub monitor_id: String,
+    pub timestamp: i64,
+    pub healthy: bool,
+    pub latency_ms: Option<i64>, // None when the check could not run
+    pub detail: Option<String>,  // Failure reason
+}
+
+/// Uptime and latency of a monitor over a time range
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MonitorStats {
+    pub monitor_id: String,
+    pub from: i64,
+    pub to: i64,
+    pub total_checks: u64,
+    pub healthy_checks: u64,
+    pub uptime_percent: Option<f64>, // None without checks in the range
+    pub incidents: u64,              // Runs of consecutive failed checks
+    pub avg_latency_ms: Option<f64>,
+    pub p95_latency_ms: Option<i64>,
+}
+
+/// Checks of one time bucket, used to chart long ranges
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MonitorCheckBucket {
+    pub start: i64,
+    pub total_checks: u64,
+    pub healthy_checks: u64,
+    pub avg_latency_ms: Option<f64>,
+    pub max_latency_ms: Option<i64>,
+}
+
+/// Downsampled check history of a monitor
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MonitorCheckHistory {
+    pub bucket_secs: i64,
+    pub buckets: Vec<MonitorCheckBucket>,
+}
+
+/// Filters for searching logs; every filter is optional
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LogSearchQuery {
+    pub text: Option<String>,      // Whitespace separated terms, all must appear in the message
+    pub levels: Vec<String>,       // Empty matches every level
+    pub from: Option<i64>,         // Inclusive timestamp bounds
+    pub to: Option<i64>,
+    pub button_id: Option<String>,
+    pub monitor_id: Option<String>,
+    pub execution_id: Option<String>,
+    pub cursor: Option<String>,    // next_cursor of the previous page
+    pub limit: Option<u32>,
+}
+
+/// One page of search results, newest first
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LogSearchPage {
+    pub entries: Vec<LogEntry>,
+    pub next_cursor: Option<String>, // None on the last page
+}
+
+/// Position after the last entry of a page: results continue with older logs
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogCursor {
+    pub timestamp: i64,
+    pub id: String,
+}
+
+impl LogCursor {
+    pub fn after(log: &LogEntry) -> Self {
+        Self {
+            timestamp: log.timestamp,
+            id: log.id.clone(),
+        }
+    }
+
+    pub fn encode(&self) -> String {
+        format!("{}:{}", self.timestamp, self.id)
+    }
+
+    pub fn parse(cursor: &str) -> Option<Self> {
+        let (timestamp, id) = cursor.split_once(':')?;
+        Some(Self {
+            timestamp: timestamp.parse().ok()?,
+            id: id.to_string(),
+        })
+    }
+}
+
+/// One run of a button, covering all of its retry attempts
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExecutionRecord {
+    pub id: String,
+    pub button_id: String,
+    pub trigger: String,         // "manual", "schedule", "file_watch", "remediation"
+    #[serde(default)]
+    pub trigger_detail: Option<String>,
+    pub status: String,          // "running", "success", "failed"
+    pub attempts: u32,
+    pub exit_code: Option<i32>,
+    pub error: Option<String>,
+    pub started_at: i64,
+    pub finished_at: Option<i64>,
+    pub duration_ms: Option<i64>,
+}
+
+/// Webhook that receives monitor alerts and failed runs
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NotificationChannel {
+    pub id: String,
+    pub name: String,
+    pub channel_type: String,     // "slack", "mattermost", "feishu", "dingtalk", "webhook"
+    pub url: String,              // May contain ${secret:NAME} references
+    #[serde(default)]
+    pub template: Option<String>, // JSON payload with {{placeholders}} (None = default for the type)
+    pub enabled: bool,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+/// Encrypted secret as stored in the database
+#[derive(Debug)]
+pub struct SecretRecord {
+    pub name: String,
+    pub nonce: Vec<u8>,
+    pub ciphertext: Vec<u8>,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+/// Secret metadata exposed to the frontend (never contains the value)
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SecretInfo {
+    pub name: String,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+/// Cron schedule that runs a button automatically
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Schedule {
+    pub id: String,
+    pub button_id: String,
+    pub cron_expression: String, // 5 fields, or 6/7 fields starting with seconds
+    pub timezone: String,        // IANA name, e.g. "Asia/Shanghai"
+    pub enabled: bool,
+    pub last_run_at: Option<i64>,
+    #[serde(default)]
+    pub confirmed_script: Option<String>, // Fingerprint of the script confirmed for unattended runs
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+/// File system watch that runs a button when matching files change
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileTrigger {
+    pub id: String,
+    pub button_id: String,
+    pub paths: Vec<String>,   // Files or directories to watch (directories recursively)
+    #[serde(default)]
+    pub include: Vec<String>, // Glob patterns a changed path must match (empty = everything)
+    #[serde(default)]
+    pub exclude: Vec<String>, // Glob patterns that are ignored
+    pub debounce_ms: u64,     // Quiet period before running, collecting changes in between
+    pub enabled: bool,
+    #[serde(default)]
+    pub confirmed_script: Option<String>, // Fingerprint of the script confirmed for unattended runs
+    pub created_at: i64,
+    pub updated_at: i64,
 }
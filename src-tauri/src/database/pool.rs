@@ -0,0 +1,122 @@
+use r2d2::{Pool, PooledConnection};
+use r2d2_sqlite::SqliteConnectionManager;
+use rusqlite::Connection;
+use std::path::Path;
+use std::time::Duration;
+use tokio::sync::oneshot;
+
+use super::models::LogEntry;
+use super::writer::DbWriter;
+
+/// A connection checked out of the pool, returned when dropped
+pub type DbConn = PooledConnection<SqliteConnectionManager>;
+
+const MAX_CONNECTIONS: u32 = 8;
+const CONNECTION_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Pooled SQLite database shared by commands, executors, monitors and background tasks.
+/// Reads run on the blocking thread pool, writes on a single writer thread.
+/// Cloning is cheap and every clone uses the same pool and writer.
+#[derive(Clone)]
+pub struct Database {
+    pool: Pool<SqliteConnectionManager>,
+    writer: DbWriter,
+}
+
+impl Database {
+    /// Open (or create) the database file and make sure the schema is up to date
+    pub fn open(path: &Path) -> Result<Self, String> {
+        let manager = SqliteConnectionManager::file(path).with_init(|conn| {
+            // WAL lets readers continue while a write is in progress;
+            // the busy timeout makes concurrent writers wait instead of failing
+            conn.execute_batch("PRAGMA journal_mode = WAL; PRAGMA busy_timeout = 5000;")
+        });
+
+        let pool = Pool::builder()
+            .max_size(MAX_CONNECTIONS)
+            .connection_timeout(CONNECTION_TIMEOUT)
+            .build(manager)
+            .map_err(|e| format!("Failed to open database {}: {}", path.display(), e))?;
+
+        let conn = pool
+            .get()
+            .map_err(|e| format!("Failed to get database connection: {}", e))?;
+        super::create_tables(&conn).map_err(|e| format!("Failed to create tables: {}", e))?;
+
+        // The writer keeps this connection for as long as the app runs
+        let writer = DbWriter::spawn(conn)?;
+
+        Ok(Self { pool, writer })
+    }
+
+    /// Check out a connection (blocking; prefer `read` / `write` from async code)
+    pub fn conn(&self) -> Result<DbConn, String> {
+        self.pool
+            .get()
+            .map_err(|e| format!("Failed to get database connection: {}", e))
+    }
+
+    /// Run a query on the blocking thread pool so it never stalls the async runtime
+    pub async fn read<T, F>(&self, f: F) -> Result<T, String>
+    where
+        T: Send + 'static,
+        F: FnOnce(&Connection) -> Result<T, String> + Send + 'static,
+    {
+        let pool = self.pool.clone();
+        tokio::task::spawn_blocking(move || {
+            let conn = pool
+                .get()
+                .map_err(|e| format!("Failed to get database connection: {}", e))?;
+            f(&conn)
+        })
+        .await
+        .map_err(|e| format!("Database task failed: {}", e))?
+    }
+
+    /// Run a write on the writer thread and wait for its result
+    pub async fn write<T, F>(&self, f: F) -> Result<T, String>
+    where
+        T: Send + 'static,
+        F: FnOnce(&Connection) -> Result<T, String> + Send + 'static,
+    {
+        let (reply_tx, reply_rx) = oneshot::channel();
+        self.writer.execute(move |conn| {
+            let _ = reply_tx.send(f(conn));
+        })?;
+        reply_rx
+            .await
+            .map_err(|_| "Database writer dropped the request".to_string())?
+    }
+
+    /// Queue a write without waiting for it; failures are only reported on stderr
+    pub fn write_detached<F>(&self, f: F)
+    where
+        F: FnOnce(&Connection) -> Result<(), String> + Send + 'static,
+    {
+        let result = self.writer.execute(move |conn| {
+            if let Err(e) = f(conn) {
+                eprintln!("Database write failed: {}", e);
+            }
+        });
+        if let Err(e) = result {
+            eprintln!("{}", e);
+        }
+    }
+
+    /// Queue a log entry; entries are inserted in batches
+    pub fn insert_log(&self, log: LogEntry) {
+        self.writer.insert_logs(vec![log]);
+    }
+
+    /// Queue several log entries to be inserted in one transaction
+    pub fn insert_logs(&self, logs: Vec<LogEntry>) {
+        if !logs.is_empty() {
+            self.writer.insert_logs(logs);
+        }
+    }
+
+    /// Wait until every write queued so far has been applied
+    pub async fn flush(&self) -> Result<(), String> {
+        self.write(|_| Ok(())).await
+    }
+}
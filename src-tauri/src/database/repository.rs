@@ -1,15 +1,54 @@
-use rusqlite::{Connection, Result};
-use super::models::{Button, LogEntry, Monitor};
+use rusqlite::types::{Type, Value};
+use rusqlite::{params, params_from_iter, Connection, Result, Row};
+use crate::monitor::MonitorConfig;
+use super::models::{
+    Button, ExecutionRecord, FileTrigger, LogCursor, LogEntry, LogSearchQuery, Monitor, MonitorCheck,
+    MonitorCheckBucket, MonitorStats, NotificationChannel, RetryPolicy, Schedule, SecretInfo, SecretRecord,
+};
 
 // ============================================================================
 // Button CRUD Operations
 // ============================================================================
 
+/// Parse a JSON column; unreadable JSON is an error rather than a missing value
+fn json_column<T: serde::de::DeserializeOwned>(row: &Row, index: usize) -> Result<Option<T>> {
+    row.get::<_, Option<String>>(index)?
+        .map(|json| {
+            serde_json::from_str(&json)
+                .map_err(|e| rusqlite::Error::FromSqlConversionFailure(index, Type::Text, Box::new(e)))
+        })
+        .transpose()
+}
+
+/// Map a row selected with the standard button column list
+fn button_from_row(row: &Row) -> Result<Button> {
+    Ok(Button {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        icon: row.get(2)?,
+        script_type: row.get(3)?,
+        script_content: row.get(4)?,
+        folder_id: row.get(5)?,
+        position: row.get(6)?,
+        created_at: row.get(7)?,
+        updated_at: row.get(8)?,
+        requires_confirmation: row.get(9)?,
+        retry_policy: json_column(row, 10)?,
+        notifications_muted: row.get(11)?,
+    })
+}
+
+/// Serialize a retry policy for the buttons.retry_policy column
+fn retry_policy_to_json(policy: &Option<RetryPolicy>) -> Option<String> {
+    policy.as_ref().and_then(|p| serde_json::to_string(p).ok())
+}
+
 /// Create a new button in the database
 pub fn create_button(conn: &Connection, button: &Button) -> Result<String> {
     conn.execute(
-        "INSERT INTO buttons (id, name, icon, script_type, script_content, folder_id, position, created_at, updated_at)
-         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+        "INSERT INTO buttons (id, name, icon, script_type, script_content, folder_id, position, created_at, updated_at,
+         requires_confirmation, retry_policy, notifications_muted)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
         (
             &button.id,
             &button.name,
@@ -20,6 +59,9 @@ pub fn create_button(conn: &Connection, button: &Button) -> Result<String> {
             &button.position,
             &button.created_at,
             &button.updated_at,
+            &button.requires_confirmation,
+            retry_policy_to_json(&button.retry_policy),
+            &button.notifications_muted,
         ),
     )?;
     Ok(button.id.clone())
@@ -28,24 +70,13 @@ pub fn create_button(conn: &Connection, button: &Button) -> Result<String> {
 /// Get all buttons ordered by position
 pub fn get_all_buttons(conn: &Connection) -> Result<Vec<Button>> {
     let mut stmt = conn.prepare(
-        "SELECT id, name, icon, script_type, script_content, folder_id, position, created_at, updated_at
+        "SELECT id, name, icon, script_type, script_content, folder_id, position, created_at, updated_at,
+                requires_confirmation, retry_policy, notifications_muted
          FROM buttons ORDER BY position",
     )?;
 
     let buttons = stmt
-        .query_map([], |row| {
-            Ok(Button {
-                id: row.get(0)?,
-                name: row.get(1)?,
-                icon: row.get(2)?,
-                script_type: row.get(3)?,
-                script_content: row.get(4)?,
-                folder_id: row.get(5)?,
-                position: row.get(6)?,
-                created_at: row.get(7)?,
-                updated_at: row.get(8)?,
-            })
-        })?
+        .query_map([], button_from_row)?
         .collect::<Result<Vec<_>>>()?;
 
     Ok(buttons)
@@ -54,23 +85,12 @@ pub fn get_all_buttons(conn: &Connection) -> Result<Vec<Button>> {
 /// Get a single button by ID
 pub fn get_button_by_id(conn: &Connection, id: &str) -> Result<Button> {
     let mut stmt = conn.prepare(
-        "SELECT id, name, icon, script_type, script_content, folder_id, position, created_at, updated_at
+        "SELECT id, name, icon, script_type, script_content, folder_id, position, created_at, updated_at,
+                requires_confirmation, retry_policy, notifications_muted
          FROM buttons WHERE id = ?1",
     )?;
 
-    let button = stmt.query_row([id], |row| {
-        Ok(Button {
-            id: row.get(0)?,
-            name: row.get(1)?,
-            icon: row.get(2)?,
-            script_type: row.get(3)?,
-            script_content: row.get(4)?,
-            folder_id: row.get(5)?,
-            position: row.get(6)?,
-            created_at: row.get(7)?,
-            updated_at: row.get(8)?,
-        })
-    })?;
+    let button = stmt.query_row([id], button_from_row)?;
 
     Ok(button)
 }
@@ -80,8 +100,9 @@ pub fn update_button(conn: &Connection, id: &str, button: &Button) -> Result<()>
     conn.execute(
         "UPDATE buttons
          SET name = ?1, icon = ?2, script_type = ?3, script_content = ?4,
-             folder_id = ?5, position = ?6, updated_at = ?7
-         WHERE id = ?8",
+             folder_id = ?5, position = ?6, updated_at = ?7, requires_confirmation = ?8,
+             retry_policy = ?9, notifications_muted = ?10
+         WHERE id = ?11",
         (
             &button.name,
             &button.icon,
@@ -90,61 +111,64 @@ pub fn update_button(conn: &Connection, id: &str, button: &Button) -> Result<()>
             &button.folder_id,
             &button.position,
             &button.updated_at,
+            &button.requires_confirmation,
+            retry_policy_to_json(&button.retry_policy),
+            &button.notifications_muted,
             id,
         ),
     )?;
     Ok(())
 }
 
-/// Delete a button by ID
-pub fn delete_button(conn: &Connection, id: &str) -> Result<()> {
-    conn.execute("DELETE FROM buttons WHERE id = ?1", [id])?;
-    Ok(())
+/// Delete a button with its schedules and file triggers
+pub fn delete_button(conn: &Connection, id: &str) -> Result<DeletedButton> {
+    let ids = |sql: &str| -> Result<Vec<String>> {
+        let mut stmt = conn.prepare(sql)?;
+        let ids = stmt.query_map([id], |row| row.get(0))?.collect();
+        ids
+    };
+
+    // Schedules and file triggers would otherwise keep firing for a button that no longer exists
+    let tx = conn.unchecked_transaction()?;
+    let deleted = DeletedButton {
+        schedule_ids: ids("SELECT id FROM schedules WHERE button_id = ?1")?,
+        file_trigger_ids: ids("SELECT id FROM file_triggers WHERE button_id = ?1")?,
+    };
+    tx.execute("DELETE FROM schedules WHERE button_id = ?1", [id])?;
+    tx.execute("DELETE FROM file_triggers WHERE button_id = ?1", [id])?;
+    tx.execute("DELETE FROM buttons WHERE id = ?1", [id])?;
+    tx.commit()?;
+    Ok(deleted)
+}
+
+/// Automations removed together with a button, whose tasks must be stopped
+#[derive(Debug, Default, PartialEq)]
+pub struct DeletedButton {
+    pub schedule_ids: Vec<String>,
+    pub file_trigger_ids: Vec<String>,
 }
 
 /// Get buttons by folder ID (None for root level)
 pub fn get_buttons_by_folder(conn: &Connection, folder_id: Option<&str>) -> Result<Vec<Button>> {
     let mut stmt = if folder_id.is_some() {
         conn.prepare(
-            "SELECT id, name, icon, script_type, script_content, folder_id, position, created_at, updated_at
+            "SELECT id, name, icon, script_type, script_content, folder_id, position, created_at, updated_at,
+                    requires_confirmation, retry_policy, notifications_muted
              FROM buttons WHERE folder_id = ?1 ORDER BY position",
         )?
     } else {
         conn.prepare(
-            "SELECT id, name, icon, script_type, script_content, folder_id, position, created_at, updated_at
+            "SELECT id, name, icon, script_type, script_content, folder_id, position, created_at, updated_at,
+                    requires_confirmation, retry_policy, notifications_muted
              FROM buttons WHERE folder_id IS NULL ORDER BY position",
         )?
     };
 
     let buttons = if let Some(fid) = folder_id {
-        stmt.query_map([fid], |row| {
-            Ok(Button {
-                id: row.get(0)?,
-                name: row.get(1)?,
-                icon: row.get(2)?,
-                script_type: row.get(3)?,
-                script_content: row.get(4)?,
-                folder_id: row.get(5)?,
-                position: row.get(6)?,
-                created_at: row.get(7)?,
-                updated_at: row.get(8)?,
-            })
-        })?
+        stmt.query_map([fid], button_from_row)?
         .collect::<Result<Vec<_>>>()?
     } else {
-        stmt.query_map([], |row| {
-            Ok(Button {
-                id: row.get(0)?,
-                name: row.get(1)?,
-                icon: row.get(2)?,
-                script_type: row.get(3)?,
-                script_content: row.get(4)?,
-                folder_id: row.get(5)?,
-                position: row.get(6)?,
-                created_at: row.get(7)?,
-                updated_at: row.get(8)?,
-            })
-        })?
+        stmt.query_map([], button_from_row)?
         .collect::<Result<Vec<_>>>()?
     };
 
@@ -155,41 +179,47 @@ pub fn get_buttons_by_folder(conn: &Connection, folder_id: Option<&str>) -> Resu
 // Log Operations
 // ============================================================================
 
+/// Map a row selected with the standard log column list
+fn log_from_row(row: &Row) -> Result<LogEntry> {
+    Ok(LogEntry {
+        id: row.get(0)?,
+        button_id: row.get(1)?,
+        monitor_id: row.get(2)?,
+        level: row.get(3)?,
+        message: row.get(4)?,
+        timestamp: row.get(5)?,
+        execution_id: row.get(6)?,
+    })
+}
+
 /// Create a new log entry
 pub fn create_log(conn: &Connection, log: &LogEntry) -> Result<String> {
-    conn.execute(
-        "INSERT INTO logs (id, button_id, monitor_id, level, message, timestamp)
-         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
-        (
-            &log.id,
-            &log.button_id,
-            &log.monitor_id,
-            &log.level,
-            &log.message,
-            &log.timestamp,
-        ),
-    )?;
+    // Cached: the database writer inserts logs in large batches
+    conn.prepare_cached(
+        "INSERT INTO logs (id, button_id, monitor_id, level, message, timestamp, execution_id)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+    )?
+    .execute((
+        &log.id,
+        &log.button_id,
+        &log.monitor_id,
+        &log.level,
+        &log.message,
+        &log.timestamp,
+        &log.execution_id,
+    ))?;
     Ok(log.id.clone())
 }
 
 /// Get all logs ordered by timestamp (newest first)
 pub fn get_all_logs(conn: &Connection) -> Result<Vec<LogEntry>> {
     let mut stmt = conn.prepare(
-        "SELECT id, button_id, monitor_id, level, message, timestamp
+        "SELECT id, button_id, monitor_id, level, message, timestamp, execution_id
          FROM logs ORDER BY timestamp DESC LIMIT 1000",
     )?;
 
     let logs = stmt
-        .query_map([], |row| {
-            Ok(LogEntry {
-                id: row.get(0)?,
-                button_id: row.get(1)?,
-                monitor_id: row.get(2)?,
-                level: row.get(3)?,
-                message: row.get(4)?,
-                timestamp: row.get(5)?,
-            })
-        })?
+        .query_map([], log_from_row)?
         .collect::<Result<Vec<_>>>()?;
 
     Ok(logs)
@@ -198,21 +228,12 @@ pub fn get_all_logs(conn: &Connection) -> Result<Vec<LogEntry>> {
 /// Get logs for a specific button
 pub fn get_logs_by_button(conn: &Connection, button_id: &str) -> Result<Vec<LogEntry>> {
     let mut stmt = conn.prepare(
-        "SELECT id, button_id, monitor_id, level, message, timestamp
+        "SELECT id, button_id, monitor_id, level, message, timestamp, execution_id
          FROM logs WHERE button_id = ?1 ORDER BY timestamp DESC LIMIT 1000",
     )?;
 
     let logs = stmt
-        .query_map([button_id], |row| {
-            Ok(LogEntry {
-                id: row.get(0)?,
-                button_id: row.get(1)?,
-                monitor_id: row.get(2)?,
-                level: row.get(3)?,
-                message: row.get(4)?,
-                timestamp: row.get(5)?,
-            })
-        })?
+        .query_map([button_id], log_from_row)?
         .collect::<Result<Vec<_>>>()?;
 
     Ok(logs)
@@ -224,17 +245,118 @@ pub fn clear_all_logs(conn: &Connection) -> Result<()> {
     Ok(())
 }
 
+/// Delete logs older than the cutoff timestamp, returning the number of rows removed
+pub fn delete_logs_before(conn: &Connection, cutoff: i64) -> Result<usize> {
+    conn.execute("DELETE FROM logs WHERE timestamp < ?1", [cutoff])
+}
+
+/// Keep only the newest `keep` logs of every button and of every monitor
+pub fn prune_logs_per_source(conn: &Connection, keep: u64) -> Result<usize> {
+    let mut removed = 0;
+    for column in ["button_id", "monitor_id"] {
+        removed += conn.execute(
+            &format!(
+                "DELETE FROM logs WHERE id IN (
+                     SELECT id FROM (
+                         SELECT id, ROW_NUMBER() OVER (
+                             PARTITION BY {column} ORDER BY timestamp DESC, rowid DESC
+                         ) AS rn
+                         FROM logs WHERE {column} IS NOT NULL
+                     ) WHERE rn > ?1
+                 )"
+            ),
+            [keep as i64],
+        )?;
+    }
+    Ok(removed)
+}
+
+/// Keep only the newest `keep` logs overall
+pub fn prune_logs_total(conn: &Connection, keep: u64) -> Result<usize> {
+    conn.execute(
+        "DELETE FROM logs WHERE id IN (
+             SELECT id FROM logs ORDER BY timestamp DESC, rowid DESC LIMIT -1 OFFSET ?1
+         )",
+        [keep as i64],
+    )
+}
+
 // ============================================================================
 // Monitor CRUD Operations
 // ============================================================================
 
+/// Map a row selected with the standard monitor column list
+fn monitor_from_row(row: &Row) -> Result<Monitor> {
+    let mut monitor = Monitor {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        icon: row.get(2)?,
+        monitor_type: row.get(3)?,
+        target: row.get(4)?,
+        check_interval: row.get(5)?,
+        expected_result: row.get(6)?,
+        alert_on_failure: row.get(7)?,
+        is_active: row.get(8)?,
+        last_check_time: row.get(9)?,
+        last_status: row.get(10)?,
+        folder_id: row.get(11)?,
+        position: row.get(12)?,
+        created_at: row.get(13)?,
+        remediation_button_id: row.get(14)?,
+        remediation_threshold: row.get(15)?,
+        remediation_cooldown_secs: row.get(16)?,
+        degraded_threshold_ms: row.get(17)?,
+        alert_threshold: row.get(18)?,
+        alert_renotify_secs: row.get(19)?,
+        notifications_muted: row.get(20)?,
+        config: json_column(row, 21)?,
+        remediation_confirmed_script: row.get(22)?,
+    };
+    // Targets that could not be migrated stay without a config; checking or saving the monitor reports why
+    if monitor.config.is_none() {
+        monitor.config = monitor.typed_config().ok();
+    }
+    Ok(monitor)
+}
+
+/// Config column value of a monitor, derived from the target when it has none
+fn config_json(monitor: &Monitor) -> Option<String> {
+    let config = monitor.typed_config().ok()?;
+    serde_json::to_string(&config).ok()
+}
+
+/// Store a typed config for every monitor that only has a target string
+pub fn migrate_monitor_configs(conn: &Connection) -> Result<()> {
+    let mut stmt = conn.prepare("SELECT id, monitor_type, target FROM monitors WHERE config IS NULL")?;
+    let legacy = stmt
+        .query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?, row.get::<_, String>(2)?)))?
+        .collect::<Result<Vec<_>>>()?;
+
+    for (id, monitor_type, target) in legacy {
+        let config = match MonitorConfig::from_legacy(&monitor_type, &target) {
+            Ok(config) => config,
+            Err(e) => {
+                eprintln!("Failed to migrate the target of monitor {}: {}", id, e);
+                continue;
+            }
+        };
+        conn.execute(
+            "UPDATE monitors SET config = ?1 WHERE id = ?2",
+            (serde_json::to_string(&config).ok(), &id),
+        )?;
+    }
+    Ok(())
+}
+
 /// Create a new monitor in the database
 pub fn create_monitor(conn: &Connection, monitor: &Monitor) -> Result<String> {
     conn.execute(
         "INSERT INTO monitors (id, name, icon, monitor_type, target, check_interval, expected_result,
-         alert_on_failure, is_active, last_check_time, last_status, folder_id, position, created_at)
-         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)",
-        (
+         alert_on_failure, is_active, last_check_time, last_status, folder_id, position, created_at,
+         remediation_button_id, remediation_threshold, remediation_cooldown_secs, degraded_threshold_ms,
+         alert_threshold, alert_renotify_secs, notifications_muted, config, remediation_confirmed_script)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20, ?21, ?22, ?23)",
+        params![
             &monitor.id,
             &monitor.name,
             &monitor.icon,
@@ -249,7 +371,16 @@ pub fn create_monitor(conn: &Connection, monitor: &Monitor) -> Result<String> {
             &monitor.folder_id,
             &monitor.position,
             &monitor.created_at,
-        ),
+            &monitor.remediation_button_id,
+            &monitor.remediation_threshold,
+            &monitor.remediation_cooldown_secs,
+            &monitor.degraded_threshold_ms,
+            &monitor.alert_threshold,
+            &monitor.alert_renotify_secs,
+            &monitor.notifications_muted,
+            config_json(monitor),
+            &monitor.remediation_confirmed_script,
+        ],
     )?;
     Ok(monitor.id.clone())
 }
@@ -258,29 +389,14 @@ pub fn create_monitor(conn: &Connection, monitor: &Monitor) -> Result<String> {
 pub fn get_all_monitors(conn: &Connection) -> Result<Vec<Monitor>> {
     let mut stmt = conn.prepare(
         "SELECT id, name, icon, monitor_type, target, check_interval, expected_result,
-         alert_on_failure, is_active, last_check_time, last_status, folder_id, position, created_at
+         alert_on_failure, is_active, last_check_time, last_status, folder_id, position, created_at,
+         remediation_button_id, remediation_threshold, remediation_cooldown_secs, degraded_threshold_ms,
+         alert_threshold, alert_renotify_secs, notifications_muted, config, remediation_confirmed_script
          FROM monitors ORDER BY position, created_at DESC",
     )?;
 
     let monitors = stmt
-        .query_map([], |row| {
-            Ok(Monitor {
-                id: row.get(0)?,
-                name: row.get(1)?,
-                icon: row.get(2)?,
-                monitor_type: row.get(3)?,
-                target: row.get(4)?,
-                check_interval: row.get(5)?,
-                expected_result: row.get(6)?,
-                alert_on_failure: row.get(7)?,
-                is_active: row.get(8)?,
-                last_check_time: row.get(9)?,
-                last_status: row.get(10)?,
-                folder_id: row.get(11)?,
-                position: row.get(12)?,
-                created_at: row.get(13)?,
-            })
-        })?
+        .query_map([], monitor_from_row)?
         .collect::<Result<Vec<_>>>()?;
 
     Ok(monitors)
@@ -290,28 +406,13 @@ pub fn get_all_monitors(conn: &Connection) -> Result<Vec<Monitor>> {
 pub fn get_monitor_by_id(conn: &Connection, id: &str) -> Result<Monitor> {
     let mut stmt = conn.prepare(
         "SELECT id, name, icon, monitor_type, target, check_interval, expected_result,
-         alert_on_failure, is_active, last_check_time, last_status, folder_id, position, created_at
+         alert_on_failure, is_active, last_check_time, last_status, folder_id, position, created_at,
+         remediation_button_id, remediation_threshold, remediation_cooldown_secs, degraded_threshold_ms,
+         alert_threshold, alert_renotify_secs, notifications_muted, config, remediation_confirmed_script
          FROM monitors WHERE id = ?1",
     )?;
 
-    let monitor = stmt.query_row([id], |row| {
-        Ok(Monitor {
-            id: row.get(0)?,
-            name: row.get(1)?,
-            icon: row.get(2)?,
-            monitor_type: row.get(3)?,
-            target: row.get(4)?,
-            check_interval: row.get(5)?,
-            expected_result: row.get(6)?,
-            alert_on_failure: row.get(7)?,
-            is_active: row.get(8)?,
-            last_check_time: row.get(9)?,
-            last_status: row.get(10)?,
-            folder_id: row.get(11)?,
-            position: row.get(12)?,
-            created_at: row.get(13)?,
-        })
-    })?;
+    let monitor = stmt.query_row([id], monitor_from_row)?;
 
     Ok(monitor)
 }
@@ -322,9 +423,12 @@ pub fn update_monitor(conn: &Connection, id: &str, monitor: &Monitor) -> Result<
         "UPDATE monitors
          SET name = ?1, icon = ?2, monitor_type = ?3, target = ?4, check_interval = ?5,
              expected_result = ?6, alert_on_failure = ?7, is_active = ?8,
-             last_check_time = ?9, last_status = ?10, folder_id = ?11, position = ?12
-         WHERE id = ?13",
-        (
+             last_check_time = ?9, last_status = ?10, folder_id = ?11, position = ?12,
+             remediation_button_id = ?13, remediation_threshold = ?14, remediation_cooldown_secs = ?15,
+             degraded_threshold_ms = ?16, alert_threshold = ?17, alert_renotify_secs = ?18,
+             notifications_muted = ?19, config = ?20, remediation_confirmed_script = ?21
+         WHERE id = ?22",
+        params![
             &monitor.name,
             &monitor.icon,
             &monitor.monitor_type,
@@ -337,16 +441,29 @@ pub fn update_monitor(conn: &Connection, id: &str, monitor: &Monitor) -> Result<
             &monitor.last_status,
             &monitor.folder_id,
             &monitor.position,
+            &monitor.remediation_button_id,
+            &monitor.remediation_threshold,
+            &monitor.remediation_cooldown_secs,
+            &monitor.degraded_threshold_ms,
+            &monitor.alert_threshold,
+            &monitor.alert_renotify_secs,
+            &monitor.notifications_muted,
+            config_json(monitor),
+            &monitor.remediation_confirmed_script,
             id,
-        ),
+        ],
     )?;
     Ok(())
 }
 
 /// Delete a monitor by ID
 pub fn delete_monitor(conn: &Connection, id: &str) -> Result<()> {
-    conn.execute("DELETE FROM monitors WHERE id = ?1", [id])?;
-    Ok(())
+    // Logs reference the monitor without a cascade; they stay in the history detached from it.
+    // Checks, alert and remediation states are removed by their cascades.
+    let tx = conn.unchecked_transaction()?;
+    tx.execute("UPDATE logs SET monitor_id = NULL WHERE monitor_id = ?1", [id])?;
+    tx.execute("DELETE FROM monitors WHERE id = ?1", [id])?;
+    tx.commit()
 }
 
 /// Update monitor status after a check
@@ -363,27 +480,304 @@ pub fn update_monitor_status(
     Ok(())
 }
 
+/// Mark a monitor as not running, e.g. when it could not be restored at launch
+pub fn mark_monitor_stopped(conn: &Connection, id: &str) -> Result<()> {
+    conn.execute(
+        "UPDATE monitors SET is_active = 0, last_status = 'stopped' WHERE id = ?1",
+        [id],
+    )?;
+    Ok(())
+}
+
+/// Clear the status of a monitor that is not running, keeping whether it is active
+pub fn reset_monitor_status(conn: &Connection, id: &str) -> Result<()> {
+    conn.execute("UPDATE monitors SET last_status = 'stopped' WHERE id = ?1", [id])?;
+    Ok(())
+}
+
 /// Get logs for a specific monitor
 pub fn get_logs_by_monitor(conn: &Connection, monitor_id: &str) -> Result<Vec<LogEntry>> {
     let mut stmt = conn.prepare(
-        "SELECT id, button_id, monitor_id, level, message, timestamp
+        "SELECT id, button_id, monitor_id, level, message, timestamp, execution_id
          FROM logs WHERE monitor_id = ?1 ORDER BY timestamp DESC LIMIT 1000",
     )?;
 
     let logs = stmt
-        .query_map([monitor_id], |row| {
-            Ok(LogEntry {
+        .query_map([monitor_id], log_from_row)?
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(logs)
+}
+
+/// Shortest term the trigram index can match; shorter terms fall back to LIKE
+const MIN_FTS_TERM_CHARS: usize = 3;
+
+/// Build the WHERE clause (possibly empty) and its parameters for a log search.
+/// Columns are qualified so callers can join other tables.
+pub fn log_search_filter(query: &LogSearchQuery) -> (String, Vec<Value>) {
+    let mut conditions: Vec<String> = Vec::new();
+    let mut values: Vec<Value> = Vec::new();
+
+    let terms: Vec<&str> = query.text.as_deref().unwrap_or("").split_whitespace().collect();
+    let (fts_terms, like_terms): (Vec<&str>, Vec<&str>) = terms
+        .into_iter()
+        .partition(|term| term.chars().count() >= MIN_FTS_TERM_CHARS);
+    if !fts_terms.is_empty() {
+        // Each term is quoted as a phrase so FTS5 operators in user input are matched literally
+        let expression = fts_terms
+            .iter()
+            .map(|term| format!("\"{}\"", term.replace('"', "\"\"")))
+            .collect::<Vec<_>>()
+            .join(" AND ");
+        conditions.push("logs.rowid IN (SELECT rowid FROM logs_fts WHERE logs_fts MATCH ?)".to_string());
+        values.push(Value::Text(expression));
+    }
+    for term in like_terms {
+        let escaped = term.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_");
+        conditions.push("logs.message LIKE ? ESCAPE '\\'".to_string());
+        values.push(Value::Text(format!("%{}%", escaped)));
+    }
+
+    if !query.levels.is_empty() {
+        let placeholders = vec!["?"; query.levels.len()].join(", ");
+        conditions.push(format!("logs.level IN ({})", placeholders));
+        values.extend(query.levels.iter().map(|level| Value::Text(level.clone())));
+    }
+    if let Some(from) = query.from {
+        conditions.push("logs.timestamp >= ?".to_string());
+        values.push(Value::Integer(from));
+    }
+    if let Some(to) = query.to {
+        conditions.push("logs.timestamp <= ?".to_string());
+        values.push(Value::Integer(to));
+    }
+    for (column, value) in [
+        ("button_id", &query.button_id),
+        ("monitor_id", &query.monitor_id),
+        ("execution_id", &query.execution_id),
+    ] {
+        if let Some(value) = value {
+            conditions.push(format!("logs.{} = ?", column));
+            values.push(Value::Text(value.clone()));
+        }
+    }
+
+    if conditions.is_empty() {
+        (String::new(), values)
+    } else {
+        (format!("WHERE {}", conditions.join(" AND ")), values)
+    }
+}
+
+/// Search logs newest first, continuing after `cursor`; returns at most `limit` entries
+pub fn search_logs(
+    conn: &Connection,
+    query: &LogSearchQuery,
+    cursor: Option<&LogCursor>,
+    limit: u32,
+) -> Result<Vec<LogEntry>> {
+    let (mut where_clause, mut values) = log_search_filter(query);
+    if let Some(cursor) = cursor {
+        where_clause.push_str(if where_clause.is_empty() { "WHERE " } else { " AND " });
+        where_clause.push_str("(logs.timestamp < ? OR (logs.timestamp = ? AND logs.id < ?))");
+        values.push(Value::Integer(cursor.timestamp));
+        values.push(Value::Integer(cursor.timestamp));
+        values.push(Value::Text(cursor.id.clone()));
+    }
+    values.push(Value::Integer(i64::from(limit)));
+
+    let mut stmt = conn.prepare(&format!(
+        "SELECT id, button_id, monitor_id, level, message, timestamp, execution_id
+         FROM logs {} ORDER BY timestamp DESC, id DESC LIMIT ?",
+        where_clause
+    ))?;
+
+    let logs = stmt
+        .query_map(params_from_iter(values), log_from_row)?
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(logs)
+}
+
+/// Rebuild the full-text index from the logs table.
+/// Needed after a full VACUUM, which may renumber the rowids the index refers to.
+pub fn rebuild_log_search_index(conn: &Connection) -> Result<()> {
+    conn.execute("INSERT INTO logs_fts (logs_fts) VALUES ('rebuild')", [])?;
+    Ok(())
+}
+
+// ============================================================================
+// Monitor Check Operations
+// ============================================================================
+
+/// Get the JSON encoded alert state of a monitor
+pub fn get_monitor_alert_state(conn: &Connection, monitor_id: &str) -> Result<Option<String>> {
+    let mut stmt = conn.prepare("SELECT state FROM monitor_alert_states WHERE monitor_id = ?1")?;
+    let mut rows = stmt.query([monitor_id])?;
+
+    match rows.next()? {
+        Some(row) => Ok(Some(row.get(0)?)),
+        None => Ok(None),
+    }
+}
+
+/// Save the JSON encoded alert state of a monitor
+pub fn set_monitor_alert_state(conn: &Connection, monitor_id: &str, state: &str, updated_at: i64) -> Result<()> {
+    conn.execute(
+        "INSERT INTO monitor_alert_states (monitor_id, state, updated_at) VALUES (?1, ?2, ?3)
+         ON CONFLICT(monitor_id) DO UPDATE SET state = excluded.state, updated_at = excluded.updated_at",
+        (monitor_id, state, updated_at),
+    )?;
+    Ok(())
+}
+
+/// Get the JSON encoded remediation state of a monitor
+pub fn get_monitor_remediation_state(conn: &Connection, monitor_id: &str) -> Result<Option<String>> {
+    let mut stmt = conn.prepare("SELECT state FROM monitor_remediation_states WHERE monitor_id = ?1")?;
+    let mut rows = stmt.query([monitor_id])?;
+
+    match rows.next()? {
+        Some(row) => Ok(Some(row.get(0)?)),
+        None => Ok(None),
+    }
+}
+
+/// Save the JSON encoded remediation state of a monitor
+pub fn set_monitor_remediation_state(conn: &Connection, monitor_id: &str, state: &str, updated_at: i64) -> Result<()> {
+    conn.execute(
+        "INSERT INTO monitor_remediation_states (monitor_id, state, updated_at) VALUES (?1, ?2, ?3)
+         ON CONFLICT(monitor_id) DO UPDATE SET state = excluded.state, updated_at = excluded.updated_at",
+        (monitor_id, state, updated_at),
+    )?;
+    Ok(())
+}
+
+/// Record the result of a monitor check
+pub fn create_monitor_check(
+    conn: &Connection,
+    monitor_id: &str,
+    timestamp: i64,
+    healthy: bool,
+    latency_ms: Option<i64>,
+    detail: Option<&str>,
+) -> Result<i64> {
+    conn.prepare_cached(
+        "INSERT INTO monitor_checks (monitor_id, timestamp, healthy, latency_ms, detail)
+         VALUES (?1, ?2, ?3, ?4, ?5)",
+    )?
+    .execute((monitor_id, timestamp, healthy, latency_ms, detail))?;
+    Ok(conn.last_insert_rowid())
+}
+
+/// Get the checks of a monitor within [from, to], newest first
+pub fn get_monitor_checks(
+    conn: &Connection,
+    monitor_id: &str,
+    from: i64,
+    to: i64,
+    limit: u32,
+) -> Result<Vec<MonitorCheck>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, monitor_id, timestamp, healthy, latency_ms, detail
+         FROM monitor_checks WHERE monitor_id = ?1 AND timestamp BETWEEN ?2 AND ?3
+         ORDER BY timestamp DESC, id DESC LIMIT ?4",
+    )?;
+
+    let checks = stmt
+        .query_map((monitor_id, from, to, limit), |row| {
+            Ok(MonitorCheck {
                 id: row.get(0)?,
-                button_id: row.get(1)?,
-                monitor_id: row.get(2)?,
-                level: row.get(3)?,
-                message: row.get(4)?,
-                timestamp: row.get(5)?,
+                monitor_id: row.get(1)?,
+                timestamp: row.get(2)?,
+                healthy: row.get(3)?,
+                latency_ms: row.get(4)?,
+                detail: row.get(5)?,
             })
         })?
         .collect::<Result<Vec<_>>>()?;
 
-    Ok(logs)
+    Ok(checks)
+}
+
+/// Uptime, incident count and latency of a monitor within [from, to]
+pub fn get_monitor_stats(conn: &Connection, monitor_id: &str, from: i64, to: i64) -> Result<MonitorStats> {
+    let (total_checks, healthy_checks, avg_latency_ms, latency_count): (u64, u64, Option<f64>, u64) = conn
+        .query_row(
+            "SELECT COUNT(*), COALESCE(SUM(healthy), 0), AVG(latency_ms), COUNT(latency_ms)
+             FROM monitor_checks WHERE monitor_id = ?1 AND timestamp BETWEEN ?2 AND ?3",
+            (monitor_id, from, to),
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+        )?;
+
+    // An incident starts with a failed check that follows a healthy one (or opens the range)
+    let incidents: u64 = conn.query_row(
+        "SELECT COUNT(*) FROM (
+             SELECT healthy, LAG(healthy) OVER (ORDER BY timestamp, id) AS previous
+             FROM monitor_checks WHERE monitor_id = ?1 AND timestamp BETWEEN ?2 AND ?3
+         ) WHERE healthy = 0 AND (previous IS NULL OR previous = 1)",
+        (monitor_id, from, to),
+        |row| row.get(0),
+    )?;
+
+    // Nearest-rank percentile
+    let p95_latency_ms = if latency_count > 0 {
+        let rank = (latency_count * 95).div_ceil(100);
+        conn.query_row(
+            "SELECT latency_ms FROM monitor_checks
+             WHERE monitor_id = ?1 AND timestamp BETWEEN ?2 AND ?3 AND latency_ms IS NOT NULL
+             ORDER BY latency_ms LIMIT 1 OFFSET ?4",
+            (monitor_id, from, to, rank - 1),
+            |row| row.get(0),
+        )?
+    } else {
+        None
+    };
+
+    Ok(MonitorStats {
+        monitor_id: monitor_id.to_string(),
+        from,
+        to,
+        total_checks,
+        healthy_checks,
+        uptime_percent: (total_checks > 0).then(|| healthy_checks as f64 * 100.0 / total_checks as f64),
+        incidents,
+        avg_latency_ms,
+        p95_latency_ms,
+    })
+}
+
+/// Checks of a monitor within [from, to] grouped into buckets of `bucket_secs`; empty buckets are omitted
+pub fn get_monitor_check_buckets(
+    conn: &Connection,
+    monitor_id: &str,
+    from: i64,
+    to: i64,
+    bucket_secs: i64,
+) -> Result<Vec<MonitorCheckBucket>> {
+    let mut stmt = conn.prepare(
+        "SELECT (timestamp - ?2) / ?4 AS bucket, COUNT(*), SUM(healthy), AVG(latency_ms), MAX(latency_ms)
+         FROM monitor_checks WHERE monitor_id = ?1 AND timestamp BETWEEN ?2 AND ?3
+         GROUP BY bucket ORDER BY bucket",
+    )?;
+
+    let buckets = stmt
+        .query_map((monitor_id, from, to, bucket_secs), |row| {
+            Ok(MonitorCheckBucket {
+                start: from + row.get::<_, i64>(0)? * bucket_secs,
+                total_checks: row.get(1)?,
+                healthy_checks: row.get(2)?,
+                avg_latency_ms: row.get(3)?,
+                max_latency_ms: row.get(4)?,
+            })
+        })?
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(buckets)
+}
+
+/// Delete checks older than the cutoff timestamp, returning the number of rows removed
+pub fn delete_monitor_checks_before(conn: &Connection, cutoff: i64) -> Result<usize> {
+    conn.execute("DELETE FROM monitor_checks WHERE timestamp < ?1", [cutoff])
 }
 
 // ============================================================================
@@ -525,3 +919,490 @@ pub fn delete_folder(conn: &Connection, id: &str) -> Result<()> {
 
     Ok(())
 }
+
+// ============================================================================
+// Settings Operations
+// ============================================================================
+
+/// Get a backend setting value by key
+pub fn get_setting(conn: &Connection, key: &str) -> Result<Option<String>> {
+    let mut stmt = conn.prepare("SELECT value FROM settings WHERE key = ?1")?;
+    let mut rows = stmt.query([key])?;
+
+    match rows.next()? {
+        Some(row) => Ok(Some(row.get(0)?)),
+        None => Ok(None),
+    }
+}
+
+/// Insert or replace a backend setting value
+pub fn set_setting(conn: &Connection, key: &str, value: &str) -> Result<()> {
+    let now = chrono::Utc::now().timestamp();
+
+    conn.execute(
+        "INSERT INTO settings (key, value, updated_at) VALUES (?1, ?2, ?3)
+         ON CONFLICT(key) DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at",
+        (key, value, now),
+    )?;
+    Ok(())
+}
+
+// ============================================================================
+// Secret Operations
+// ============================================================================
+
+/// Insert or replace an encrypted secret
+pub fn upsert_secret(conn: &Connection, secret: &SecretRecord) -> Result<()> {
+    conn.execute(
+        "INSERT INTO secrets (name, nonce, ciphertext, created_at, updated_at)
+         VALUES (?1, ?2, ?3, ?4, ?5)
+         ON CONFLICT(name) DO UPDATE SET
+             nonce = excluded.nonce, ciphertext = excluded.ciphertext, updated_at = excluded.updated_at",
+        (
+            &secret.name,
+            &secret.nonce,
+            &secret.ciphertext,
+            &secret.created_at,
+            &secret.updated_at,
+        ),
+    )?;
+    Ok(())
+}
+
+/// Get all encrypted secrets
+pub fn get_all_secrets(conn: &Connection) -> Result<Vec<SecretRecord>> {
+    let mut stmt = conn.prepare(
+        "SELECT name, nonce, ciphertext, created_at, updated_at FROM secrets ORDER BY name",
+    )?;
+
+    let secrets = stmt
+        .query_map([], |row| {
+            Ok(SecretRecord {
+                name: row.get(0)?,
+                nonce: row.get(1)?,
+                ciphertext: row.get(2)?,
+                created_at: row.get(3)?,
+                updated_at: row.get(4)?,
+            })
+        })?
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(secrets)
+}
+
+/// List secret names and timestamps without values
+pub fn list_secrets(conn: &Connection) -> Result<Vec<SecretInfo>> {
+    let mut stmt = conn.prepare("SELECT name, created_at, updated_at FROM secrets ORDER BY name")?;
+
+    let secrets = stmt
+        .query_map([], |row| {
+            Ok(SecretInfo {
+                name: row.get(0)?,
+                created_at: row.get(1)?,
+                updated_at: row.get(2)?,
+            })
+        })?
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(secrets)
+}
+
+/// Delete a secret by name
+pub fn delete_secret(conn: &Connection, name: &str) -> Result<()> {
+    conn.execute("DELETE FROM secrets WHERE name = ?1", [name])?;
+    Ok(())
+}
+
+// ============================================================================
+// Execution Operations
+// ============================================================================
+
+/// Map a row selected with the standard execution column list
+fn execution_from_row(row: &Row) -> Result<ExecutionRecord> {
+    Ok(ExecutionRecord {
+        id: row.get(0)?,
+        button_id: row.get(1)?,
+        trigger: row.get(2)?,
+        status: row.get(3)?,
+        attempts: row.get(4)?,
+        exit_code: row.get(5)?,
+        error: row.get(6)?,
+        started_at: row.get(7)?,
+        finished_at: row.get(8)?,
+        duration_ms: row.get(9)?,
+        trigger_detail: row.get(10)?,
+    })
+}
+
+/// Insert or replace an execution record
+pub fn save_execution(conn: &Connection, execution: &ExecutionRecord) -> Result<()> {
+    conn.execute(
+        "INSERT INTO executions (id, button_id, trigger, status, attempts, exit_code, error,
+                                 started_at, finished_at, duration_ms, trigger_detail)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)
+         ON CONFLICT(id) DO UPDATE SET
+             status = excluded.status, attempts = excluded.attempts, exit_code = excluded.exit_code,
+             error = excluded.error, finished_at = excluded.finished_at, duration_ms = excluded.duration_ms",
+        (
+            &execution.id,
+            &execution.button_id,
+            &execution.trigger,
+            &execution.status,
+            &execution.attempts,
+            &execution.exit_code,
+            &execution.error,
+            &execution.started_at,
+            &execution.finished_at,
+            &execution.duration_ms,
+            &execution.trigger_detail,
+        ),
+    )?;
+    Ok(())
+}
+
+/// Get recent executions of a button (newest first)
+pub fn get_executions_by_button(conn: &Connection, button_id: &str, limit: u32) -> Result<Vec<ExecutionRecord>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, button_id, trigger, status, attempts, exit_code, error, started_at, finished_at, duration_ms,
+                trigger_detail
+         FROM executions WHERE button_id = ?1 ORDER BY started_at DESC LIMIT ?2",
+    )?;
+
+    let executions = stmt
+        .query_map((button_id, limit), execution_from_row)?
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(executions)
+}
+
+/// Get logs of a single execution in the order they were written
+pub fn get_logs_by_execution(conn: &Connection, execution_id: &str) -> Result<Vec<LogEntry>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, button_id, monitor_id, level, message, timestamp, execution_id
+         FROM logs WHERE execution_id = ?1 ORDER BY timestamp, rowid",
+    )?;
+
+    let logs = stmt
+        .query_map([execution_id], log_from_row)?
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(logs)
+}
+
+// ============================================================================
+// Schedule Operations
+// ============================================================================
+
+/// Map a row selected with the standard schedule column list
+fn schedule_from_row(row: &Row) -> Result<Schedule> {
+    Ok(Schedule {
+        id: row.get(0)?,
+        button_id: row.get(1)?,
+        cron_expression: row.get(2)?,
+        timezone: row.get(3)?,
+        enabled: row.get(4)?,
+        last_run_at: row.get(5)?,
+        created_at: row.get(6)?,
+        updated_at: row.get(7)?,
+        confirmed_script: row.get(8)?,
+    })
+}
+
+/// Create a new schedule
+pub fn create_schedule(conn: &Connection, schedule: &Schedule) -> Result<String> {
+    conn.execute(
+        "INSERT INTO schedules (id, button_id, cron_expression, timezone, enabled, last_run_at, created_at, updated_at, confirmed_script)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+        (
+            &schedule.id,
+            &schedule.button_id,
+            &schedule.cron_expression,
+            &schedule.timezone,
+            &schedule.enabled,
+            &schedule.last_run_at,
+            &schedule.created_at,
+            &schedule.updated_at,
+            &schedule.confirmed_script,
+        ),
+    )?;
+    Ok(schedule.id.clone())
+}
+
+/// Get all schedules ordered by creation time
+pub fn get_all_schedules(conn: &Connection) -> Result<Vec<Schedule>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, button_id, cron_expression, timezone, enabled, last_run_at, created_at, updated_at, confirmed_script
+         FROM schedules ORDER BY created_at",
+    )?;
+
+    let schedules = stmt
+        .query_map([], schedule_from_row)?
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(schedules)
+}
+
+/// Get a single schedule by ID
+pub fn get_schedule_by_id(conn: &Connection, id: &str) -> Result<Schedule> {
+    let mut stmt = conn.prepare(
+        "SELECT id, button_id, cron_expression, timezone, enabled, last_run_at, created_at, updated_at, confirmed_script
+         FROM schedules WHERE id = ?1",
+    )?;
+
+    stmt.query_row([id], schedule_from_row)
+}
+
+/// Update an existing schedule
+pub fn update_schedule(conn: &Connection, id: &str, schedule: &Schedule) -> Result<()> {
+    conn.execute(
+        "UPDATE schedules
+         SET button_id = ?1, cron_expression = ?2, timezone = ?3, enabled = ?4, confirmed_script = ?5, updated_at = ?6
+         WHERE id = ?7",
+        (
+            &schedule.button_id,
+            &schedule.cron_expression,
+            &schedule.timezone,
+            &schedule.enabled,
+            &schedule.confirmed_script,
+            &schedule.updated_at,
+            id,
+        ),
+    )?;
+    Ok(())
+}
+
+/// Enable or disable a schedule without touching its other settings
+pub fn set_schedule_enabled(conn: &Connection, id: &str, enabled: bool, updated_at: i64) -> Result<()> {
+    conn.execute(
+        "UPDATE schedules SET enabled = ?1, updated_at = ?2 WHERE id = ?3",
+        (enabled, updated_at, id),
+    )?;
+    Ok(())
+}
+
+/// Record when a schedule last fired
+pub fn update_schedule_last_run(conn: &Connection, id: &str, last_run_at: i64) -> Result<()> {
+    conn.execute(
+        "UPDATE schedules SET last_run_at = ?1 WHERE id = ?2",
+        (last_run_at, id),
+    )?;
+    Ok(())
+}
+
+/// Delete a schedule by ID
+pub fn delete_schedule(conn: &Connection, id: &str) -> Result<()> {
+    conn.execute("DELETE FROM schedules WHERE id = ?1", [id])?;
+    Ok(())
+}
+
+// ============================================================================
+// File Trigger Operations
+// ============================================================================
+
+/// Map a row selected with the standard file trigger column list
+fn file_trigger_from_row(row: &Row) -> Result<FileTrigger> {
+    // A list that cannot be read is an error: an empty exclude list would widen the trigger
+    let json_list = |index: usize| -> Result<Vec<String>> { Ok(json_column(row, index)?.unwrap_or_default()) };
+
+    Ok(FileTrigger {
+        id: row.get(0)?,
+        button_id: row.get(1)?,
+        paths: json_list(2)?,
+        include: json_list(3)?,
+        exclude: json_list(4)?,
+        debounce_ms: row.get(5)?,
+        enabled: row.get(6)?,
+        confirmed_script: row.get(9)?,
+        created_at: row.get(7)?,
+        updated_at: row.get(8)?,
+    })
+}
+
+fn list_to_json(values: &[String]) -> String {
+    serde_json::to_string(values).unwrap_or_else(|_| "[]".to_string())
+}
+
+/// Create a new file trigger
+pub fn create_file_trigger(conn: &Connection, trigger: &FileTrigger) -> Result<String> {
+    conn.execute(
+        "INSERT INTO file_triggers (id, button_id, paths, include_patterns, exclude_patterns, debounce_ms,
+                                    enabled, created_at, updated_at, confirmed_script)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+        (
+            &trigger.id,
+            &trigger.button_id,
+            list_to_json(&trigger.paths),
+            list_to_json(&trigger.include),
+            list_to_json(&trigger.exclude),
+            &trigger.debounce_ms,
+            &trigger.enabled,
+            &trigger.created_at,
+            &trigger.updated_at,
+            &trigger.confirmed_script,
+        ),
+    )?;
+    Ok(trigger.id.clone())
+}
+
+/// Get all file triggers ordered by creation time
+pub fn get_all_file_triggers(conn: &Connection) -> Result<Vec<FileTrigger>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, button_id, paths, include_patterns, exclude_patterns, debounce_ms, enabled, created_at, updated_at,
+                confirmed_script
+         FROM file_triggers ORDER BY created_at",
+    )?;
+
+    let triggers = stmt
+        .query_map([], file_trigger_from_row)?
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(triggers)
+}
+
+/// Get a single file trigger by ID
+pub fn get_file_trigger_by_id(conn: &Connection, id: &str) -> Result<FileTrigger> {
+    let mut stmt = conn.prepare(
+        "SELECT id, button_id, paths, include_patterns, exclude_patterns, debounce_ms, enabled, created_at, updated_at,
+                confirmed_script
+         FROM file_triggers WHERE id = ?1",
+    )?;
+
+    stmt.query_row([id], file_trigger_from_row)
+}
+
+/// Update an existing file trigger
+pub fn update_file_trigger(conn: &Connection, id: &str, trigger: &FileTrigger) -> Result<()> {
+    conn.execute(
+        "UPDATE file_triggers
+         SET button_id = ?1, paths = ?2, include_patterns = ?3, exclude_patterns = ?4, debounce_ms = ?5,
+             enabled = ?6, confirmed_script = ?7, updated_at = ?8
+         WHERE id = ?9",
+        (
+            &trigger.button_id,
+            list_to_json(&trigger.paths),
+            list_to_json(&trigger.include),
+            list_to_json(&trigger.exclude),
+            &trigger.debounce_ms,
+            &trigger.enabled,
+            &trigger.confirmed_script,
+            &trigger.updated_at,
+            id,
+        ),
+    )?;
+    Ok(())
+}
+
+/// Enable or disable a file trigger without touching its other settings
+pub fn set_file_trigger_enabled(conn: &Connection, id: &str, enabled: bool, updated_at: i64) -> Result<()> {
+    conn.execute(
+        "UPDATE file_triggers SET enabled = ?1, updated_at = ?2 WHERE id = ?3",
+        (enabled, updated_at, id),
+    )?;
+    Ok(())
+}
+
+/// Delete a file trigger by ID
+pub fn delete_file_trigger(conn: &Connection, id: &str) -> Result<()> {
+    conn.execute("DELETE FROM file_triggers WHERE id = ?1", [id])?;
+    Ok(())
+}
+
+// ============================================================================
+// Notification Channel Operations
+// ============================================================================
+
+/// Map a row selected with the standard notification channel column list
+fn notification_channel_from_row(row: &Row) -> Result<NotificationChannel> {
+    Ok(NotificationChannel {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        channel_type: row.get(2)?,
+        url: row.get(3)?,
+        template: row.get(4)?,
+        enabled: row.get(5)?,
+        created_at: row.get(6)?,
+        updated_at: row.get(7)?,
+    })
+}
+
+/// Create a new notification channel
+pub fn create_notification_channel(conn: &Connection, channel: &NotificationChannel) -> Result<String> {
+    conn.execute(
+        "INSERT INTO notification_channels (id, name, channel_type, url, template, enabled, created_at, updated_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+        (
+            &channel.id,
+            &channel.name,
+            &channel.channel_type,
+            &channel.url,
+            &channel.template,
+            &channel.enabled,
+            &channel.created_at,
+            &channel.updated_at,
+        ),
+    )?;
+    Ok(channel.id.clone())
+}
+
+/// Get all notification channels ordered by creation time
+pub fn get_all_notification_channels(conn: &Connection) -> Result<Vec<NotificationChannel>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, name, channel_type, url, template, enabled, created_at, updated_at
+         FROM notification_channels ORDER BY created_at",
+    )?;
+
+    let channels = stmt
+        .query_map([], notification_channel_from_row)?
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(channels)
+}
+
+/// Get the channels that receive notifications
+pub fn get_enabled_notification_channels(conn: &Connection) -> Result<Vec<NotificationChannel>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, name, channel_type, url, template, enabled, created_at, updated_at
+         FROM notification_channels WHERE enabled = 1 ORDER BY created_at",
+    )?;
+
+    let channels = stmt
+        .query_map([], notification_channel_from_row)?
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(channels)
+}
+
+/// Get a single notification channel by ID
+pub fn get_notification_channel_by_id(conn: &Connection, id: &str) -> Result<NotificationChannel> {
+    let mut stmt = conn.prepare(
+        "SELECT id, name, channel_type, url, template, enabled, created_at, updated_at
+         FROM notification_channels WHERE id = ?1",
+    )?;
+
+    stmt.query_row([id], notification_channel_from_row)
+}
+
+/// Update an existing notification channel
+pub fn update_notification_channel(conn: &Connection, id: &str, channel: &NotificationChannel) -> Result<()> {
+    conn.execute(
+        "UPDATE notification_channels
+         SET name = ?1, channel_type = ?2, url = ?3, template = ?4, enabled = ?5, updated_at = ?6
+         WHERE id = ?7",
+        (
+            &channel.name,
+            &channel.channel_type,
+            &channel.url,
+            &channel.template,
+            &channel.enabled,
+            &channel.updated_at,
+            id,
+        ),
+    )?;
+    Ok(())
+}
+
+/// Delete a notification channel by ID
+pub fn delete_notification_channel(conn: &Connection, id: &str) -> Result<()> {
+    conn.execute("DELETE FROM notification_channels WHERE id = ?1", [id])?;
+    Ok(())
+}
@@ -0,0 +1,70 @@
+use rusqlite::Connection;
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+
+use super::models::LogEntry;
+use super::{repository, Database, DB_FILE_NAME};
+
+/// Database in a fresh temporary directory, removed again when dropped
+pub struct TestDb {
+    db: Option<Database>,
+    dir: PathBuf,
+}
+
+impl TestDb {
+    pub fn open() -> Self {
+        let dir = std::env::temp_dir().join(format!("devtools-test-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let db = Database::open(&dir.join(DB_FILE_NAME)).unwrap();
+        Self { db: Some(db), dir }
+    }
+
+    /// Temporary directory holding the database file, for other files of the test
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+}
+
+impl Deref for TestDb {
+    type Target = Database;
+
+    fn deref(&self) -> &Database {
+        self.db.as_ref().expect("database is open until drop")
+    }
+}
+
+impl Drop for TestDb {
+    fn drop(&mut self) {
+        // Close the pool before the files go away
+        drop(self.db.take());
+        let _ = std::fs::remove_dir_all(&self.dir);
+    }
+}
+
+/// Shell button running `echo hi`
+pub fn insert_button(conn: &Connection, id: &str, name: &str) {
+    conn.execute(
+        "INSERT INTO buttons (id, name, script_type, script_content, position, created_at, updated_at)
+         VALUES (?1, ?2, 'shell', 'echo hi', 0, 0, 0)",
+        [id, name],
+    )
+    .unwrap();
+}
+
+/// Log entry with a new id, outside of any execution
+pub fn log_entry(button_id: Option<&str>, level: &str, message: &str, timestamp: i64) -> LogEntry {
+    LogEntry {
+        id: uuid::Uuid::new_v4().to_string(),
+        button_id: button_id.map(str::to_string),
+        monitor_id: None,
+        level: level.to_string(),
+        message: message.to_string(),
+        timestamp,
+        execution_id: None,
+    }
+}
+
+/// Store a `log_entry`
+pub fn insert_log(conn: &Connection, button_id: Option<&str>, level: &str, message: &str, timestamp: i64) {
+    repository::create_log(conn, &log_entry(button_id, level, message, timestamp)).unwrap();
+}
@@ -0,0 +1,133 @@
+use rusqlite::Connection;
+use std::sync::mpsc::{self, Receiver, Sender};
+
+use super::models::LogEntry;
+use super::pool::DbConn;
+use super::repository;
+
+/// Maximum number of queued operations handled in one pass
+const MAX_BATCH: usize = 500;
+
+/// A write job run on the writer thread
+type Job = Box<dyn FnOnce(&Connection) + Send>;
+
+/// Operations queued for the writer thread
+enum WriteOp {
+    Logs(Vec<LogEntry>), // Consecutive log inserts are committed together
+    Job(Job),
+}
+
+/// Handle to the single thread that performs all database writes.
+/// Writes are applied in the order they were queued.
+#[derive(Clone)]
+pub struct DbWriter {
+    tx: Sender<WriteOp>,
+}
+
+impl DbWriter {
+    /// Start the writer thread on its own connection; it stops once every handle is dropped
+    pub fn spawn(conn: DbConn) -> Result<Self, String> {
+        let (tx, rx) = mpsc::channel();
+        std::thread::Builder::new()
+            .name("db-writer".to_string())
+            .spawn(move || run(conn, rx))
+            .map_err(|e| format!("Failed to start database writer: {}", e))?;
+        Ok(Self { tx })
+    }
+
+    /// Queue log inserts without waiting for them
+    pub fn insert_logs(&self, logs: Vec<LogEntry>) {
+        let _ = self.tx.send(WriteOp::Logs(logs));
+    }
+
+    /// Queue a write job without waiting for it
+    pub fn execute(&self, job: impl FnOnce(&Connection) + Send + 'static) -> Result<(), String> {
+        self.tx
+            .send(WriteOp::Job(Box::new(job)))
+            .map_err(|_| "Database writer has stopped".to_string())
+    }
+}
+
+fn run(mut conn: DbConn, rx: Receiver<WriteOp>) {
+    while let Ok(first) = rx.recv() {
+        let mut batch = vec![first];
+        while batch.len() < MAX_BATCH {
+            match rx.try_recv() {
+                Ok(op) => batch.push(op),
+                Err(_) => break,
+            }
+        }
+
+        let mut logs = Vec::new();
+        for op in batch {
+            match op {
+                WriteOp::Logs(mut entries) => logs.append(&mut entries),
+                WriteOp::Job(job) => {
+                    // Keep queue order: pending logs go in before the job runs
+                    insert_logs(&mut conn, &mut logs);
+                    job(&conn);
+                }
+            }
+        }
+        insert_logs(&mut conn, &mut logs);
+    }
+}
+
+/// Insert pending logs in a single transaction
+fn insert_logs(conn: &mut Connection, logs: &mut Vec<LogEntry>) {
+    if logs.is_empty() {
+        return;
+    }
+
+    let result = conn.transaction().and_then(|tx| {
+        for log in logs.iter() {
+            // A rejected row (e.g. its button was deleted) must not drop the rest of the batch
+            if let Err(e) = repository::create_log(&tx, log) {
+                eprintln!("Failed to write log entry {}: {}", log.id, e);
+            }
+        }
+        tx.commit()
+    });
+    if let Err(e) = result {
+        eprintln!("Failed to write {} log entries: {}", logs.len(), e);
+    }
+    logs.clear();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::TestDb;
+    use super::*;
+
+    fn log(button_id: Option<&str>) -> LogEntry {
+        LogEntry {
+            id: uuid::Uuid::new_v4().to_string(),
+            button_id: button_id.map(|id| id.to_string()),
+            monitor_id: None,
+            level: "info".to_string(),
+            message: "line".to_string(),
+            timestamp: 0,
+            execution_id: None,
+        }
+    }
+
+    #[test]
+    fn test_queued_writes_apply_in_order() {
+        let db = TestDb::open();
+
+        for _ in 0..(MAX_BATCH * 2 + 10) {
+            db.insert_log(log(None));
+        }
+        // Violates the foreign key; the rest of its batch must still be stored
+        db.insert_log(log(Some("missing-button")));
+
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let count: i64 = runtime
+            .block_on(db.write(|conn| {
+                conn.query_row("SELECT COUNT(*) FROM logs", [], |row| row.get(0))
+                    .map_err(|e| e.to_string())
+            }))
+            .unwrap();
+        assert_eq!(count, (MAX_BATCH * 2 + 10) as i64);
+    }
+}
@@ -1,31 +1,32 @@
-use std::process::{Command, Stdio};
+use std::process::Stdio;
 use std::io::{BufRead, BufReader};
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
-use tokio::sync::mpsc;
 
 use crate::database::models::LogEntry;
+use super::log_stream::LogSender;
 use crate::executor::ExecutionResult;
+use super::plan::ExecutionPlan;
 
 pub struct JsExecutor {
     pub execution_id: String,
     pub button_id: String,
     pub button_name: String,
-    pub script: String,
+    pub plan: ExecutionPlan,
 }
 
 impl JsExecutor {
-    pub fn new(execution_id: String, button_id: String, button_name: String, script: String) -> Self {
+    pub fn new(execution_id: String, button_id: String, button_name: String, plan: ExecutionPlan) -> Self {
         Self {
             execution_id,
             button_id,
             button_name,
-            script,
+            plan,
         }
     }
 
     pub async fn execute(
         &self,
-        log_sender: mpsc::Sender<LogEntry>,
+        log_sender: LogSender,
     ) -> Result<ExecutionResult, String> {
         let start_time = SystemTime::now();
 
@@ -40,31 +41,14 @@ impl JsExecutor {
                 .duration_since(UNIX_EPOCH)
                 .unwrap()
                 .as_secs() as i64,
+            execution_id: Some(self.execution_id.clone()),
         };
         let _ = log_sender.send(start_log).await;
 
-        // Check if Node.js is available
-        if Command::new("node").arg("--version").output().is_err() {
-            let error_msg = "❌ Node.js 未安装或不在 PATH 中".to_string();
-            let error_log = LogEntry {
-                id: uuid::Uuid::new_v4().to_string(),
-                button_id: Some(self.button_id.clone()),
-                monitor_id: None,
-                level: "error".to_string(),
-                message: error_msg.clone(),
-                timestamp: SystemTime::now()
-                    .duration_since(UNIX_EPOCH)
-                    .unwrap()
-                    .as_secs() as i64,
-            };
-            let _ = log_sender.send(error_log).await;
-            return Err(error_msg);
-        }
-
         // Execute the JavaScript code
-        let mut child = match Command::new("node")
-            .arg("-e")
-            .arg(&self.script)
+        let mut child = match self
+            .plan
+            .command()
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
             .spawn()
@@ -82,6 +66,7 @@ impl JsExecutor {
                         .duration_since(UNIX_EPOCH)
                         .unwrap()
                         .as_secs() as i64,
+                    execution_id: Some(self.execution_id.clone()),
                 };
                 let _ = log_sender.send(error_log).await;
                 return Err(error_msg);
@@ -91,6 +76,7 @@ impl JsExecutor {
         // Capture stdout
         if let Some(stdout) = child.stdout.take() {
             let button_id = self.button_id.clone();
+            let execution_id = self.execution_id.clone();
             let sender = log_sender.clone();
             tokio::spawn(async move {
                 let reader = BufReader::new(stdout);
@@ -106,8 +92,9 @@ impl JsExecutor {
                                 .duration_since(UNIX_EPOCH)
                                 .unwrap()
                                 .as_secs() as i64,
+                            execution_id: Some(execution_id.clone()),
                         };
-                        let _ = sender.send(log).await;
+                        sender.send_line(log);
                     }
                 }
             });
@@ -116,6 +103,7 @@ impl JsExecutor {
         // Capture stderr
         if let Some(stderr) = child.stderr.take() {
             let button_id = self.button_id.clone();
+            let execution_id = self.execution_id.clone();
             let sender = log_sender.clone();
             tokio::spawn(async move {
                 let reader = BufReader::new(stderr);
@@ -131,8 +119,9 @@ impl JsExecutor {
                                 .duration_since(UNIX_EPOCH)
                                 .unwrap()
                                 .as_secs() as i64,
+                            execution_id: Some(execution_id.clone()),
                         };
-                        let _ = sender.send(log).await;
+                        sender.send_line(log);
                     }
                 }
             });
@@ -170,6 +159,7 @@ impl JsExecutor {
                         .duration_since(UNIX_EPOCH)
                         .unwrap()
                         .as_secs() as i64,
+                    execution_id: Some(self.execution_id.clone()),
                 };
                 let _ = log_sender.send(result_log).await;
 
@@ -192,6 +182,7 @@ impl JsExecutor {
                         .duration_since(UNIX_EPOCH)
                         .unwrap()
                         .as_secs() as i64,
+                    execution_id: Some(self.execution_id.clone()),
                 };
                 let _ = log_sender.send(error_log).await;
 
@@ -214,6 +205,7 @@ impl JsExecutor {
                         .duration_since(UNIX_EPOCH)
                         .unwrap()
                         .as_secs() as i64,
+                    execution_id: Some(self.execution_id.clone()),
                 };
                 let _ = log_sender.send(error_log).await;
 
@@ -236,6 +228,7 @@ impl JsExecutor {
                         .duration_since(UNIX_EPOCH)
                         .unwrap()
                         .as_secs() as i64,
+                    execution_id: Some(self.execution_id.clone()),
                 };
                 let _ = log_sender.send(error_log).await;
 
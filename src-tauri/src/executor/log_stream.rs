@@ -0,0 +1,145 @@
+use serde::Serialize;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc::{self, error::SendError, error::TrySendError};
+use tokio::time::Instant;
+
+use crate::database::models::LogEntry;
+
+/// Lines buffered between the executor readers and the batcher
+pub const LOG_CHANNEL_CAPACITY: usize = 1000;
+/// A batch is flushed once it holds this many entries...
+pub const BATCH_MAX_ENTRIES: usize = 200;
+/// ...or this long after its first entry arrived
+pub const BATCH_WINDOW: Duration = Duration::from_millis(100);
+
+/// Payload of the `log-batch` event
+#[derive(Debug, Serialize)]
+pub struct LogBatch {
+    pub execution_id: String,
+    pub entries: Vec<LogEntry>,
+    pub dropped: u64, // Output lines dropped since the previous batch
+}
+
+/// Sending side of an execution's log stream.
+///
+/// Backpressure policy: script output lines (`send_line`) are dropped and counted
+/// when the batcher falls behind, so a chatty script never blocks on a full pipe.
+/// Status messages of the executor itself (`send`) wait for room and are never dropped.
+#[derive(Clone)]
+pub struct LogSender {
+    tx: mpsc::Sender<LogEntry>,
+    dropped: Arc<AtomicU64>,
+}
+
+impl LogSender {
+    /// Send a status message, waiting for room in the channel
+    pub async fn send(&self, log: LogEntry) -> Result<(), SendError<LogEntry>> {
+        self.tx.send(log).await
+    }
+
+    /// Send one line of script output, dropping it if the channel is full
+    pub fn send_line(&self, log: LogEntry) {
+        if let Err(TrySendError::Full(_)) = self.tx.try_send(log) {
+            self.dropped.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+}
+
+/// Receiving side of an execution's log stream
+pub struct LogReceiver {
+    rx: mpsc::Receiver<LogEntry>,
+    dropped: Arc<AtomicU64>,
+}
+
+impl LogReceiver {
+    /// Wait for the next batch; None once every sender is gone and nothing is left
+    pub async fn next_batch(&mut self) -> Option<Vec<LogEntry>> {
+        let first = self.rx.recv().await?;
+        let deadline = Instant::now() + BATCH_WINDOW;
+
+        let mut entries = vec![first];
+        while entries.len() < BATCH_MAX_ENTRIES {
+            match tokio::time::timeout_at(deadline, self.rx.recv()).await {
+                Ok(Some(entry)) => entries.push(entry),
+                Ok(None) | Err(_) => break,
+            }
+        }
+        Some(entries)
+    }
+
+    /// Number of lines dropped since the last call
+    pub fn take_dropped(&self) -> u64 {
+        self.dropped.swap(0, Ordering::Relaxed)
+    }
+}
+
+/// Create the log stream of one execution
+pub fn log_channel() -> (LogSender, LogReceiver) {
+    let (tx, rx) = mpsc::channel(LOG_CHANNEL_CAPACITY);
+    let dropped = Arc::new(AtomicU64::new(0));
+    (
+        LogSender {
+            tx,
+            dropped: dropped.clone(),
+        },
+        LogReceiver { rx, dropped },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line(message: &str) -> LogEntry {
+        LogEntry {
+            id: uuid::Uuid::new_v4().to_string(),
+            button_id: Some("button-1".to_string()),
+            monitor_id: None,
+            level: "info".to_string(),
+            message: message.to_string(),
+            timestamp: 0,
+            execution_id: Some("execution-1".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_batches_are_capped_by_count() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        runtime.block_on(async {
+            let (sender, mut receiver) = log_channel();
+            for i in 0..(BATCH_MAX_ENTRIES + 50) {
+                sender.send_line(line(&i.to_string()));
+            }
+            drop(sender);
+
+            assert_eq!(receiver.next_batch().await.unwrap().len(), BATCH_MAX_ENTRIES);
+            let rest = receiver.next_batch().await.unwrap();
+            assert_eq!(rest.len(), 50);
+            assert_eq!(rest[0].message, BATCH_MAX_ENTRIES.to_string());
+            assert!(receiver.next_batch().await.is_none());
+        });
+    }
+
+    #[test]
+    fn test_output_lines_are_dropped_when_full() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        runtime.block_on(async {
+            let (sender, mut receiver) = log_channel();
+            for _ in 0..(LOG_CHANNEL_CAPACITY + 25) {
+                sender.send_line(line("spam"));
+            }
+            assert_eq!(receiver.take_dropped(), 25);
+            assert_eq!(receiver.take_dropped(), 0);
+
+            // Status messages wait for room instead of being dropped
+            let status = tokio::spawn({
+                let sender = sender.clone();
+                async move { sender.send(line("done")).await.is_ok() }
+            });
+            receiver.next_batch().await.unwrap();
+            assert!(status.await.unwrap());
+        });
+    }
+}
@@ -1,7 +1,15 @@
 pub mod shell_executor;
 pub mod python_executor;
 pub mod js_executor;
+pub mod script_analyzer;
+pub mod log_stream;
+pub mod plan;
+pub mod preflight;
+pub mod runner;
 
 pub use shell_executor::{ShellExecutor, ExecutionResult};
 pub use python_executor::PythonExecutor;
 pub use js_executor::JsExecutor;
+pub use script_analyzer::{analyze_script, RiskFinding};
+pub use plan::{build_plan, EnvChange, ExecutionPlan, SecretMode};
+pub use preflight::PreflightReport;
@@ -0,0 +1,370 @@
+use regex::Regex;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
+use std::process::Command;
+use std::sync::OnceLock;
+
+use crate::database::models::Button;
+
+/// A variable the plan sets on top of the inherited environment
+#[derive(Debug, Clone, Serialize)]
+pub struct EnvChange {
+    pub name: String,
+    pub value: String,
+    pub previous: Option<String>, // Inherited value being overridden, if any
+}
+
+/// Everything needed to spawn a script, resolved up front so that
+/// a preview shows exactly what a real run would do
+#[derive(Debug, Clone, Serialize)]
+pub struct ExecutionPlan {
+    pub interpreter: String,           // Absolute path of the resolved interpreter
+    pub args: Vec<String>,             // Arguments after the interpreter
+    pub working_dir: String,
+    pub env: BTreeMap<String, String>, // Variables added to the inherited environment
+    pub script: String,                // Rendered script content
+}
+
+impl ExecutionPlan {
+    /// Build the command to spawn (stdio is configured by the executor)
+    pub fn command(&self) -> Command {
+        let mut command = Command::new(&self.interpreter);
+        command
+            .args(&self.args)
+            .envs(&self.env)
+            .current_dir(&self.working_dir);
+        command
+    }
+
+    /// Full argv including the interpreter
+    pub fn argv(&self) -> Vec<String> {
+        std::iter::once(self.interpreter.clone())
+            .chain(self.args.iter().cloned())
+            .collect()
+    }
+
+    /// Environment changes compared to the current process environment
+    pub fn env_diff(&self) -> Vec<EnvChange> {
+        self.env
+            .iter()
+            .map(|(name, value)| EnvChange {
+                name: name.clone(),
+                value: value.clone(),
+                previous: std::env::var(name).ok(),
+            })
+            .collect()
+    }
+}
+
+/// Where the DEVTOOLS_SECRET_* variables read by `${secret:NAME}` references get their values
+pub enum SecretMode<'a> {
+    Resolve(&'a dyn Fn(&str) -> Result<String, String>), // Real run: the secret values
+    Keep,                                                 // Preview: the references themselves
+}
+
+/// Resolve the interpreter, render the script and compute the environment for a button
+pub fn build_plan(
+    execution_id: &str,
+    button: &Button,
+    params: &HashMap<String, String>,
+    secrets: SecretMode,
+) -> Result<ExecutionPlan, String> {
+    let (interpreter, script_flag) = resolve_interpreter(&button.script_type)?;
+    let (script, secret_env) = render(&button.script_type, &button.script_content, params, secrets)?;
+
+    let working_dir = std::env::current_dir()
+        .map_err(|e| format!("Failed to resolve working directory: {}", e))?
+        .to_string_lossy()
+        .to_string();
+
+    let mut env = BTreeMap::new();
+    env.insert("DEVTOOLS_EXECUTION_ID".to_string(), execution_id.to_string());
+    env.insert("DEVTOOLS_BUTTON_ID".to_string(), button.id.clone());
+    env.insert("DEVTOOLS_BUTTON_NAME".to_string(), button.name.clone());
+    for (name, value) in params {
+        env.insert(param_env_name(name), value.clone());
+    }
+    // Secret values only reach the script through its environment, never its command line
+    env.extend(secret_env);
+
+    Ok(ExecutionPlan {
+        interpreter: interpreter.to_string_lossy().to_string(),
+        args: vec![script_flag.to_string(), script.clone()],
+        working_dir,
+        env,
+        script,
+    })
+}
+
+/// Environment variable a parameter is exported as, e.g. `branch` -> `DEVTOOLS_PARAM_BRANCH`
+pub fn param_env_name(name: &str) -> String {
+    let sanitized: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_uppercase() } else { '_' })
+        .collect();
+    format!("DEVTOOLS_PARAM_{}", sanitized)
+}
+
+fn placeholder_regex() -> &'static Regex {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    REGEX.get_or_init(|| Regex::new(r"\$\{(param|secret):([A-Za-z_][A-Za-z0-9_]*)\}").unwrap())
+}
+
+/// Environment variable a secret is exported as, e.g. `API_TOKEN` -> `DEVTOOLS_SECRET_API_TOKEN`
+pub fn secret_env_name(name: &str) -> String {
+    format!("DEVTOOLS_SECRET_{}", name)
+}
+
+/// Substitute `${param:NAME}` and `${secret:NAME}` in a single pass,
+/// so substituted values are never scanned for further placeholders.
+/// Parameters become quoted literals of the script language (see `quote_param`),
+/// secrets read their DEVTOOLS_SECRET_* variable (see `env_expansion`).
+pub fn render_script(
+    script_type: &str,
+    template: &str,
+    params: &HashMap<String, String>,
+    secrets: SecretMode,
+) -> Result<String, String> {
+    render(script_type, template, params, secrets).map(|(script, _)| script)
+}
+
+/// Rendered script and the DEVTOOLS_SECRET_* variables it reads
+fn render(
+    script_type: &str,
+    template: &str,
+    params: &HashMap<String, String>,
+    secrets: SecretMode,
+) -> Result<(String, BTreeMap<String, String>), String> {
+    let mut errors = Vec::new();
+    let mut secret_env = BTreeMap::new();
+
+    let rendered = placeholder_regex().replace_all(template, |caps: &regex::Captures| {
+        let name = &caps[2];
+        match (&caps[1], &secrets) {
+            ("param", _) => match params.get(name).map(|value| quote_param(script_type, name, value)) {
+                Some(Ok(quoted)) => quoted,
+                Some(Err(e)) => {
+                    errors.push(e);
+                    caps[0].to_string()
+                }
+                None => {
+                    errors.push(format!("Missing parameter '{}'", name));
+                    caps[0].to_string()
+                }
+            },
+            (_, mode) => {
+                let variable = secret_env_name(name);
+                let value = match mode {
+                    SecretMode::Resolve(lookup) => lookup(name),
+                    SecretMode::Keep => Ok(caps[0].to_string()),
+                };
+                let expansion =
+                    value.and_then(|value| Ok((env_expansion(script_type, name, &variable, &value)?, value)));
+                match expansion {
+                    Ok((expansion, value)) => {
+                        secret_env.insert(variable, value);
+                        expansion
+                    }
+                    Err(e) => {
+                        errors.push(e);
+                        caps[0].to_string()
+                    }
+                }
+            }
+        }
+    });
+
+    if errors.is_empty() {
+        Ok((rendered.into_owned(), secret_env))
+    } else {
+        Err(errors.join("; "))
+    }
+}
+
+/// Quote a parameter value as a single literal of the script language, so a value can never
+/// add commands or end the surrounding code. The value is also available unquoted in the
+/// DEVTOOLS_PARAM_* environment variable.
+pub fn quote_param(script_type: &str, name: &str, value: &str) -> Result<String, String> {
+    match script_type {
+        "shell" if cfg!(target_os = "windows") => {
+            // cmd has no escape that holds inside double quotes
+            if value.chars().any(|c| "\"%!^&|<>\r\n".contains(c)) {
+                return Err(format!("Parameter '{}' contains characters cmd cannot quote", name));
+            }
+            Ok(format!("\"{}\"", value))
+        }
+        "shell" => Ok(format!("'{}'", value.replace('\'', r"'\''"))),
+        // JSON strings are valid Python and JavaScript string literals
+        "python" | "javascript" => serde_json::to_string(value).map_err(|e| e.to_string()),
+        _ => Err(format!("Unknown script type: {}", script_type)),
+    }
+}
+
+/// Expression of the script language reading the variable a secret is exported as,
+/// used as a single value like a quoted parameter
+fn env_expansion(script_type: &str, name: &str, variable: &str, value: &str) -> Result<String, String> {
+    match script_type {
+        "shell" if cfg!(target_os = "windows") => {
+            // cmd expands %VAR% before parsing the line, so the value must be safe to paste
+            if value.chars().any(|c| "\"%!^&|<>\r\n".contains(c)) {
+                return Err(format!("Secret '{}' contains characters cmd cannot quote", name));
+            }
+            Ok(format!("\"%{}%\"", variable))
+        }
+        "shell" => Ok(format!("\"${}\"", variable)),
+        "python" => Ok(format!("__import__(\"os\").environ[\"{}\"]", variable)),
+        "javascript" => Ok(format!("process.env[\"{}\"]", variable)),
+        _ => Err(format!("Unknown script type: {}", script_type)),
+    }
+}
+
+/// Find the interpreter for a script type and the flag used to pass inline code
+fn resolve_interpreter(script_type: &str) -> Result<(PathBuf, &'static str), String> {
+    match script_type {
+        "shell" => {
+            if cfg!(target_os = "windows") {
+                find_in_path("cmd").map(|p| (p, "/C")).ok_or_else(|| "❌ 找不到 cmd".to_string())
+            } else {
+                find_in_path("bash").map(|p| (p, "-c")).ok_or_else(|| "❌ 找不到 bash".to_string())
+            }
+        }
+        "python" => find_in_path("python3")
+            .or_else(|| find_in_path("python"))
+            .map(|p| (p, "-c"))
+            .ok_or_else(|| "❌ Python 未安装或不在 PATH 中".to_string()),
+        "javascript" => find_in_path("node")
+            .map(|p| (p, "-e"))
+            .ok_or_else(|| "❌ Node.js 未安装或不在 PATH 中".to_string()),
+        _ => Err(format!("Unknown script type: {}", script_type)),
+    }
+}
+
+/// Look up an executable on PATH
+fn find_in_path(program: &str) -> Option<PathBuf> {
+    let path = std::env::var_os("PATH")?;
+    let extensions: &[&str] = if cfg!(target_os = "windows") {
+        &[".exe", ".cmd", ".bat"]
+    } else {
+        &[""]
+    };
+
+    std::env::split_paths(&path)
+        .flat_map(|dir| extensions.iter().map(move |ext| dir.join(format!("{}{}", program, ext))))
+        .find(|candidate| candidate.is_file())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn button(script_type: &str, script: &str) -> Button {
+        Button {
+            id: "b1".to_string(),
+            name: "Deploy".to_string(),
+            icon: None,
+            script_type: script_type.to_string(),
+            script_content: script.to_string(),
+            folder_id: None,
+            position: 0,
+            created_at: 0,
+            updated_at: 0,
+            requires_confirmation: false,
+            retry_policy: None,
+            notifications_muted: false,
+        }
+    }
+
+    #[test]
+    fn test_render_script() {
+        let params = HashMap::from([("branch".to_string(), "main".to_string())]);
+        let lookup = |name: &str| -> Result<String, String> {
+            if name == "TOKEN" {
+                Ok("t0k3n".to_string())
+            } else {
+                Err(format!("Unknown secret(s): {}", name))
+            }
+        };
+
+        let script = "git push ${param:branch} # ${secret:TOKEN}";
+        assert_eq!(
+            render_script("shell", script, &params, SecretMode::Resolve(&lookup)).unwrap(),
+            "git push 'main' # \"$DEVTOOLS_SECRET_TOKEN\""
+        );
+        assert_eq!(
+            render_script("python", "print(${secret:TOKEN})", &params, SecretMode::Keep).unwrap(),
+            "print(__import__(\"os\").environ[\"DEVTOOLS_SECRET_TOKEN\"])"
+        );
+        assert_eq!(
+            render_script("javascript", "log(${secret:TOKEN})", &params, SecretMode::Keep).unwrap(),
+            "log(process.env[\"DEVTOOLS_SECRET_TOKEN\"])"
+        );
+
+        let err = render_script("shell", "${param:tag} ${secret:OTHER}", &params, SecretMode::Resolve(&lookup))
+            .unwrap_err();
+        assert!(err.contains("tag") && err.contains("OTHER"));
+    }
+
+    #[test]
+    fn test_param_values_are_not_rendered_again() {
+        let params = HashMap::from([("x".to_string(), "${secret:TOKEN}".to_string())]);
+        let lookup = |_: &str| -> Result<String, String> { Ok("leaked".to_string()) };
+        assert_eq!(
+            render_script("shell", "echo ${param:x}", &params, SecretMode::Resolve(&lookup)).unwrap(),
+            "echo '${secret:TOKEN}'"
+        );
+    }
+
+    #[test]
+    fn test_param_values_stay_literals() {
+        let value = "x'; rm -rf ~; echo \"$(id)\"\n";
+        let params = HashMap::from([("name".to_string(), value.to_string())]);
+        let render = |script_type: &str, script: &str| render_script(script_type, script, &params, SecretMode::Keep);
+
+        if cfg!(not(target_os = "windows")) {
+            let script = render("shell", "printf %s ${param:name}").unwrap();
+            assert_eq!(script, "printf %s 'x'\\''; rm -rf ~; echo \"$(id)\"\n'");
+            let output = std::process::Command::new("sh").arg("-c").arg(&script).output().unwrap();
+            assert_eq!(String::from_utf8_lossy(&output.stdout), value);
+        }
+        assert_eq!(
+            render("python", "print(${param:name})").unwrap(),
+            r#"print("x'; rm -rf ~; echo \"$(id)\"\n")"#
+        );
+        assert_eq!(
+            render("javascript", "console.log(${param:name})").unwrap(),
+            r#"console.log("x'; rm -rf ~; echo \"$(id)\"\n")"#
+        );
+    }
+
+    #[test]
+    fn test_build_plan_env() {
+        let params = HashMap::from([("target_env".to_string(), "staging".to_string())]);
+        let plan = build_plan("e1", &button("shell", "echo ${param:target_env}"), &params, SecretMode::Keep).unwrap();
+
+        assert_eq!(plan.script, "echo 'staging'");
+        assert_eq!(plan.args[1], plan.script);
+        assert_eq!(plan.env.get("DEVTOOLS_PARAM_TARGET_ENV").unwrap(), "staging");
+        assert_eq!(plan.env.get("DEVTOOLS_EXECUTION_ID").unwrap(), "e1");
+        assert!(build_plan("e1", &button("ruby", "puts 1"), &params, SecretMode::Keep).is_err());
+    }
+
+    #[test]
+    fn test_secrets_are_passed_through_the_environment() {
+        let secret = "x'; echo pwned; $(id)\n";
+        let lookup = |_: &str| -> Result<String, String> { Ok(secret.to_string()) };
+        let script = button("shell", "printf %s ${secret:TOKEN}");
+        let plan = build_plan("e1", &script, &HashMap::new(), SecretMode::Resolve(&lookup)).unwrap();
+
+        assert!(plan.argv().iter().all(|arg| !arg.contains("pwned")), "{:?}", plan.argv());
+        assert_eq!(plan.env.get("DEVTOOLS_SECRET_TOKEN").unwrap(), secret);
+        if cfg!(not(target_os = "windows")) {
+            let output = plan.command().output().unwrap();
+            assert_eq!(String::from_utf8_lossy(&output.stdout), secret);
+        }
+
+        // Previews show where the value comes from, never the value
+        let preview = build_plan("e1", &script, &HashMap::new(), SecretMode::Keep).unwrap();
+        assert_eq!(preview.env.get("DEVTOOLS_SECRET_TOKEN").unwrap(), "${secret:TOKEN}");
+    }
+}
@@ -0,0 +1,125 @@
+use serde::Serialize;
+use std::collections::HashMap;
+
+use crate::database::models::Button;
+use super::plan::{render_script, SecretMode};
+use super::{analyze_script, RiskFinding};
+
+/// Result of checking a button before execution
+#[derive(Debug, Serialize)]
+pub struct PreflightReport {
+    pub button_id: String,
+    pub requires_confirmation: bool,
+    pub flagged_by_button: bool,     // Button has requires_confirmation set
+    pub findings: Vec<RiskFinding>,  // Risky patterns detected in the script
+}
+
+impl PreflightReport {
+    /// Check a button as it would run with `params`. The scan covers the rendered script,
+    /// or the template while parameters are missing.
+    pub fn for_button(button: &Button, params: &HashMap<String, String>) -> Self {
+        let rendered = render_script(&button.script_type, &button.script_content, params, SecretMode::Keep);
+        let findings = analyze_script(rendered.as_deref().unwrap_or(&button.script_content));
+        Self {
+            button_id: button.id.clone(),
+            requires_confirmation: button.requires_confirmation || !findings.is_empty(),
+            flagged_by_button: button.requires_confirmation,
+            findings,
+        }
+    }
+}
+
+/// Stable fingerprint of the script a confirmation applies to (64-bit FNV-1a, hex)
+pub fn script_fingerprint(button: &Button) -> String {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in button.script_type.bytes().chain([0]).chain(button.script_content.bytes()) {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    format!("{:016x}", hash)
+}
+
+/// Check a run nobody is watching (schedule, file trigger, remediation) against the confirmation
+/// given when it was set up. Buttons needing confirmation only run the exact script that was confirmed.
+pub fn check_unattended(button: &Button, confirmed_script: Option<&str>) -> Result<(), String> {
+    // Unattended runs pass no parameters
+    if !PreflightReport::for_button(button, &HashMap::new()).requires_confirmation {
+        return Ok(());
+    }
+    match confirmed_script {
+        Some(fingerprint) if fingerprint == script_fingerprint(button) => Ok(()),
+        Some(_) => Err(format!(
+            "The script of '{}' changed since the automatic run was confirmed",
+            button.name
+        )),
+        None => Err(format!(
+            "'{}' requires confirmation before it can run automatically",
+            button.name
+        )),
+    }
+}
+
+/// Confirmation to store when an automatic run is created or updated: the current script if the user
+/// confirmed it, otherwise the previous confirmation if it still applies
+pub fn confirm_unattended(button: &Button, confirmed: bool, previous: Option<String>) -> Result<Option<String>, String> {
+    if confirmed {
+        return Ok(Some(script_fingerprint(button)));
+    }
+    check_unattended(button, previous.as_deref())?;
+    Ok(previous)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn button(script: &str, requires_confirmation: bool) -> Button {
+        Button {
+            id: "b1".to_string(),
+            name: "deploy".to_string(),
+            icon: None,
+            script_type: "shell".to_string(),
+            script_content: script.to_string(),
+            folder_id: None,
+            position: 0,
+            created_at: 0,
+            updated_at: 0,
+            requires_confirmation,
+            retry_policy: None,
+            notifications_muted: false,
+        }
+    }
+
+    #[test]
+    fn test_unattended_runs_need_the_confirmed_script() {
+        let safe = button("echo hi", false);
+        assert!(check_unattended(&safe, None).is_ok());
+        assert_eq!(confirm_unattended(&safe, false, None), Ok(None));
+
+        let risky = button("kubectl delete pod api", false);
+        assert!(check_unattended(&risky, None).is_err());
+        assert!(confirm_unattended(&risky, false, None).is_err());
+        let confirmed = confirm_unattended(&risky, true, None).unwrap();
+        assert!(check_unattended(&risky, confirmed.as_deref()).is_ok());
+        // Keeping an earlier confirmation of the same script needs no new one
+        assert_eq!(confirm_unattended(&risky, false, confirmed.clone()).unwrap(), confirmed);
+
+        let changed = button("kubectl delete pod web", false);
+        let error = check_unattended(&changed, confirmed.as_deref()).unwrap_err();
+        assert!(error.contains("changed"), "{}", error);
+
+        // Flagging the button later also stops unconfirmed runs
+        assert!(check_unattended(&button("echo hi", true), None).is_err());
+    }
+
+    #[test]
+    fn test_scans_the_rendered_script() {
+        let cleanup = button("rm -rf ${param:dir}", false);
+        let params = |dir: &str| HashMap::from([("dir".to_string(), dir.to_string())]);
+
+        assert!(!PreflightReport::for_button(&cleanup, &params("./dist")).requires_confirmation);
+        let report = PreflightReport::for_button(&cleanup, &params("/"));
+        assert!(report.requires_confirmation);
+        assert_eq!(report.findings[0].snippet, "rm -rf '/'");
+    }
+}
@@ -1,31 +1,32 @@
-use std::process::{Command, Stdio};
+use std::process::Stdio;
 use std::io::{BufRead, BufReader};
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
-use tokio::sync::mpsc;
 
 use crate::database::models::LogEntry;
+use super::log_stream::LogSender;
 use crate::executor::ExecutionResult;
+use super::plan::ExecutionPlan;
 
 pub struct PythonExecutor {
     pub execution_id: String,
     pub button_id: String,
     pub button_name: String,
-    pub script: String,
+    pub plan: ExecutionPlan,
 }
 
 impl PythonExecutor {
-    pub fn new(execution_id: String, button_id: String, button_name: String, script: String) -> Self {
+    pub fn new(execution_id: String, button_id: String, button_name: String, plan: ExecutionPlan) -> Self {
         Self {
             execution_id,
             button_id,
             button_name,
-            script,
+            plan,
         }
     }
 
     pub async fn execute(
         &self,
-        log_sender: mpsc::Sender<LogEntry>,
+        log_sender: LogSender,
     ) -> Result<ExecutionResult, String> {
         let start_time = SystemTime::now();
 
@@ -40,35 +41,14 @@ impl PythonExecutor {
                 .duration_since(UNIX_EPOCH)
                 .unwrap()
                 .as_secs() as i64,
+            execution_id: Some(self.execution_id.clone()),
         };
         let _ = log_sender.send(start_log).await;
 
-        // Check if Python is available
-        let python_cmd = if Command::new("python3").arg("--version").output().is_ok() {
-            "python3"
-        } else if Command::new("python").arg("--version").output().is_ok() {
-            "python"
-        } else {
-            let error_msg = "❌ Python 未安装或不在 PATH 中".to_string();
-            let error_log = LogEntry {
-                id: uuid::Uuid::new_v4().to_string(),
-                button_id: Some(self.button_id.clone()),
-                monitor_id: None,
-                level: "error".to_string(),
-                message: error_msg.clone(),
-                timestamp: SystemTime::now()
-                    .duration_since(UNIX_EPOCH)
-                    .unwrap()
-                    .as_secs() as i64,
-            };
-            let _ = log_sender.send(error_log).await;
-            return Err(error_msg);
-        };
-
         // Execute the Python script
-        let mut child = match Command::new(python_cmd)
-            .arg("-c")
-            .arg(&self.script)
+        let mut child = match self
+            .plan
+            .command()
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
             .spawn()
@@ -86,6 +66,7 @@ impl PythonExecutor {
                         .duration_since(UNIX_EPOCH)
                         .unwrap()
                         .as_secs() as i64,
+                    execution_id: Some(self.execution_id.clone()),
                 };
                 let _ = log_sender.send(error_log).await;
                 return Err(error_msg);
@@ -95,6 +76,7 @@ impl PythonExecutor {
         // Capture stdout
         if let Some(stdout) = child.stdout.take() {
             let button_id = self.button_id.clone();
+            let execution_id = self.execution_id.clone();
             let sender = log_sender.clone();
             tokio::spawn(async move {
                 let reader = BufReader::new(stdout);
@@ -110,8 +92,9 @@ impl PythonExecutor {
                                 .duration_since(UNIX_EPOCH)
                                 .unwrap()
                                 .as_secs() as i64,
+                            execution_id: Some(execution_id.clone()),
                         };
-                        let _ = sender.send(log).await;
+                        sender.send_line(log);
                     }
                 }
             });
@@ -120,6 +103,7 @@ impl PythonExecutor {
         // Capture stderr
         if let Some(stderr) = child.stderr.take() {
             let button_id = self.button_id.clone();
+            let execution_id = self.execution_id.clone();
             let sender = log_sender.clone();
             tokio::spawn(async move {
                 let reader = BufReader::new(stderr);
@@ -135,8 +119,9 @@ impl PythonExecutor {
                                 .duration_since(UNIX_EPOCH)
                                 .unwrap()
                                 .as_secs() as i64,
+                            execution_id: Some(execution_id.clone()),
                         };
-                        let _ = sender.send(log).await;
+                        sender.send_line(log);
                     }
                 }
             });
@@ -174,6 +159,7 @@ impl PythonExecutor {
                         .duration_since(UNIX_EPOCH)
                         .unwrap()
                         .as_secs() as i64,
+                    execution_id: Some(self.execution_id.clone()),
                 };
                 let _ = log_sender.send(result_log).await;
 
@@ -196,6 +182,7 @@ impl PythonExecutor {
                         .duration_since(UNIX_EPOCH)
                         .unwrap()
                         .as_secs() as i64,
+                    execution_id: Some(self.execution_id.clone()),
                 };
                 let _ = log_sender.send(error_log).await;
 
@@ -218,6 +205,7 @@ impl PythonExecutor {
                         .duration_since(UNIX_EPOCH)
                         .unwrap()
                         .as_secs() as i64,
+                    execution_id: Some(self.execution_id.clone()),
                 };
                 let _ = log_sender.send(error_log).await;
 
@@ -240,6 +228,7 @@ impl PythonExecutor {
                         .duration_since(UNIX_EPOCH)
                         .unwrap()
                         .as_secs() as i64,
+                    execution_id: Some(self.execution_id.clone()),
                 };
                 let _ = log_sender.send(error_log).await;
 
@@ -0,0 +1,429 @@
+use std::collections::{BTreeMap, HashMap};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::task::JoinHandle;
+
+use crate::commands::SecretStoreState;
+use crate::database::models::{Button, ExecutionRecord, LogEntry, RetryPolicy};
+use crate::database::repository;
+use crate::database::Database;
+use crate::notifications::{self, ChannelMessage};
+use crate::redaction;
+use super::log_stream::{log_channel, LogBatch, LogReceiver, LogSender};
+use super::plan::{build_plan, ExecutionPlan, SecretMode};
+use super::{ExecutionResult, JsExecutor, PythonExecutor, ShellExecutor};
+
+/// Trigger source of runs started from the UI
+pub const TRIGGER_MANUAL: &str = "manual";
+/// Trigger source of runs started by the scheduler
+pub const TRIGGER_SCHEDULE: &str = "schedule";
+/// Trigger source of runs started by a file watch
+pub const TRIGGER_FILE_WATCH: &str = "file_watch";
+/// Trigger source of runs started by a failing monitor
+pub const TRIGGER_REMEDIATION: &str = "remediation";
+
+/// What started a run and the extra environment it provides to the script
+#[derive(Debug, Clone)]
+pub struct Trigger {
+    pub source: &'static str,
+    pub detail: Option<String>,        // Shown in execution history, e.g. the changed files
+    pub env: BTreeMap<String, String>, // Added to the plan environment
+}
+
+impl Trigger {
+    pub fn manual() -> Self {
+        Self::new(TRIGGER_MANUAL, None)
+    }
+
+    pub fn schedule(schedule_id: &str) -> Self {
+        let mut trigger = Self::new(TRIGGER_SCHEDULE, Some(schedule_id.to_string()));
+        trigger.env.insert("DEVTOOLS_SCHEDULE_ID".to_string(), schedule_id.to_string());
+        trigger
+    }
+
+    /// Changed paths are exported newline separated in DEVTOOLS_CHANGED_FILES
+    pub fn file_watch(trigger_id: &str, changed_files: &[String]) -> Self {
+        let mut trigger = Self::new(TRIGGER_FILE_WATCH, Some(changed_files.join(", ")));
+        trigger.env.insert("DEVTOOLS_FILE_TRIGGER_ID".to_string(), trigger_id.to_string());
+        trigger.env.insert("DEVTOOLS_CHANGED_FILES".to_string(), changed_files.join("\n"));
+        if let Some(first) = changed_files.first() {
+            trigger.env.insert("DEVTOOLS_CHANGED_FILE".to_string(), first.clone());
+        }
+        trigger
+    }
+
+    pub fn remediation(monitor_id: &str, monitor_name: &str) -> Self {
+        let mut trigger = Self::new(TRIGGER_REMEDIATION, Some(monitor_name.to_string()));
+        trigger.env.insert("DEVTOOLS_MONITOR_ID".to_string(), monitor_id.to_string());
+        trigger.env.insert("DEVTOOLS_MONITOR_NAME".to_string(), monitor_name.to_string());
+        trigger
+    }
+
+    fn new(source: &'static str, detail: Option<String>) -> Self {
+        let env = BTreeMap::from([("DEVTOOLS_TRIGGER".to_string(), source.to_string())]);
+        Self { source, detail, env }
+    }
+}
+
+const MAX_ATTEMPTS_LIMIT: u32 = 10;
+const MAX_BACKOFF_MS: u64 = 10 * 60 * 1000;
+
+/// Check that retry settings are within sane bounds
+pub fn validate_retry_policy(policy: &RetryPolicy) -> Result<(), String> {
+    if policy.max_attempts == 0 || policy.max_attempts > MAX_ATTEMPTS_LIMIT {
+        return Err(format!("max_attempts must be between 1 and {}", MAX_ATTEMPTS_LIMIT));
+    }
+    if policy.backoff_ms > MAX_BACKOFF_MS {
+        return Err(format!("backoff_ms must not exceed {}", MAX_BACKOFF_MS));
+    }
+    if !policy.backoff_multiplier.is_finite() || policy.backoff_multiplier < 1.0 {
+        return Err("backoff_multiplier must be at least 1.0".to_string());
+    }
+    Ok(())
+}
+
+/// Delay before the given retry (1 = first retry), capped at the maximum backoff
+pub fn retry_delay(policy: &RetryPolicy, retry: u32) -> Duration {
+    let factor = policy.backoff_multiplier.max(1.0).powi(retry.saturating_sub(1) as i32);
+    let delay_ms = (policy.backoff_ms as f64 * factor).min(MAX_BACKOFF_MS as f64);
+    Duration::from_millis(delay_ms as u64)
+}
+
+/// Whether a failed attempt qualifies for a retry under the policy
+pub fn should_retry(policy: &RetryPolicy, result: &ExecutionResult) -> bool {
+    if result.success {
+        return false;
+    }
+    policy.retry_exit_codes.is_empty()
+        || result
+            .exit_code
+            .is_some_and(|code| policy.retry_exit_codes.contains(&code))
+}
+
+/// A run started in the background
+pub struct StartedExecution {
+    pub execution_id: String,
+    pub task: JoinHandle<()>, // Finishes after the last attempt
+}
+
+/// Start a button run in the background.
+/// All attempts share one execution record; logs are redacted, stored and emitted.
+pub fn start_execution(
+    app_handle: &AppHandle,
+    button: Button,
+    params: &HashMap<String, String>,
+    trigger: Trigger,
+) -> Result<StartedExecution, String> {
+    let execution_id = uuid::Uuid::new_v4().to_string();
+
+    // Resolve the interpreter, parameters and ${secret:NAME} references right before running
+    let mut plan = {
+        let secrets = app_handle.try_state::<SecretStoreState>();
+        let lookup = |name: &str| match &secrets {
+            Some(secrets) => secrets.0.lookup(name),
+            None => Err("Secret store is not available".to_string()),
+        };
+        build_plan(&execution_id, &button, params, SecretMode::Resolve(&lookup))?
+    };
+    plan.env.extend(trigger.env);
+
+    let record = ExecutionRecord {
+        id: execution_id.clone(),
+        button_id: button.id.clone(),
+        trigger: trigger.source.to_string(),
+        trigger_detail: trigger.detail,
+        status: "running".to_string(),
+        attempts: 0,
+        exit_code: None,
+        error: None,
+        started_at: chrono::Utc::now().timestamp(),
+        finished_at: None,
+        duration_ms: None,
+    };
+    save_execution(app_handle, &record);
+
+    // Create a channel for log streaming
+    let (log_tx, log_rx) = log_channel();
+    spawn_log_receiver(app_handle.clone(), &button, &execution_id, log_rx);
+
+    // Spawn execution in background
+    let app_handle = app_handle.clone();
+    let task = tokio::spawn(async move {
+        run_attempts(&app_handle, button, plan, record, log_tx).await;
+    });
+
+    Ok(StartedExecution { execution_id, task })
+}
+
+/// Receive logs from the executors in batches, redact them, emit them to the frontend and store them
+fn spawn_log_receiver(app_handle: AppHandle, button: &Button, execution_id: &str, mut log_rx: LogReceiver) {
+    let button_id = button.id.clone();
+    let execution_id = execution_id.to_string();
+    tokio::spawn(async move {
+        loop {
+            let received = log_rx.next_batch().await;
+            let finished = received.is_none();
+            let mut entries = received.unwrap_or_default();
+
+            // Report lines dropped by backpressure once per batch
+            let dropped = log_rx.take_dropped();
+            if dropped > 0 {
+                entries.push(LogEntry {
+                    id: uuid::Uuid::new_v4().to_string(),
+                    button_id: Some(button_id.clone()),
+                    monitor_id: None,
+                    level: "warning".to_string(),
+                    message: format!("⚠️ 输出过快，已丢弃 {} 行日志", dropped),
+                    timestamp: chrono::Utc::now().timestamp(),
+                    execution_id: Some(execution_id.clone()),
+                });
+            }
+
+            if !entries.is_empty() {
+                // Never persist or emit secret values or other sensitive data
+                for entry in &mut entries {
+                    entry.message = redaction::redact_log_message(&app_handle, &entry.message);
+                }
+
+                let batch = LogBatch {
+                    execution_id: execution_id.clone(),
+                    entries,
+                    dropped,
+                };
+                let _ = app_handle.emit("log-batch", &batch);
+
+                if let Some(db) = app_handle.try_state::<Database>() {
+                    db.insert_logs(batch.entries);
+                }
+            }
+
+            if finished {
+                break;
+            }
+        }
+    });
+}
+
+/// Run the plan until it succeeds or the retry policy gives up, then finish the record
+async fn run_attempts(
+    app_handle: &AppHandle,
+    button: Button,
+    plan: ExecutionPlan,
+    mut record: ExecutionRecord,
+    log_sender: LogSender,
+) {
+    let started = Instant::now();
+    let max_attempts = button.retry_policy.as_ref().map_or(1, |p| p.max_attempts.max(1));
+
+    let result = loop {
+        record.attempts += 1;
+        save_execution(app_handle, &record);
+
+        let result = run_once(&record.id, &button, plan.clone(), log_sender.clone()).await;
+
+        match &button.retry_policy {
+            Some(policy) if record.attempts < max_attempts && should_retry(policy, &result) => {
+                let delay = retry_delay(policy, record.attempts);
+                let log = LogEntry {
+                    id: uuid::Uuid::new_v4().to_string(),
+                    button_id: Some(button.id.clone()),
+                    monitor_id: None,
+                    level: "warning".to_string(),
+                    message: format!(
+                        "🔁 第 {}/{} 次尝试失败，{:.1} 秒后重试",
+                        record.attempts,
+                        max_attempts,
+                        delay.as_secs_f64()
+                    ),
+                    timestamp: chrono::Utc::now().timestamp(),
+                    execution_id: Some(record.id.clone()),
+                };
+                let _ = log_sender.send(log).await;
+                tokio::time::sleep(delay).await;
+            }
+            _ => break result,
+        }
+    };
+
+    record.status = if result.success { "success" } else { "failed" }.to_string();
+    record.exit_code = result.exit_code;
+    record.error = result.error;
+    record.finished_at = Some(chrono::Utc::now().timestamp());
+    record.duration_ms = Some(started.elapsed().as_millis() as i64);
+    save_execution(app_handle, &record);
+
+    let _ = app_handle.emit("execution-finished", &record);
+
+    let error = record.error.as_deref().map(|error| redaction::redact_log_message(app_handle, error));
+
+    // Long runs are often left in the background, let the user know they are done
+    if let Some((title, body)) = long_run_notification(&button, result.success, started.elapsed(), error.as_deref()) {
+        notifications::notify(app_handle, &title, &body).await;
+    }
+
+    if !result.success {
+        let mut message = format!("第 {} 次尝试后失败", record.attempts);
+        if let Some(error) = &error {
+            message.push_str(&format!("：{}", error));
+        }
+        notifications::dispatch(
+            app_handle,
+            ChannelMessage {
+                event: "execution_failed".to_string(),
+                title: format!("❌ {} 运行失败", button.name),
+                message,
+                source: button.name.clone(),
+                timestamp: record.finished_at.unwrap_or_default(),
+            },
+        )
+        .await;
+    }
+}
+
+/// Title and body of the notification for a finished run, None for short runs and muted buttons
+fn long_run_notification(button: &Button, success: bool, elapsed: Duration, error: Option<&str>) -> Option<(String, String)> {
+    if button.notifications_muted || elapsed < notifications::LONG_RUN_THRESHOLD {
+        return None;
+    }
+    let title = if success {
+        format!("✅ {} 运行完成", button.name)
+    } else {
+        format!("❌ {} 运行失败", button.name)
+    };
+    let mut body = format!("耗时 {:.0} 秒", elapsed.as_secs_f64());
+    if let Some(error) = error {
+        body.push_str(&format!("：{}", error));
+    }
+    Some((title, body))
+}
+
+/// Run a single attempt with the executor matching the script type
+async fn run_once(
+    execution_id: &str,
+    button: &Button,
+    plan: ExecutionPlan,
+    log_sender: LogSender,
+) -> ExecutionResult {
+    let execution_id = execution_id.to_string();
+    let result = match button.script_type.as_str() {
+        "shell" => {
+            ShellExecutor::new(execution_id, button.id.clone(), button.name.clone(), plan)
+                .execute(log_sender)
+                .await
+        }
+        "python" => {
+            PythonExecutor::new(execution_id, button.id.clone(), button.name.clone(), plan)
+                .execute(log_sender)
+                .await
+        }
+        "javascript" => {
+            JsExecutor::new(execution_id, button.id.clone(), button.name.clone(), plan)
+                .execute(log_sender)
+                .await
+        }
+        _ => Err(format!("Unknown script type: {}", button.script_type)),
+    };
+
+    // Spawn failures count as a failed attempt without an exit code
+    result.unwrap_or_else(|e| ExecutionResult {
+        success: false,
+        exit_code: None,
+        duration_ms: 0,
+        error: Some(e),
+    })
+}
+
+fn save_execution(app_handle: &AppHandle, record: &ExecutionRecord) {
+    if let Some(db) = app_handle.try_state::<Database>() {
+        let record = record.clone();
+        db.write_detached(move |conn| {
+            repository::save_execution(conn, &record)
+                .map_err(|e| format!("Failed to save execution {}: {}", record.id, e))
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(retry_exit_codes: Vec<i32>) -> RetryPolicy {
+        RetryPolicy {
+            max_attempts: 3,
+            backoff_ms: 1000,
+            backoff_multiplier: 2.0,
+            retry_exit_codes,
+        }
+    }
+
+    fn failed(exit_code: Option<i32>) -> ExecutionResult {
+        ExecutionResult {
+            success: false,
+            exit_code,
+            duration_ms: 0,
+            error: None,
+        }
+    }
+
+    #[test]
+    fn test_only_long_runs_notify() {
+        let mut button = Button {
+            id: "b1".to_string(),
+            name: "build".to_string(),
+            icon: None,
+            script_type: "shell".to_string(),
+            script_content: "make".to_string(),
+            folder_id: None,
+            position: 0,
+            created_at: 0,
+            updated_at: 0,
+            requires_confirmation: false,
+            retry_policy: None,
+            notifications_muted: false,
+        };
+        let short = notifications::LONG_RUN_THRESHOLD - Duration::from_millis(1);
+        assert_eq!(long_run_notification(&button, true, short, None), None);
+
+        let (title, body) = long_run_notification(&button, true, notifications::LONG_RUN_THRESHOLD, None).unwrap();
+        assert_eq!(title, "✅ build 运行完成");
+        assert_eq!(body, "耗时 30 秒");
+        let (title, body) =
+            long_run_notification(&button, false, Duration::from_secs(95), Some("exit code 2")).unwrap();
+        assert_eq!(title, "❌ build 运行失败");
+        assert_eq!(body, "耗时 95 秒：exit code 2");
+
+        button.notifications_muted = true;
+        assert_eq!(long_run_notification(&button, true, Duration::from_secs(95), None), None);
+    }
+
+    #[test]
+    fn test_retry_delay_backoff() {
+        let policy = policy(vec![]);
+        assert_eq!(retry_delay(&policy, 1), Duration::from_millis(1000));
+        assert_eq!(retry_delay(&policy, 2), Duration::from_millis(2000));
+        assert_eq!(retry_delay(&policy, 3), Duration::from_millis(4000));
+        assert_eq!(retry_delay(&policy, 30), Duration::from_millis(MAX_BACKOFF_MS));
+    }
+
+    #[test]
+    fn test_should_retry_exit_codes() {
+        let any = policy(vec![]);
+        assert!(should_retry(&any, &failed(Some(1))));
+        assert!(should_retry(&any, &failed(None)));
+
+        let network_only = policy(vec![128, 6]);
+        assert!(should_retry(&network_only, &failed(Some(6))));
+        assert!(!should_retry(&network_only, &failed(Some(1))));
+        assert!(!should_retry(&network_only, &failed(None)));
+
+        let ok = ExecutionResult { success: true, exit_code: Some(0), duration_ms: 0, error: None };
+        assert!(!should_retry(&any, &ok));
+    }
+
+    #[test]
+    fn test_validate_retry_policy() {
+        assert!(validate_retry_policy(&policy(vec![])).is_ok());
+        assert!(validate_retry_policy(&RetryPolicy { max_attempts: 0, ..policy(vec![]) }).is_err());
+        assert!(validate_retry_policy(&RetryPolicy { backoff_multiplier: 0.5, ..policy(vec![]) }).is_err());
+    }
+}
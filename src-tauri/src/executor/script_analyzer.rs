@@ -0,0 +1,115 @@
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::sync::OnceLock;
+
+/// A risky pattern found in a script
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RiskFinding {
+    pub rule: String,        // Stable rule id, e.g. "rm-rf-root"
+    pub description: String, // Human readable reason shown in the confirmation dialog
+    pub line: usize,         // 1-based line number
+    pub snippet: String,     // The offending line (trimmed)
+}
+
+/// Static rule definition
+struct RiskRule {
+    id: &'static str,
+    description: &'static str,
+    pattern: &'static str,
+}
+
+const RULES: &[RiskRule] = &[
+    RiskRule {
+        id: "rm-rf-root",
+        description: "递归强制删除根目录或用户主目录",
+        pattern: r#"\brm\s+(?:-[a-zA-Z]*(?:[rR][a-zA-Z]*f|f[a-zA-Z]*[rR])[a-zA-Z]*|-[rR]\s+-f|-f\s+-[rR]|--recursive\s+--force|--force\s+--recursive)\s+(?:--no-preserve-root\s+)?['"]?(?:/|/\*|~|~/|\$HOME|\$HOME/)['"]?(?:\s|;|&|\||$)"#,
+    },
+    RiskRule {
+        id: "git-push-force",
+        description: "强制推送可能覆盖远程分支历史",
+        pattern: r"\bgit\b[^\n;&|]*\bpush\b[^\n;&|]*\s(?:--force\b|-f\b|\+\S)",
+    },
+    RiskRule {
+        id: "sql-drop",
+        description: "删除数据库表或数据库",
+        pattern: r"(?i)\bDROP\s+(?:TABLE|DATABASE|SCHEMA)\b",
+    },
+    RiskRule {
+        id: "kubectl-delete",
+        description: "删除 Kubernetes 资源",
+        pattern: r"\bkubectl\b[^\n;&|]*\sdelete\b",
+    },
+];
+
+fn compiled_rules() -> &'static [(&'static RiskRule, Regex)] {
+    static COMPILED: OnceLock<Vec<(&'static RiskRule, Regex)>> = OnceLock::new();
+    COMPILED.get_or_init(|| {
+        RULES
+            .iter()
+            .map(|rule| (rule, Regex::new(rule.pattern).expect("invalid built-in risk pattern")))
+            .collect()
+    })
+}
+
+/// Scan script content line by line and report every risky pattern found
+pub fn analyze_script(script: &str) -> Vec<RiskFinding> {
+    let mut findings = Vec::new();
+
+    for (index, line) in script.lines().enumerate() {
+        for (rule, regex) in compiled_rules() {
+            if regex.is_match(line) {
+                findings.push(RiskFinding {
+                    rule: rule.id.to_string(),
+                    description: rule.description.to_string(),
+                    line: index + 1,
+                    snippet: line.trim().to_string(),
+                });
+            }
+        }
+    }
+
+    findings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules_for(script: &str) -> Vec<String> {
+        analyze_script(script).into_iter().map(|f| f.rule).collect()
+    }
+
+    #[test]
+    fn test_detects_rm_rf_root() {
+        assert_eq!(rules_for("rm -rf /"), vec!["rm-rf-root"]);
+        assert_eq!(rules_for("sudo rm -Rf /*"), vec!["rm-rf-root"]);
+        assert_eq!(rules_for("rm -r -f ~"), vec!["rm-rf-root"]);
+        assert_eq!(rules_for("rm --force --recursive $HOME/"), vec!["rm-rf-root"]);
+        // Quoted targets, e.g. a rendered parameter
+        assert_eq!(rules_for("rm -rf '/'"), vec!["rm-rf-root"]);
+        assert_eq!(rules_for("rm -rf \"$HOME\""), vec!["rm-rf-root"]);
+
+        // Deleting a sub directory is not flagged
+        assert!(rules_for("rm -rf /tmp/build").is_empty());
+        assert!(rules_for("rm -rf ./dist").is_empty());
+    }
+
+    #[test]
+    fn test_detects_other_rules() {
+        assert_eq!(rules_for("git push --force origin main"), vec!["git-push-force"]);
+        assert_eq!(rules_for("git push -f"), vec!["git-push-force"]);
+        assert_eq!(rules_for("cursor.execute('drop table users')"), vec!["sql-drop"]);
+        assert_eq!(rules_for("kubectl -n dev delete pod api-0"), vec!["kubectl-delete"]);
+
+        assert!(rules_for("git push origin main").is_empty());
+        assert!(rules_for("kubectl get pods").is_empty());
+    }
+
+    #[test]
+    fn test_reports_line_numbers() {
+        let findings = analyze_script("echo start\ngit fetch\ngit push --force\n");
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].line, 3);
+        assert_eq!(findings[0].snippet, "git push --force");
+    }
+}
@@ -1,10 +1,11 @@
-use std::process::{Command, Stdio};
+use std::process::Stdio;
 use std::io::{BufRead, BufReader};
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
-use tokio::sync::mpsc;
 use serde::{Deserialize, Serialize};
 
 use crate::database::models::LogEntry;
+use super::log_stream::LogSender;
+use super::plan::ExecutionPlan;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ExecutionResult {
@@ -18,22 +19,22 @@ pub struct ShellExecutor {
     pub execution_id: String,
     pub button_id: String,
     pub button_name: String,
-    pub script: String,
+    pub plan: ExecutionPlan,
 }
 
 impl ShellExecutor {
-    pub fn new(execution_id: String, button_id: String, button_name: String, script: String) -> Self {
+    pub fn new(execution_id: String, button_id: String, button_name: String, plan: ExecutionPlan) -> Self {
         Self {
             execution_id,
             button_id,
             button_name,
-            script,
+            plan,
         }
     }
 
     pub async fn execute(
         &self,
-        log_sender: mpsc::Sender<LogEntry>,
+        log_sender: LogSender,
     ) -> Result<ExecutionResult, String> {
         let start_time = SystemTime::now();
 
@@ -48,27 +49,15 @@ impl ShellExecutor {
                 .duration_since(UNIX_EPOCH)
                 .unwrap()
                 .as_secs() as i64,
+            execution_id: Some(self.execution_id.clone()),
         };
         let _ = log_sender.send(start_log).await;
 
-        // Detect platform and choose shell
-        let shell = if cfg!(target_os = "windows") {
-            "cmd"
-        } else {
-            "bash"
-        };
-
-        let shell_arg = if cfg!(target_os = "windows") {
-            "/C"
-        } else {
-            "-c"
-        };
-
-        // Execute the script directly using shell's -c parameter
+        // Execute the script directly using shell's -c parameter (resolved in the plan)
         // This avoids file I/O issues and quote escaping problems
-        let mut child = match Command::new(shell)
-            .arg(shell_arg)
-            .arg(&self.script)
+        let mut child = match self
+            .plan
+            .command()
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
             .spawn()
@@ -86,6 +75,7 @@ impl ShellExecutor {
                         .duration_since(UNIX_EPOCH)
                         .unwrap()
                         .as_secs() as i64,
+                    execution_id: Some(self.execution_id.clone()),
                 };
                 let _ = log_sender.send(error_log).await;
 
@@ -96,6 +86,7 @@ impl ShellExecutor {
         // Capture stdout
         if let Some(stdout) = child.stdout.take() {
             let button_id = self.button_id.clone();
+            let execution_id = self.execution_id.clone();
             let sender = log_sender.clone();
             tokio::spawn(async move {
                 let reader = BufReader::new(stdout);
@@ -111,8 +102,9 @@ impl ShellExecutor {
                                 .duration_since(UNIX_EPOCH)
                                 .unwrap()
                                 .as_secs() as i64,
+                            execution_id: Some(execution_id.clone()),
                         };
-                        let _ = sender.send(log).await;
+                        sender.send_line(log);
                     }
                 }
             });
@@ -121,6 +113,7 @@ impl ShellExecutor {
         // Capture stderr
         if let Some(stderr) = child.stderr.take() {
             let button_id = self.button_id.clone();
+            let execution_id = self.execution_id.clone();
             let sender = log_sender.clone();
             tokio::spawn(async move {
                 let reader = BufReader::new(stderr);
@@ -136,8 +129,9 @@ impl ShellExecutor {
                                 .duration_since(UNIX_EPOCH)
                                 .unwrap()
                                 .as_secs() as i64,
+                            execution_id: Some(execution_id.clone()),
                         };
-                        let _ = sender.send(log).await;
+                        sender.send_line(log);
                     }
                 }
             });
@@ -175,6 +169,7 @@ impl ShellExecutor {
                         .duration_since(UNIX_EPOCH)
                         .unwrap()
                         .as_secs() as i64,
+                    execution_id: Some(self.execution_id.clone()),
                 };
                 let _ = log_sender.send(result_log).await;
 
@@ -197,6 +192,7 @@ impl ShellExecutor {
                         .duration_since(UNIX_EPOCH)
                         .unwrap()
                         .as_secs() as i64,
+                    execution_id: Some(self.execution_id.clone()),
                 };
                 let _ = log_sender.send(error_log).await;
 
@@ -219,6 +215,7 @@ impl ShellExecutor {
                         .duration_since(UNIX_EPOCH)
                         .unwrap()
                         .as_secs() as i64,
+                    execution_id: Some(self.execution_id.clone()),
                 };
                 let _ = log_sender.send(error_log).await;
 
@@ -241,6 +238,7 @@ impl ShellExecutor {
                         .duration_since(UNIX_EPOCH)
                         .unwrap()
                         .as_secs() as i64,
+                    execution_id: Some(self.execution_id.clone()),
                 };
                 let _ = log_sender.send(error_log).await;
 
@@ -0,0 +1,4 @@
+mod path_filter;
+mod watch_manager;
+
+pub use watch_manager::{validate_trigger, FileWatchManager};
@@ -0,0 +1,104 @@
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use std::path::{Path, PathBuf};
+
+/// Decides which changed paths of a file trigger are relevant.
+/// Patterns are matched against the path relative to its watched root
+/// (or the file name when the root is the file itself) and the absolute path.
+#[derive(Debug)]
+pub struct PathFilter {
+    roots: Vec<PathBuf>,
+    include: Option<GlobSet>, // None = include everything
+    exclude: GlobSet,
+}
+
+impl PathFilter {
+    pub fn new(roots: &[String], include: &[String], exclude: &[String]) -> Result<Self, String> {
+        Ok(Self {
+            roots: roots.iter().map(PathBuf::from).collect(),
+            include: if include.is_empty() {
+                None
+            } else {
+                Some(build_glob_set(include)?)
+            },
+            exclude: build_glob_set(exclude)?,
+        })
+    }
+
+    /// Whether a change to this path should count towards a run
+    pub fn matches(&self, path: &Path) -> bool {
+        let candidates = self.candidates(path);
+
+        let included = match &self.include {
+            Some(include) => candidates.iter().any(|c| include.is_match(c)),
+            None => true,
+        };
+        included && !candidates.iter().any(|c| self.exclude.is_match(c))
+    }
+
+    fn candidates(&self, path: &Path) -> Vec<PathBuf> {
+        let mut candidates = vec![path.to_path_buf()];
+        for root in &self.roots {
+            match path.strip_prefix(root) {
+                Ok(relative) if relative.as_os_str().is_empty() => {
+                    if let Some(name) = path.file_name() {
+                        candidates.push(PathBuf::from(name));
+                    }
+                }
+                Ok(relative) => candidates.push(relative.to_path_buf()),
+                Err(_) => {}
+            }
+        }
+        candidates
+    }
+}
+
+fn build_glob_set(patterns: &[String]) -> Result<GlobSet, String> {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        let glob = Glob::new(pattern).map_err(|e| format!("Invalid glob pattern '{}': {}", pattern, e))?;
+        builder.add(glob);
+    }
+    builder
+        .build()
+        .map_err(|e| format!("Failed to build glob patterns: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(values: &[&str]) -> Vec<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn test_include_and_exclude_relative_to_root() {
+        let filter = PathFilter::new(
+            &strings(&["/work/app/src"]),
+            &strings(&["**/*.ts", "**/*.tsx"]),
+            &strings(&["**/generated/**", "**/*.test.ts"]),
+        )
+        .unwrap();
+
+        assert!(filter.matches(Path::new("/work/app/src/api/client.ts")));
+        assert!(filter.matches(Path::new("/work/app/src/App.tsx")));
+        assert!(!filter.matches(Path::new("/work/app/src/generated/types.ts")));
+        assert!(!filter.matches(Path::new("/work/app/src/api/client.test.ts")));
+        assert!(!filter.matches(Path::new("/work/app/src/styles.css")));
+    }
+
+    #[test]
+    fn test_single_file_root() {
+        let filter = PathFilter::new(&strings(&["/work/app/schema.graphql"]), &strings(&["schema.graphql"]), &[])
+            .unwrap();
+        assert!(filter.matches(Path::new("/work/app/schema.graphql")));
+
+        let everything = PathFilter::new(&strings(&["/work/app"]), &[], &[]).unwrap();
+        assert!(everything.matches(Path::new("/work/app/anything.txt")));
+    }
+
+    #[test]
+    fn test_invalid_pattern() {
+        assert!(PathFilter::new(&[], &strings(&["src/[a-"]), &[]).is_err());
+    }
+}
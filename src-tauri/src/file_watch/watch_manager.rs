@@ -0,0 +1,287 @@
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::collections::{BTreeSet, HashMap};
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::{Emitter, Manager};
+use tokio::sync::{mpsc, Mutex};
+use tokio::task::JoinHandle;
+
+use crate::commands::FileWatchManagerState;
+use crate::database::models::{Button, FileTrigger};
+use crate::database::repository;
+use crate::database::Database;
+use crate::executor::preflight;
+use crate::executor::runner::{self, Trigger};
+use crate::notifications;
+use super::path_filter::PathFilter;
+
+/// Upper bound for the debounce period of a trigger
+pub const MAX_DEBOUNCE_MS: u64 = 60_000;
+
+/// An OS watcher (inotify on Linux) and the task turning its events into runs
+struct ActiveWatch {
+    _watcher: RecommendedWatcher, // Dropping the watcher stops the OS watch
+    task: JoinHandle<()>,
+}
+
+/// Runs buttons when files matching their triggers change
+pub struct FileWatchManager {
+    active_watches: Arc<Mutex<HashMap<String, ActiveWatch>>>,
+}
+
+impl FileWatchManager {
+    /// Create a new FileWatchManager
+    pub fn new() -> Self {
+        Self {
+            active_watches: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Start every enabled file trigger stored in the database (called at app launch)
+    pub async fn start_all(&self, app_handle: tauri::AppHandle) {
+        let Some(db) = app_handle.try_state::<Database>() else { return };
+        let triggers = db
+            .read(|conn| repository::get_all_file_triggers(conn).map_err(|e| e.to_string()))
+            .await;
+
+        match triggers {
+            Ok(triggers) => {
+                for trigger in triggers.into_iter().filter(|t| t.enabled) {
+                    let id = trigger.id.clone();
+                    if let Err(e) = self.start_trigger(trigger, app_handle.clone()).await {
+                        eprintln!("Failed to start file trigger {}: {}", id, e);
+                    }
+                }
+            }
+            Err(e) => eprintln!("Failed to load file triggers: {}", e),
+        }
+    }
+
+    /// Start (or restart) watching the paths of a trigger
+    pub async fn start_trigger(&self, trigger: FileTrigger, app_handle: tauri::AppHandle) -> Result<(), String> {
+        validate_trigger(&trigger)?;
+        let filter = PathFilter::new(&trigger.paths, &trigger.include, &trigger.exclude)?;
+
+        let (tx, rx) = mpsc::unbounded_channel::<PathBuf>();
+        let mut watcher = notify::recommended_watcher(move |result: notify::Result<Event>| {
+            if let Ok(event) = result {
+                if is_change(&event.kind) {
+                    for path in event.paths {
+                        let _ = tx.send(path);
+                    }
+                }
+            }
+        })
+        .map_err(|e| format!("Failed to create file watcher: {}", e))?;
+
+        for path in &trigger.paths {
+            watcher
+                .watch(Path::new(path), RecursiveMode::Recursive)
+                .map_err(|e| format!("Failed to watch {}: {}", path, e))?;
+        }
+
+        let mut watches = self.active_watches.lock().await;
+        if let Some(previous) = watches.remove(&trigger.id) {
+            previous.task.abort();
+        }
+
+        let trigger_id = trigger.id.clone();
+        let debounce = Duration::from_millis(trigger.debounce_ms);
+        let task = tokio::spawn(async move {
+            let trigger = Arc::new(trigger);
+            debounce_loop(debounce, filter, rx, move |changed| {
+                let (trigger, app_handle) = (trigger.clone(), app_handle.clone());
+                async move { Self::run_button(&app_handle, &trigger, &changed).await }
+            })
+            .await;
+        });
+        watches.insert(
+            trigger_id,
+            ActiveWatch {
+                _watcher: watcher,
+                task,
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Stop watching for a trigger if it is active
+    pub async fn stop_trigger(&self, trigger_id: &str) {
+        let mut watches = self.active_watches.lock().await;
+        if let Some(watch) = watches.remove(trigger_id) {
+            watch.task.abort();
+        }
+    }
+
+    /// Start a run for the changed files; returns the run so the loop can wait for it
+    async fn run_button(app_handle: &tauri::AppHandle, trigger: &FileTrigger, changed: &[String]) -> Option<JoinHandle<()>> {
+        let db = app_handle.try_state::<Database>()?;
+        let button_id = trigger.button_id.clone();
+        let button = db
+            .read(move |conn| repository::get_button_by_id(conn, &button_id).map_err(|e| e.to_string()))
+            .await;
+        let button = match button {
+            Ok(button) => button,
+            Err(e) => {
+                eprintln!("File trigger {}: failed to load button: {}", trigger.id, e);
+                return None;
+            }
+        };
+
+        // The confirmation given when the trigger was saved only covers the script it was given for
+        if let Err(e) = preflight::check_unattended(&button, trigger.confirmed_script.as_deref()) {
+            Self::pause(app_handle, &db, trigger, &button, &e).await;
+            return None;
+        }
+
+        match runner::start_execution(app_handle, button, &HashMap::new(), Trigger::file_watch(&trigger.id, changed)) {
+            Ok(started) => Some(started.task),
+            Err(e) => {
+                eprintln!("File trigger {}: {}", trigger.id, e);
+                None
+            }
+        }
+    }
+
+    /// Disable and stop a trigger whose button can no longer run unattended, and tell the user
+    async fn pause(app_handle: &tauri::AppHandle, db: &Database, trigger: &FileTrigger, button: &Button, reason: &str) {
+        eprintln!("File trigger {}: paused, {}", trigger.id, reason);
+
+        let trigger_id = trigger.id.clone();
+        let now = chrono::Utc::now().timestamp();
+        if let Err(e) = db
+            .write(move |conn| {
+                repository::set_file_trigger_enabled(conn, &trigger_id, false, now).map_err(|e| e.to_string())
+            })
+            .await
+        {
+            eprintln!("File trigger {}: failed to disable: {}", trigger.id, e);
+        }
+
+        let _ = app_handle.emit("file-trigger-paused", &trigger.id);
+        notifications::notify(
+            app_handle,
+            &format!("⏸️ {} 的文件触发已暂停", button.name),
+            "脚本已修改或需要确认，请在文件触发器中重新确认后启用",
+        )
+        .await;
+
+        // Last, as this also ends the loop calling us
+        if let Some(manager) = app_handle.try_state::<FileWatchManagerState>() {
+            manager.0.stop_trigger(&trigger.id).await;
+        }
+    }
+}
+
+impl Default for FileWatchManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Collect changes until the paths have been quiet for the debounce period, then run once.
+/// Changes made while the run is going and during one debounce period after it are dropped,
+/// since they are mostly the run writing to the watched paths itself.
+async fn debounce_loop<F, Fut>(
+    debounce: Duration,
+    filter: PathFilter,
+    mut rx: mpsc::UnboundedReceiver<PathBuf>,
+    mut run: F,
+) where
+    F: FnMut(Vec<String>) -> Fut,
+    Fut: Future<Output = Option<JoinHandle<()>>>,
+{
+    while let Some(first) = rx.recv().await {
+        let mut changed = BTreeSet::new();
+        if filter.matches(&first) {
+            changed.insert(first);
+        }
+
+        loop {
+            match tokio::time::timeout(debounce, rx.recv()).await {
+                Ok(Some(path)) => {
+                    if filter.matches(&path) {
+                        changed.insert(path);
+                    }
+                }
+                Ok(None) => return,
+                Err(_) => break,
+            }
+        }
+
+        if changed.is_empty() {
+            continue;
+        }
+        let changed = changed.iter().map(|p| p.to_string_lossy().to_string()).collect();
+        if let Some(running) = run(changed).await {
+            let _ = running.await;
+            tokio::time::sleep(debounce).await;
+            while rx.try_recv().is_ok() {}
+        }
+    }
+}
+
+/// Check the settings of a trigger before it is saved or started
+pub fn validate_trigger(trigger: &FileTrigger) -> Result<(), String> {
+    if trigger.paths.is_empty() {
+        return Err("At least one path to watch is required".to_string());
+    }
+    if let Some(missing) = trigger.paths.iter().find(|p| !Path::new(p).exists()) {
+        return Err(format!("Path does not exist: {}", missing));
+    }
+    if trigger.debounce_ms > MAX_DEBOUNCE_MS {
+        return Err(format!("debounce_ms must not exceed {}", MAX_DEBOUNCE_MS));
+    }
+    PathFilter::new(&trigger.paths, &trigger.include, &trigger.exclude).map(|_| ())
+}
+
+/// Content changes only; access events would fire on every read
+fn is_change(kind: &EventKind) -> bool {
+    matches!(kind, EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_changes_during_and_right_after_a_run_are_ignored() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        runtime.block_on(async {
+            let filter = PathFilter::new(&["/work".to_string()], &["**/*.rs".to_string()], &[]).unwrap();
+            let (tx, rx) = mpsc::unbounded_channel();
+            let (runs_tx, mut runs) = mpsc::unbounded_channel();
+
+            let events = tx.clone();
+            let task = tokio::spawn(debounce_loop(Duration::from_millis(50), filter, rx, move |changed| {
+                let _ = runs_tx.send(changed);
+                let events = events.clone();
+                async move {
+                    // The run rewrites a watched file while it is going
+                    Some(tokio::spawn(async move {
+                        let _ = events.send(PathBuf::from("/work/src/generated.rs"));
+                        tokio::time::sleep(Duration::from_millis(100)).await;
+                    }))
+                }
+            }));
+
+            tx.send(PathBuf::from("/work/src/main.rs")).unwrap();
+            tx.send(PathBuf::from("/work/README.md")).unwrap();
+            tx.send(PathBuf::from("/work/src/lib.rs")).unwrap();
+            tokio::time::sleep(Duration::from_millis(120)).await;
+            // Saved while the run is going
+            tx.send(PathBuf::from("/work/src/lib.rs")).unwrap();
+            tokio::time::sleep(Duration::from_millis(300)).await;
+            assert_eq!(runs.try_recv().unwrap(), ["/work/src/lib.rs", "/work/src/main.rs"]);
+            assert!(runs.try_recv().is_err());
+
+            tx.send(PathBuf::from("/work/src/main.rs")).unwrap();
+            tokio::time::sleep(Duration::from_millis(150)).await;
+            task.abort();
+            assert_eq!(runs.try_recv().unwrap(), ["/work/src/main.rs"]);
+        });
+    }
+}
@@ -1,25 +1,69 @@
 mod commands;
 mod database;
 mod executor;
+mod file_watch;
+mod log_export;
 mod monitor;
+mod notifications;
+mod redaction;
+mod retention;
+mod scheduler;
+mod secrets;
+#[cfg(test)]
+mod test_http;
 
-use std::sync::{Arc, Mutex};
+use std::sync::Arc;
 use tauri::Manager;
+use file_watch::FileWatchManager;
 use monitor::MonitorManager;
+use redaction::Redactor;
+use scheduler::ScheduleManager;
+use secrets::SecretStore;
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
+        .plugin(tauri_plugin_notification::init())
         .setup(|app| {
-            let conn = database::init_database(&app.handle())
-                .expect("Failed to initialize database");
+            let db = database::init_database(app.handle())?;
+            let conn = db.conn()?;
+
+            // Load the secret store key; a password protected store stays locked until unlocked
+            let secret_store = Arc::new(SecretStore::new());
+            let app_dir = app.path().app_data_dir()?;
+            if let Err(e) = secret_store.initialize(&conn, &app_dir) {
+                eprintln!("Failed to initialize secret store: {}", e);
+            }
+
+            // Log redaction rules; invalid stored patterns fall back to the built-in rules
+            let redactor = Redactor::new(&redaction::load_config(&conn)).unwrap_or_else(|e| {
+                eprintln!("Failed to load redaction rules: {}", e);
+                Redactor::default()
+            });
 
             // Initialize monitor manager with Arc for async access
-            let monitor_manager = Arc::new(MonitorManager::new());
+            let monitor_manager = Arc::new(MonitorManager::new(db.clone()));
+
+            app.manage(db);
+            app.manage(commands::MonitorManagerState(monitor_manager.clone()));
+            app.manage(commands::SecretStoreState(secret_store));
+            app.manage(commands::RedactorState(Arc::new(redactor)));
+
+            // Restore monitors, cron schedules and file watches in the background once all state is managed
+            let schedule_manager = Arc::new(ScheduleManager::new());
+            let file_watch_manager = Arc::new(FileWatchManager::new());
+            app.manage(commands::ScheduleManagerState(schedule_manager.clone()));
+            app.manage(commands::FileWatchManagerState(file_watch_manager.clone()));
+            let app_handle = app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                monitor_manager.restore_active_monitors(app_handle.clone()).await;
+                schedule_manager.start_all(app_handle.clone()).await;
+                file_watch_manager.start_all(app_handle).await;
+            });
 
-            app.manage(commands::DbConnection(Mutex::new(conn)));
-            app.manage(commands::MonitorManagerState(monitor_manager));
+            // Enforce log retention at launch and then every hour
+            retention::spawn_pruning_task(app.handle().clone());
 
             Ok(())
         })
@@ -44,8 +88,19 @@ pub fn run() {
             commands::get_logs,
             commands::get_logs_by_button,
             commands::clear_logs,
+            commands::search_logs,
+            commands::export_logs,
+            commands::get_log_retention_config,
+            commands::update_log_retention_config,
+            commands::prune_logs,
+            commands::get_last_prune_report,
+            commands::enable_incremental_vacuum,
             // Execution commands
             commands::execute_script,
+            commands::preflight_script,
+            commands::preview_execution,
+            commands::get_button_executions,
+            commands::get_execution_logs,
             // Monitor commands
             commands::create_monitor,
             commands::get_all_monitors,
@@ -55,7 +110,42 @@ pub fn run() {
             commands::start_monitor,
             commands::stop_monitor,
             commands::get_monitor_logs,
+            commands::get_monitor_stats,
+            commands::get_monitor_checks,
+            commands::get_monitor_check_history,
             commands::update_monitor_positions,
+            // Notification channel commands
+            commands::list_notification_channels,
+            commands::create_notification_channel,
+            commands::update_notification_channel,
+            commands::delete_notification_channel,
+            commands::test_notification_channel,
+            // Schedule commands
+            commands::list_schedules,
+            commands::create_schedule,
+            commands::update_schedule,
+            commands::delete_schedule,
+            commands::preview_cron_expression,
+            // File trigger commands
+            commands::list_file_triggers,
+            commands::create_file_trigger,
+            commands::update_file_trigger,
+            commands::delete_file_trigger,
+            // Secret commands
+            commands::list_secrets,
+            commands::set_secret,
+            commands::delete_secret,
+            commands::get_secret_status,
+            commands::unlock_secrets,
+            commands::set_master_password,
+            // Settings commands
+            commands::get_redaction_config,
+            commands::update_redaction_config,
+            commands::preview_redaction,
+            commands::get_auto_start_monitors,
+            commands::set_auto_start_monitors,
+            commands::get_notifications_enabled,
+            commands::set_notifications_enabled,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -0,0 +1,301 @@
+use chrono::TimeZone;
+use rusqlite::{params_from_iter, Connection, Row};
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+use crate::database::models::LogSearchQuery;
+use crate::database::repository;
+
+/// File format of a log export
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    Text,  // One readable line per log
+    Jsonl, // One JSON object per line
+    Csv,
+}
+
+/// A log row as written to the export, with its source resolved to a name
+#[derive(Debug, Serialize)]
+struct ExportedLog {
+    time: String, // Local time, human readable
+    timestamp: i64,
+    level: String,
+    source_type: Option<&'static str>, // "button" or "monitor"
+    source_name: Option<String>,
+    button_id: Option<String>,
+    monitor_id: Option<String>,
+    execution_id: Option<String>,
+    message: String,
+}
+
+/// CSV header, in the field order of `ExportedLog`
+const CSV_HEADER: [&str; 9] = [
+    "time",
+    "timestamp",
+    "level",
+    "source_type",
+    "source_name",
+    "button_id",
+    "monitor_id",
+    "execution_id",
+    "message",
+];
+
+impl ExportedLog {
+    fn from_row(row: &Row) -> rusqlite::Result<Self> {
+        let timestamp: i64 = row.get(0)?;
+        let button_id: Option<String> = row.get(3)?;
+        let monitor_id: Option<String> = row.get(4)?;
+        let (source_type, source_name) = if button_id.is_some() {
+            (Some("button"), row.get(6)?)
+        } else if monitor_id.is_some() {
+            (Some("monitor"), row.get(7)?)
+        } else {
+            (None, None)
+        };
+
+        Ok(Self {
+            time: format_time(timestamp),
+            timestamp,
+            level: row.get(1)?,
+            source_type,
+            source_name,
+            button_id,
+            monitor_id,
+            execution_id: row.get(5)?,
+            message: row.get(2)?,
+        })
+    }
+
+    /// Line used by the plain text format
+    fn to_text(&self) -> String {
+        let source = match (self.source_type, &self.source_name) {
+            (Some(kind), Some(name)) => format!(" [{} {}]", kind, name),
+            _ => String::new(),
+        };
+        format!("[{}] [{}]{} {}", self.time, self.level.to_uppercase(), source, self.message)
+    }
+
+    /// Fields in `CSV_HEADER` order; text cannot be taken for a formula by spreadsheets
+    fn to_csv_record(&self) -> [Cow<'_, str>; 9] {
+        [
+            csv_text(&self.time),
+            Cow::Owned(self.timestamp.to_string()),
+            csv_text(&self.level),
+            Cow::Borrowed(self.source_type.unwrap_or_default()),
+            csv_text(self.source_name.as_deref().unwrap_or_default()),
+            csv_text(self.button_id.as_deref().unwrap_or_default()),
+            csv_text(self.monitor_id.as_deref().unwrap_or_default()),
+            csv_text(self.execution_id.as_deref().unwrap_or_default()),
+            csv_text(&self.message),
+        ]
+    }
+}
+
+/// Prefix cells starting like a formula with a quote, which spreadsheets show but do not evaluate
+fn csv_text(value: &str) -> Cow<'_, str> {
+    if value.starts_with(['=', '+', '-', '@', '\t', '\r']) {
+        Cow::Owned(format!("'{}", value))
+    } else {
+        Cow::Borrowed(value)
+    }
+}
+
+fn format_time(timestamp: i64) -> String {
+    chrono::Local
+        .timestamp_opt(timestamp, 0)
+        .single()
+        .map(|time| time.format("%Y-%m-%d %H:%M:%S").to_string())
+        .unwrap_or_else(|| timestamp.to_string())
+}
+
+/// Writes exported rows in the chosen format
+enum RowWriter<W: Write> {
+    Text(W),
+    Jsonl(W),
+    Csv(Box<csv::Writer<W>>),
+}
+
+impl<W: Write> RowWriter<W> {
+    fn new(format: ExportFormat, out: W) -> Result<Self, String> {
+        Ok(match format {
+            ExportFormat::Text => Self::Text(out),
+            ExportFormat::Jsonl => Self::Jsonl(out),
+            ExportFormat::Csv => {
+                // The header is written up front so an empty export is still a valid CSV file
+                let mut writer = csv::WriterBuilder::new().has_headers(false).from_writer(out);
+                writer
+                    .write_record(CSV_HEADER)
+                    .map_err(|e| format!("Failed to write CSV header: {}", e))?;
+                Self::Csv(Box::new(writer))
+            }
+        })
+    }
+
+    fn write(&mut self, log: &ExportedLog) -> Result<(), String> {
+        let result = match self {
+            Self::Text(out) => writeln!(out, "{}", log.to_text()).map_err(|e| e.to_string()),
+            Self::Jsonl(out) => serde_json::to_writer(&mut *out, log)
+                .map_err(|e| e.to_string())
+                .and_then(|_| writeln!(out).map_err(|e| e.to_string())),
+            Self::Csv(writer) => writer
+                .write_record(log.to_csv_record().iter().map(|field| field.as_bytes()))
+                .map_err(|e| e.to_string()),
+        };
+        result.map_err(|e| format!("Failed to write log: {}", e))
+    }
+
+    fn finish(self) -> Result<(), String> {
+        let result = match self {
+            Self::Text(mut out) | Self::Jsonl(mut out) => out.flush(),
+            Self::Csv(mut writer) => writer.flush(),
+        };
+        result.map_err(|e| format!("Failed to write export file: {}", e))
+    }
+}
+
+/// Write the logs matching `query` (cursor and limit are ignored) to `path`, oldest first.
+/// Rows are streamed from SQLite, so the export is never held in memory.
+/// An existing file is only replaced with `overwrite`.
+/// Returns the number of exported logs; a partially written file is removed on failure.
+pub fn export_logs(
+    conn: &Connection,
+    query: &LogSearchQuery,
+    format: ExportFormat,
+    path: &Path,
+    overwrite: bool,
+) -> Result<u64, String> {
+    let file = if overwrite {
+        File::create(path)
+    } else {
+        File::options().write(true).create_new(true).open(path)
+    };
+    let file = file.map_err(|e| match e.kind() {
+        std::io::ErrorKind::AlreadyExists => format!("{} already exists", path.display()),
+        _ => format!("Failed to create {}: {}", path.display(), e),
+    })?;
+
+    let result = write_logs(conn, query, format, BufWriter::new(file));
+    if result.is_err() {
+        let _ = std::fs::remove_file(path);
+    }
+    result
+}
+
+fn write_logs<W: Write>(
+    conn: &Connection,
+    query: &LogSearchQuery,
+    format: ExportFormat,
+    out: W,
+) -> Result<u64, String> {
+    let (where_clause, values) = repository::log_search_filter(query);
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT logs.timestamp, logs.level, logs.message, logs.button_id, logs.monitor_id,
+                    logs.execution_id, buttons.name, monitors.name
+             FROM logs
+             LEFT JOIN buttons ON buttons.id = logs.button_id
+             LEFT JOIN monitors ON monitors.id = logs.monitor_id
+             {} ORDER BY logs.timestamp, logs.rowid",
+            where_clause
+        ))
+        .map_err(|e| format!("Failed to query logs: {}", e))?;
+
+    let mut writer = RowWriter::new(format, out)?;
+    let mut rows = stmt
+        .query(params_from_iter(values))
+        .map_err(|e| format!("Failed to query logs: {}", e))?;
+    let mut count = 0;
+    while let Some(row) = rows.next().map_err(|e| format!("Failed to read logs: {}", e))? {
+        let log = ExportedLog::from_row(row).map_err(|e| format!("Failed to read logs: {}", e))?;
+        writer.write(&log)?;
+        count += 1;
+    }
+    writer.finish()?;
+
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::{insert_button, insert_log, TestDb};
+
+    #[test]
+    fn test_export_formats() {
+        let db = TestDb::open();
+        let conn = db.conn().unwrap();
+        insert_button(&conn, "b1", "Build");
+        insert_log(&conn, Some("b1"), "info", "compiling", 200);
+        insert_log(&conn, Some("b1"), "error", "failed, \"exit\" 2", 300);
+        insert_log(&conn, None, "info", "started", 100);
+
+        let text_path = db.dir().join("logs.txt");
+        let all = LogSearchQuery::default();
+        assert_eq!(export_logs(&conn, &all, ExportFormat::Text, &text_path, false).unwrap(), 3);
+        let text = std::fs::read_to_string(&text_path).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), 3);
+        // Oldest first, with readable time and source name
+        assert!(lines[0].ends_with("[INFO] started"));
+        assert!(lines[2].starts_with(&format!("[{}]", format_time(300))));
+        assert!(lines[2].ends_with("[ERROR] [button Build] failed, \"exit\" 2"));
+
+        let errors = LogSearchQuery {
+            levels: vec!["error".to_string()],
+            ..Default::default()
+        };
+        let jsonl_path = db.dir().join("logs.jsonl");
+        assert_eq!(export_logs(&conn, &errors, ExportFormat::Jsonl, &jsonl_path, false).unwrap(), 1);
+        let json: serde_json::Value =
+            serde_json::from_str(std::fs::read_to_string(&jsonl_path).unwrap().trim()).unwrap();
+        assert_eq!(json["source_name"], "Build");
+        assert_eq!(json["timestamp"], 300);
+
+        let csv_path = db.dir().join("logs.csv");
+        assert_eq!(export_logs(&conn, &all, ExportFormat::Csv, &csv_path, false).unwrap(), 3);
+        let mut reader = csv::Reader::from_path(&csv_path).unwrap();
+        assert_eq!(reader.headers().unwrap().iter().collect::<Vec<_>>(), CSV_HEADER.to_vec());
+        let records: Vec<csv::StringRecord> = reader.records().map(|r| r.unwrap()).collect();
+        assert_eq!(records.len(), 3);
+        assert_eq!(&records[2][4], "Build");
+        assert_eq!(&records[2][8], "failed, \"exit\" 2");
+
+        // Existing files are only replaced when asked to
+        let none = LogSearchQuery {
+            text: Some("nothing matches this".to_string()),
+            ..Default::default()
+        };
+        let error = export_logs(&conn, &none, ExportFormat::Csv, &csv_path, false).unwrap_err();
+        assert!(error.ends_with("already exists"), "{}", error);
+        assert_eq!(std::fs::read_to_string(&csv_path).unwrap().lines().count(), 4);
+
+        // An empty export still has the header
+        assert_eq!(export_logs(&conn, &none, ExportFormat::Csv, &csv_path, true).unwrap(), 0);
+        assert_eq!(std::fs::read_to_string(&csv_path).unwrap().lines().count(), 1);
+    }
+
+    #[test]
+    fn test_csv_cells_are_not_formulas() {
+        let db = TestDb::open();
+        let conn = db.conn().unwrap();
+        insert_log(&conn, None, "error", "=HYPERLINK(\"http://evil\")", 100);
+        insert_log(&conn, None, "info", "-rf done", 200);
+        insert_log(&conn, None, "info", "a = b", 300);
+
+        let path = db.dir().join("logs.csv");
+        export_logs(&conn, &LogSearchQuery::default(), ExportFormat::Csv, &path, false).unwrap();
+        let mut reader = csv::Reader::from_path(&path).unwrap();
+        let messages: Vec<String> = reader.records().map(|r| r.unwrap()[8].to_string()).collect();
+        assert_eq!(messages, vec!["'=HYPERLINK(\"http://evil\")", "'-rf done", "a = b"]);
+
+        // Other formats keep the message as it is
+        let path = db.dir().join("logs.jsonl");
+        export_logs(&conn, &LogSearchQuery::default(), ExportFormat::Jsonl, &path, false).unwrap();
+        assert!(std::fs::read_to_string(&path).unwrap().contains("\"-rf done\""));
+    }
+}
@@ -0,0 +1,175 @@
+use serde::{Deserialize, Serialize};
+
+/// Health changes within this window are counted for flap detection (seconds)
+pub const FLAP_WINDOW_SECS: i64 = 600;
+/// A monitor is flapping once its health changed this many times within the window
+pub const FLAP_THRESHOLD: usize = 4;
+
+/// Notification the monitor loop should send after a check
+#[derive(Debug, PartialEq)]
+pub enum AlertEvent {
+    None,
+    Alert,         // The target just reached the consecutive failure threshold
+    Renotify,      // Still down after the re-notification interval
+    Recovered,     // First healthy check after an alert
+    Flapping,      // Health keeps changing; alerts are held until it settles
+    FlappingEnded, // Health settled while the target is up and no alert is pending
+}
+
+/// When a monitor alerts, taken from its settings
+#[derive(Debug, Clone, Copy)]
+pub struct AlertPolicy {
+    pub threshold: u32,            // Consecutive failed checks before alerting
+    pub renotify_secs: Option<u64>, // Repeat the alert while down (None = once)
+}
+
+/// Alert state of a monitor, persisted between checks and across restarts
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AlertState {
+    pub alerting: bool, // An alert was sent and the target has not recovered yet
+    pub consecutive_failures: u32,
+    pub last_notified_at: Option<i64>,
+    pub last_healthy: Option<bool>,
+    pub flapping: bool,
+    pub transitions: Vec<i64>, // Times the health changed within the flap window
+}
+
+impl AlertState {
+    /// Record a check result at `now` (unix seconds) and decide what to notify
+    pub fn on_check(&mut self, policy: AlertPolicy, healthy: bool, now: i64) -> AlertEvent {
+        if self.last_healthy.is_some_and(|previous| previous != healthy) {
+            self.transitions.push(now);
+        }
+        self.last_healthy = Some(healthy);
+        self.transitions.retain(|&at| now - at < FLAP_WINDOW_SECS);
+
+        if healthy {
+            self.consecutive_failures = 0;
+        } else {
+            self.consecutive_failures = self.consecutive_failures.saturating_add(1);
+        }
+
+        if self.flapping {
+            if !self.transitions.is_empty() {
+                return AlertEvent::None;
+            }
+            // Stable for a whole window: report the state it settled in
+            self.flapping = false;
+            let event = self.evaluate(policy, healthy, now);
+            return if event == AlertEvent::None { AlertEvent::FlappingEnded } else { event };
+        }
+        if self.transitions.len() >= FLAP_THRESHOLD {
+            self.flapping = true;
+            return AlertEvent::Flapping;
+        }
+
+        self.evaluate(policy, healthy, now)
+    }
+
+    fn evaluate(&mut self, policy: AlertPolicy, healthy: bool, now: i64) -> AlertEvent {
+        if healthy {
+            if self.alerting {
+                self.alerting = false;
+                self.last_notified_at = None;
+                return AlertEvent::Recovered;
+            }
+            return AlertEvent::None;
+        }
+
+        if !self.alerting {
+            if self.consecutive_failures >= policy.threshold.max(1) {
+                self.alerting = true;
+                self.last_notified_at = Some(now);
+                return AlertEvent::Alert;
+            }
+            return AlertEvent::None;
+        }
+
+        let due = match (policy.renotify_secs, self.last_notified_at) {
+            (Some(interval), Some(last)) => now - last >= interval as i64,
+            _ => false,
+        };
+        if due {
+            self.last_notified_at = Some(now);
+            return AlertEvent::Renotify;
+        }
+        AlertEvent::None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const POLICY: AlertPolicy = AlertPolicy {
+        threshold: 3,
+        renotify_secs: Some(300),
+    };
+
+    #[test]
+    fn test_alerts_once_then_recovers() {
+        let mut state = AlertState::default();
+
+        assert_eq!(state.on_check(POLICY, false, 0), AlertEvent::None);
+        assert_eq!(state.on_check(POLICY, false, 60), AlertEvent::None);
+        assert_eq!(state.on_check(POLICY, false, 120), AlertEvent::Alert);
+        // Still down: no repeated alert every tick
+        assert_eq!(state.on_check(POLICY, false, 180), AlertEvent::None);
+        assert_eq!(state.on_check(POLICY, false, 400), AlertEvent::None);
+        assert_eq!(state.on_check(POLICY, false, 420), AlertEvent::Renotify);
+        assert_eq!(state.on_check(POLICY, false, 480), AlertEvent::None);
+
+        assert_eq!(state.on_check(POLICY, true, 540), AlertEvent::Recovered);
+        assert_eq!(state.on_check(POLICY, true, 600), AlertEvent::None);
+        assert!(!state.alerting);
+    }
+
+    #[test]
+    fn test_single_failures_below_threshold_are_quiet() {
+        let mut state = AlertState::default();
+        let no_renotify = AlertPolicy {
+            threshold: 2,
+            renotify_secs: None,
+        };
+
+        assert_eq!(state.on_check(no_renotify, false, 0), AlertEvent::None);
+        assert_eq!(state.on_check(no_renotify, true, 1000), AlertEvent::None);
+        assert_eq!(state.on_check(no_renotify, false, 2000), AlertEvent::None);
+        assert_eq!(state.on_check(no_renotify, false, 3000), AlertEvent::Alert);
+        assert_eq!(state.on_check(no_renotify, false, 90_000), AlertEvent::None);
+    }
+
+    #[test]
+    fn test_flapping_holds_alerts_until_stable() {
+        let mut state = AlertState::default();
+        let policy = AlertPolicy {
+            threshold: 1,
+            renotify_secs: None,
+        };
+
+        assert_eq!(state.on_check(policy, false, 0), AlertEvent::Alert);
+        assert_eq!(state.on_check(policy, true, 60), AlertEvent::Recovered);
+        assert_eq!(state.on_check(policy, false, 120), AlertEvent::Alert);
+        assert_eq!(state.on_check(policy, true, 180), AlertEvent::Recovered);
+        // Fourth change within the window
+        assert_eq!(state.on_check(policy, false, 240), AlertEvent::Flapping);
+        assert_eq!(state.on_check(policy, true, 300), AlertEvent::None);
+        assert_eq!(state.on_check(policy, false, 360), AlertEvent::None);
+
+        // Down for a whole window: flapping ends with a regular alert
+        assert_eq!(state.on_check(policy, false, 900), AlertEvent::None);
+        assert_eq!(state.on_check(policy, false, 960), AlertEvent::Alert);
+        assert!(!state.flapping);
+    }
+
+    #[test]
+    fn test_state_survives_serialization() {
+        let mut state = AlertState::default();
+        state.on_check(POLICY, false, 0);
+        state.on_check(POLICY, false, 60);
+
+        let mut restored: AlertState = serde_json::from_str(&serde_json::to_string(&state).unwrap()).unwrap();
+        assert_eq!(restored, state);
+        assert_eq!(restored.on_check(POLICY, false, 120), AlertEvent::Alert);
+    }
+}
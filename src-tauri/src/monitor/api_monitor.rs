@@ -1,6 +1,19 @@
-use reqwest::{Client, Method};
+use hyper::client::connect::dns::Name;
+use reqwest::dns::{Addrs, Resolve, Resolving};
+use reqwest::redirect::Policy;
+use reqwest::{Certificate, Client, Identity, Method, Proxy};
 use serde_json::Value;
-use std::time::Duration;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime};
+use tokio::sync::Mutex;
+
+use super::assertions::CompiledAssertions;
+use super::auth::RequestAuth;
+use super::config::{HttpOptions, HttpVersion, TlsOptions};
+use super::outcome::{tls_failure_kind, CheckErrorKind, CheckOutcome};
 
 /// Configuration for API health check
 #[derive(Debug, Clone)]
@@ -10,7 +23,11 @@ pub struct ApiCheckConfig {
     pub headers: Option<Vec<(String, String)>>, // Optional HTTP headers
     pub body: Option<String>,                    // Optional request body
     pub expected_content: Option<String>,        // Optional expected response content
+    pub assertions: Arc<CompiledAssertions>,     // Further conditions on the response
     pub timeout_secs: u64,                       // Request timeout in seconds
+    pub auth: Option<RequestAuth>,               // Credentials, with OAuth2 tokens already fetched
+    pub tls: TlsOptions,                         // Certificate checks, extra CAs and client certificate
+    pub http: HttpOptions,                       // Proxy, redirects and HTTP version
 }
 
 impl ApiCheckConfig {
@@ -22,7 +39,11 @@ impl ApiCheckConfig {
             headers: None,
             body: None,
             expected_content: None,
+            assertions: Arc::default(),
             timeout_secs: 10,
+            auth: None,
+            tls: TlsOptions::default(),
+            http: HttpOptions::default(),
         }
     }
 
@@ -34,30 +55,96 @@ impl ApiCheckConfig {
             headers: None,
             body: Some(body),
             expected_content: None,
+            assertions: Arc::default(),
             timeout_secs: 10,
+            auth: None,
+            tls: TlsOptions::default(),
+            http: HttpOptions::default(),
         }
     }
 }
 
+/// Settings a client is built with
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct ClientSettings {
+    timeout_secs: u64,
+    tls: TlsOptions,
+    http: HttpOptions,
+}
+
+/// Long-lived HTTP clients, one per combination of timeout, TLS and connection settings,
+/// so checks with the same settings share pooled keep-alive connections.
+/// A client is built again once one of its CA or client certificate files changed.
+#[derive(Default)]
+pub struct ClientPool {
+    clients: Mutex<HashMap<ClientSettings, PooledClient>>,
+}
+
+struct PooledClient {
+    files_modified: Vec<Option<SystemTime>>, // Of the certificate files when the client was built
+    client: Client,
+}
+
+impl ClientPool {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Client for the settings of a check, built on first use
+    pub async fn client(&self, config: &ApiCheckConfig) -> Result<Client, String> {
+        let settings = ClientSettings {
+            timeout_secs: config.timeout_secs,
+            tls: config.tls.clone(),
+            http: config.http.clone(),
+        };
+        let files_modified = certificate_files_modified(&settings.tls);
+        let mut clients = self.clients.lock().await;
+        if let Some(pooled) = clients.get(&settings) {
+            if pooled.files_modified == files_modified {
+                return Ok(pooled.client.clone());
+            }
+        }
+        let client = build_client(&settings)?;
+        clients.insert(settings, PooledClient { files_modified, client: client.clone() });
+        Ok(client)
+    }
+}
+
+/// Modification times of the certificate files a client reads (None for unset or unreadable files)
+fn certificate_files_modified(tls: &TlsOptions) -> Vec<Option<SystemTime>> {
+    [&tls.ca_bundle, &tls.client_cert, &tls.client_key]
+        .into_iter()
+        .map(|path| {
+            let path = configured_path(path)?;
+            std::fs::metadata(path).and_then(|metadata| metadata.modified()).ok()
+        })
+        .collect()
+}
+
+/// Path of a TLS file, None when unset or blank (as `validate_tls` treats it)
+fn configured_path(path: &Option<String>) -> Option<&str> {
+    path.as_deref().filter(|path| !path.trim().is_empty())
+}
+
 /// API monitor for checking API health
 pub struct ApiMonitor {
-    client: Client,
+    clients: Arc<ClientPool>,
 }
 
 impl ApiMonitor {
-    /// Create a new ApiMonitor with default timeout
+    /// Create a new ApiMonitor with a client pool of its own
     pub fn new() -> Self {
-        Self {
-            client: Client::builder()
-                .timeout(Duration::from_secs(10))
-                .build()
-                .unwrap_or_else(|_| Client::new()),
-        }
+        Self::with_clients(Arc::new(ClientPool::new()))
+    }
+
+    /// Create an ApiMonitor sharing a client pool
+    pub fn with_clients(clients: Arc<ClientPool>) -> Self {
+        Self { clients }
     }
 
-    /// Check API health based on configuration
-    /// Returns Ok(true) if API is healthy, Ok(false) if unhealthy, Err on request failure
-    pub async fn check_api(&self, config: &ApiCheckConfig) -> Result<bool, String> {
+    /// Check API health based on configuration.
+    /// The latency covers sending the request and reading the whole response.
+    pub async fn check_api(&self, config: &ApiCheckConfig) -> CheckOutcome {
         // Parse HTTP method
         let method = match config.method.to_uppercase().as_str() {
             "GET" => Method::GET,
@@ -68,11 +155,11 @@ impl ApiMonitor {
             _ => Method::GET, // Default to GET for unknown methods
         };
 
-        // Build request with custom timeout
-        let client = Client::builder()
-            .timeout(Duration::from_secs(config.timeout_secs))
-            .build()
-            .map_err(|e| format!("Failed to build HTTP client: {}", e))?;
+        // Client with the timeout, TLS and connection settings of the check
+        let client = match self.clients.client(config).await {
+            Ok(client) => client,
+            Err(e) => return CheckOutcome::failed(CheckErrorKind::Config, e),
+        };
 
         let mut request = client.request(method, &config.url);
 
@@ -83,35 +170,66 @@ impl ApiMonitor {
             }
         }
 
+        if let Some(auth) = &config.auth {
+            request = auth.apply(request);
+        }
+
         // Add request body if provided (for POST/PUT/PATCH)
         if let Some(body) = &config.body {
             request = request.body(body.clone());
         }
 
         // Execute request
-        let response = request
-            .send()
-            .await
-            .map_err(|e| format!("Request failed: {}", e))?;
+        let started = Instant::now();
+        let response = match request.send().await {
+            Ok(response) => response,
+            Err(e) => {
+                return CheckOutcome::failed(request_error_kind(&e), format!("Request failed: {}", e))
+                    .with_latency(started.elapsed())
+            }
+        };
+        let status = response.status();
+        let headers = response.headers().clone();
 
-        // Check status code (2xx = success)
-        if !response.status().is_success() {
-            return Ok(false);
+        // Bodies over the max_size limit are not read to the end
+        let (body, truncated) = match read_body(response, config.assertions.max_body_size()).await {
+            Ok(body) => body,
+            Err(e) => {
+                let kind = if e.is_timeout() { CheckErrorKind::Timeout } else { CheckErrorKind::Body };
+                return CheckOutcome::failed(kind, format!("Failed to read response body: {}", e))
+                    .with_latency(started.elapsed())
+                    .with_response(status.as_u16(), None);
+            }
+        };
+        let latency = started.elapsed();
+        let size = body.len() as u64;
+        let bytes = Some(size);
+        let body = String::from_utf8_lossy(&body);
+
+        // Check status code (2xx = success), unless the assertions list the accepted codes
+        if !config.assertions.checks_status() && !status.is_success() {
+            return CheckOutcome::failed(CheckErrorKind::Status, format!("Unexpected status {}", status))
+                .with_latency(latency)
+                .with_response(status.as_u16(), bytes);
         }
 
+        let failed_assertions = config.assertions.evaluate(status.as_u16(), &headers, &body, size, truncated);
         // Optionally verify response content
-        if let Some(expected) = &config.expected_content {
-            let body = response
-                .text()
-                .await
-                .map_err(|e| format!("Failed to read response body: {}", e))?;
-
-            // Check if response contains expected content
-            return Ok(body.contains(expected));
-        }
+        let content_missing = !truncated
+            && config.expected_content.as_ref().is_some_and(|expected| !body.contains(expected.as_str()));
 
-        // If no content check required, just return success based on status code
-        Ok(true)
+        let outcome = if !failed_assertions.is_empty() {
+            let mut failures = failed_assertions.clone();
+            if content_missing {
+                failures.insert(0, "expected content not found in response".to_string());
+            }
+            CheckOutcome::failed(CheckErrorKind::Assertion, failures.join("; ")).with_failed_assertions(failed_assertions)
+        } else if content_missing {
+            CheckOutcome::failed(CheckErrorKind::Content, "Expected content not found in response")
+        } else {
+            CheckOutcome::healthy(None)
+        };
+        outcome.with_latency(latency).with_response(status.as_u16(), bytes)
     }
 
     /// Get detailed response information (for logging/debugging)
@@ -125,10 +243,7 @@ impl ApiMonitor {
             _ => Method::GET,
         };
 
-        let client = Client::builder()
-            .timeout(Duration::from_secs(config.timeout_secs))
-            .build()
-            .map_err(|e| format!("Failed to build HTTP client: {}", e))?;
+        let client = self.clients.client(config).await?;
 
         let mut request = client.request(method, &config.url);
 
@@ -138,6 +253,10 @@ impl ApiMonitor {
             }
         }
 
+        if let Some(auth) = &config.auth {
+            request = auth.apply(request);
+        }
+
         if let Some(body) = &config.body {
             request = request.body(body.clone());
         }
@@ -165,6 +284,108 @@ impl ApiMonitor {
     }
 }
 
+/// Read the response body in chunks, stopping once it is longer than `limit`.
+/// Returns the bytes read and whether the body was cut off.
+async fn read_body(mut response: reqwest::Response, limit: Option<u64>) -> reqwest::Result<(Vec<u8>, bool)> {
+    let mut body = Vec::new();
+    while let Some(chunk) = response.chunk().await? {
+        body.extend_from_slice(&chunk);
+        if limit.is_some_and(|limit| body.len() as u64 > limit) {
+            return Ok((body, true));
+        }
+    }
+    Ok((body, false))
+}
+
+fn build_client(settings: &ClientSettings) -> Result<Client, String> {
+    let (tls, http) = (&settings.tls, &settings.http);
+    let mut builder = Client::builder()
+        .dns_resolver(Arc::new(SystemResolver))
+        .timeout(Duration::from_secs(settings.timeout_secs))
+        .danger_accept_invalid_certs(tls.accept_invalid_certs)
+        .redirect(match http.max_redirects {
+            0 => Policy::none(),
+            max => Policy::limited(max as usize),
+        });
+
+    if let Some(proxy) = http.proxy.as_deref().filter(|proxy| !proxy.trim().is_empty()) {
+        builder = builder.proxy(Proxy::all(proxy).map_err(|e| format!("Invalid proxy: {}", e))?);
+    }
+    builder = match http.http_version {
+        HttpVersion::Auto => builder,
+        HttpVersion::Http1 => builder.http1_only(),
+        HttpVersion::Http2 => builder.http2_prior_knowledge(),
+    };
+
+    let read = |path: &str| std::fs::read(path).map_err(|e| format!("Failed to read {}: {}", path, e));
+    if let Some(path) = configured_path(&tls.ca_bundle) {
+        let certificates =
+            Certificate::from_pem_bundle(&read(path)?).map_err(|e| format!("Invalid CA bundle {}: {}", path, e))?;
+        for certificate in certificates {
+            builder = builder.add_root_certificate(certificate);
+        }
+    }
+    if let (Some(cert_path), Some(key_path)) = (configured_path(&tls.client_cert), configured_path(&tls.client_key)) {
+        let identity = Identity::from_pkcs8_pem(&read(cert_path)?, &read(key_path)?)
+            .map_err(|e| format!("Invalid client certificate {}: {}", cert_path, e))?;
+        builder = builder.identity(identity);
+    }
+
+    builder.build().map_err(|e| format!("Failed to build HTTP client: {}", e))
+}
+
+/// Classify a failed request
+fn request_error_kind(error: &reqwest::Error) -> CheckErrorKind {
+    if error.is_timeout() {
+        return CheckErrorKind::Timeout;
+    }
+    if error.is_builder() {
+        return CheckErrorKind::Config;
+    }
+    // reqwest reports resolver and TLS failures as connect errors, the cause tells them apart
+    let mut source = error.source();
+    while let Some(cause) = source {
+        if cause.is::<DnsError>() {
+            return CheckErrorKind::Dns;
+        }
+        if let Some(tls) = cause.downcast_ref::<native_tls::Error>() {
+            return tls_failure_kind(tls);
+        }
+        source = cause.source();
+    }
+    CheckErrorKind::Connection
+}
+
+/// Failed host name lookup, so checks can tell it from other connect errors
+#[derive(Debug)]
+struct DnsError(std::io::Error);
+
+impl fmt::Display for DnsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl Error for DnsError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.0)
+    }
+}
+
+/// System resolver like the default of reqwest, reporting failures as `DnsError`
+struct SystemResolver;
+
+impl Resolve for SystemResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        let host = name.as_str().to_string();
+        Box::pin(async move {
+            // The connector fills in the port
+            let addrs = tokio::net::lookup_host((host, 0)).await.map_err(DnsError)?;
+            Ok(Box::new(addrs) as Addrs)
+        })
+    }
+}
+
 impl Default for ApiMonitor {
     fn default() -> Self {
         Self::new()
@@ -174,6 +395,8 @@ impl Default for ApiMonitor {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::monitor::assertions::Assertion;
+    use crate::test_http::MockServer;
 
     #[test]
     fn test_api_check_config_get() {
@@ -192,4 +415,166 @@ mod tests {
         assert_eq!(config.method, "POST");
         assert!(config.body.is_some());
     }
+
+    /// Serve one canned HTTP response on a local port
+    fn serve_once(response: &'static str) -> String {
+        MockServer::serve([response]).url("/health")
+    }
+
+    fn compile(assertions: serde_json::Value) -> Arc<CompiledAssertions> {
+        let assertions: Vec<Assertion> = serde_json::from_value(assertions).unwrap();
+        Arc::new(CompiledAssertions::compile(&assertions).unwrap())
+    }
+
+    #[test]
+    fn test_check_api_outcome() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let monitor = ApiMonitor::new();
+
+        let mut config = ApiCheckConfig::get(serve_once(
+            "HTTP/1.1 200 OK\r\nContent-Length: 11\r\nConnection: close\r\n\r\nstatus: up\n",
+        ));
+        config.expected_content = Some("up".to_string());
+        let outcome = runtime.block_on(monitor.check_api(&config));
+        assert!(outcome.healthy);
+        assert_eq!(outcome.status_code, Some(200));
+        assert_eq!(outcome.bytes, Some(11));
+        assert!(outcome.latency_ms.is_some());
+
+        let config = ApiCheckConfig::get(serve_once(
+            "HTTP/1.1 503 Service Unavailable\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+        ));
+        let outcome = runtime.block_on(monitor.check_api(&config));
+        assert!(!outcome.healthy);
+        assert_eq!(outcome.status_code, Some(503));
+        assert_eq!(outcome.error_kind, Some(CheckErrorKind::Status));
+
+        // Status assertions replace the 2xx rule, other failures are listed one by one
+        let mut config = ApiCheckConfig::get(serve_once(
+            "HTTP/1.1 503 Service Unavailable\r\nContent-Length: 17\r\nConnection: close\r\n\r\n{\"status\":\"down\"}",
+        ));
+        config.assertions = compile(serde_json::json!([
+            {"type": "status", "codes": ["200", "503"]},
+            {"type": "json_path", "path": "$.status", "equals": "up"},
+            {"type": "header", "name": "x-version"}
+        ]));
+        let outcome = runtime.block_on(monitor.check_api(&config));
+        assert!(!outcome.healthy);
+        assert_eq!(outcome.error_kind, Some(CheckErrorKind::Assertion));
+        assert_eq!(outcome.failed_assertions.len(), 2);
+        assert_eq!(outcome.status_code, Some(503));
+
+        // Reading stops at the size limit
+        let mut config = ApiCheckConfig::get(serve_once(
+            "HTTP/1.1 200 OK\r\nContent-Length: 100000\r\nConnection: close\r\n\r\nstatus: up",
+        ));
+        config.expected_content = Some("down".to_string());
+        config.assertions = compile(serde_json::json!([{"type": "max_size", "bytes": 4}]));
+        let outcome = runtime.block_on(monitor.check_api(&config));
+        assert_eq!(outcome.failed_assertions, vec!["max_size: body is over the limit of 4 bytes".to_string()]);
+    }
+
+    #[test]
+    fn test_self_signed_certificates() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let monitor = ApiMonitor::new();
+        // The rejected handshake uses up no response
+        let ok = "HTTP/1.1 200 OK\r\nContent-Length: 2\r\nConnection: close\r\n\r\nok";
+        let server = MockServer::serve_tls(30, [ok, ok]);
+        let mut config = ApiCheckConfig::get(format!("https://localhost:{}/health", server.port));
+
+        let outcome = runtime.block_on(monitor.check_api(&config));
+        assert_eq!(outcome.error_kind, Some(CheckErrorKind::Certificate), "{:?}", outcome.error);
+
+        config.tls.accept_invalid_certs = true;
+        let outcome = runtime.block_on(monitor.check_api(&config));
+        assert!(outcome.healthy, "{:?}", outcome.error);
+
+        // Blank paths are unset, as validation treats them
+        config.tls.ca_bundle = Some(" ".to_string());
+        config.tls.client_cert = Some(String::new());
+        config.tls.client_key = Some(String::new());
+        let outcome = runtime.block_on(monitor.check_api(&config));
+        assert!(outcome.healthy, "{:?}", outcome.error);
+
+        config.tls.ca_bundle = Some("/nonexistent/ca.pem".to_string());
+        let outcome = runtime.block_on(monitor.check_api(&config));
+        assert_eq!(outcome.error_kind, Some(CheckErrorKind::Config));
+    }
+
+    #[test]
+    fn test_changed_certificate_files_are_read_again() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let dir = std::env::temp_dir().join(format!("devtools-certs-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let ca_bundle = dir.join("ca.pem");
+        let key = rcgen::KeyPair::generate().unwrap();
+        let certificate = rcgen::CertificateParams::new(vec!["ca.local".to_string()]).unwrap().self_signed(&key).unwrap();
+        std::fs::write(&ca_bundle, certificate.pem()).unwrap();
+
+        let pool = ClientPool::new();
+        let mut config = ApiCheckConfig::get("https://localhost/health".to_string());
+        config.tls.ca_bundle = Some(ca_bundle.display().to_string());
+        assert!(runtime.block_on(pool.client(&config)).is_ok());
+        assert!(runtime.block_on(pool.client(&config)).is_ok());
+
+        // A replaced file is read again instead of reusing the client built from the old one
+        std::fs::write(&ca_bundle, "-----BEGIN CERTIFICATE-----\nbm90IGEgY2VydGlmaWNhdGU=\n-----END CERTIFICATE-----\n").unwrap();
+        let file = std::fs::File::options().write(true).open(&ca_bundle).unwrap();
+        file.set_modified(SystemTime::now() + Duration::from_secs(60)).unwrap();
+        let error = runtime.block_on(pool.client(&config)).unwrap_err();
+        assert!(error.starts_with("Invalid CA bundle"), "{}", error);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_request_errors_are_classified() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let monitor = ApiMonitor::new();
+
+        let config = ApiCheckConfig::get("http://monitor-test.invalid/health".to_string());
+        let outcome = runtime.block_on(monitor.check_api(&config));
+        assert_eq!(outcome.error_kind, Some(CheckErrorKind::Dns), "{:?}", outcome.error);
+
+        // Nothing listens on the port once the listener is dropped
+        let port = std::net::TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port();
+        let config = ApiCheckConfig::get(format!("http://127.0.0.1:{}/health", port));
+        let outcome = runtime.block_on(monitor.check_api(&config));
+        assert_eq!(outcome.error_kind, Some(CheckErrorKind::Connection), "{:?}", outcome.error);
+    }
+
+    #[test]
+    fn test_checks_reuse_pooled_connections() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+
+        // Accept a single connection and answer two requests on it
+        let server = MockServer::serve_keep_alive(["HTTP/1.1 200 OK\r\nContent-Length: 2\r\n\r\nok"; 2]);
+
+        let monitor = ApiMonitor::new();
+        let mut config = ApiCheckConfig::get(server.url("/health"));
+        config.timeout_secs = 2;
+        for _ in 0..2 {
+            let outcome = runtime.block_on(monitor.check_api(&config));
+            assert!(outcome.healthy, "{:?}", outcome.error);
+        }
+    }
+
+    #[test]
+    fn test_redirect_policy() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let monitor = ApiMonitor::new();
+
+        let mut config = ApiCheckConfig::get(serve_once(
+            "HTTP/1.1 302 Found\r\nLocation: /login\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+        ));
+        config.http.max_redirects = 0;
+        let outcome = runtime.block_on(monitor.check_api(&config));
+        assert_eq!(outcome.status_code, Some(302));
+        assert_eq!(outcome.error_kind, Some(CheckErrorKind::Status));
+
+        config.http.proxy = Some("not a proxy".to_string());
+        let outcome = runtime.block_on(monitor.check_api(&config));
+        assert_eq!(outcome.error_kind, Some(CheckErrorKind::Config));
+    }
 }
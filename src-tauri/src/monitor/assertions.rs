@@ -0,0 +1,284 @@
+use regex::Regex;
+use reqwest::header::HeaderMap;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use serde_json_path::JsonPath;
+
+/// Condition an API response must meet, listed in the assertions of the API config
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Assertion {
+    /// Status code is one of the codes: "200", "2xx" or "200-204".
+    /// Replaces the default "any 2xx" rule.
+    Status { codes: Vec<String> },
+    /// JSONPath matches a value (equal to `equals` when given)
+    JsonPath {
+        path: String,
+        #[serde(default)]
+        equals: Option<Value>,
+    },
+    /// Regular expression matches the body
+    Regex { pattern: String },
+    /// Header is present (and contains `value` when given)
+    Header {
+        name: String,
+        #[serde(default)]
+        value: Option<String>,
+    },
+    /// Body is at most this many bytes
+    MaxSize { bytes: u64 },
+}
+
+impl Assertion {
+    /// Make sure the assertion can be evaluated
+    pub fn validate(&self) -> Result<(), String> {
+        self.matcher().map(|_| ())
+    }
+
+    /// Parse the pattern the assertion matches with
+    fn matcher(&self) -> Result<Matcher, String> {
+        let matcher = match self {
+            Assertion::Status { codes } => {
+                if codes.is_empty() {
+                    return Err("status assertion needs at least one code".to_string());
+                }
+                for code in codes {
+                    parse_status_range(code)?;
+                }
+                Matcher::None
+            }
+            Assertion::JsonPath { path, .. } => Matcher::JsonPath(
+                JsonPath::parse(path).map_err(|e| format!("Invalid JSONPath '{}': {}", path, e))?,
+            ),
+            Assertion::Regex { pattern } => {
+                Matcher::Regex(Regex::new(pattern).map_err(|e| format!("Invalid regex '{}': {}", pattern, e))?)
+            }
+            Assertion::Header { name, .. } => {
+                reqwest::header::HeaderName::from_bytes(name.as_bytes())
+                    .map_err(|_| format!("Invalid header name '{}'", name))?;
+                Matcher::None
+            }
+            Assertion::MaxSize { .. } => Matcher::None,
+        };
+        Ok(matcher)
+    }
+}
+
+/// Parsed pattern of an assertion
+#[derive(Debug)]
+enum Matcher {
+    None,
+    JsonPath(JsonPath),
+    Regex(Regex),
+}
+
+/// Assertions ready to evaluate, with their JSONPaths and regular expressions parsed once
+#[derive(Debug, Default)]
+pub struct CompiledAssertions {
+    assertions: Vec<(Assertion, Matcher)>,
+}
+
+impl CompiledAssertions {
+    pub fn compile(assertions: &[Assertion]) -> Result<Self, String> {
+        let assertions = assertions
+            .iter()
+            .map(|assertion| Ok((assertion.clone(), assertion.matcher()?)))
+            .collect::<Result<_, String>>()?;
+        Ok(Self { assertions })
+    }
+
+    /// Whether the assertions decide which status codes are acceptable
+    pub fn checks_status(&self) -> bool {
+        self.assertions.iter().any(|(assertion, _)| matches!(assertion, Assertion::Status { .. }))
+    }
+
+    /// Smallest body size allowed by a max_size assertion; longer bodies need not be read to the end
+    pub fn max_body_size(&self) -> Option<u64> {
+        self.assertions
+            .iter()
+            .filter_map(|(assertion, _)| match assertion {
+                Assertion::MaxSize { bytes } => Some(*bytes),
+                _ => None,
+            })
+            .min()
+    }
+
+    /// Evaluate every assertion against a response, returning one message per failed assertion.
+    /// `size` is the number of bytes received, which the text `body` may differ from after decoding.
+    /// A `truncated` body was only read up to the max_size limit, so the assertions on its content are skipped.
+    pub fn evaluate(&self, status: u16, headers: &HeaderMap, body: &str, size: u64, truncated: bool) -> Vec<String> {
+        let mut json: Option<Option<Value>> = None; // Body parsed on first use
+        let mut failures = Vec::new();
+
+        for (assertion, matcher) in &self.assertions {
+            let failure = match (assertion, matcher) {
+                (Assertion::Status { codes }, _) => {
+                    let matched = codes
+                        .iter()
+                        .filter_map(|code| parse_status_range(code).ok())
+                        .any(|(low, high)| (low..=high).contains(&status));
+                    (!matched).then(|| format!("status: expected {}, got {}", codes.join(", "), status))
+                }
+                (Assertion::JsonPath { .. } | Assertion::Regex { .. }, _) if truncated => None,
+                (Assertion::JsonPath { path, equals }, Matcher::JsonPath(json_path)) => {
+                    let document = json.get_or_insert_with(|| serde_json::from_str(body).ok());
+                    check_json_path(document.as_ref(), path, json_path, equals.as_ref())
+                }
+                (Assertion::Regex { pattern }, Matcher::Regex(regex)) => {
+                    (!regex.is_match(body)).then(|| format!("regex '{}': no match in body", pattern))
+                }
+                (Assertion::Header { name, value }, _) => match (headers.get(name.as_str()), value) {
+                    (None, _) => Some(format!("header {}: missing", name)),
+                    (Some(actual), Some(expected)) => {
+                        let actual = String::from_utf8_lossy(actual.as_bytes());
+                        (!actual.contains(expected.as_str()))
+                            .then(|| format!("header {}: expected '{}', got '{}'", name, expected, actual))
+                    }
+                    (Some(_), None) => None,
+                },
+                (Assertion::MaxSize { bytes }, _) => {
+                    if truncated {
+                        Some(format!("max_size: body is over the limit of {} bytes", bytes))
+                    } else {
+                        (size > *bytes).then(|| format!("max_size: body is {} bytes, limit {}", size, bytes))
+                    }
+                }
+                // `compile` pairs JSONPath and regex assertions with their matcher
+                (Assertion::JsonPath { .. } | Assertion::Regex { .. }, _) => None,
+            };
+            failures.extend(failure);
+        }
+
+        failures
+    }
+}
+
+fn check_json_path(document: Option<&Value>, path: &str, json_path: &JsonPath, equals: Option<&Value>) -> Option<String> {
+    let Some(document) = document else {
+        return Some(format!("json_path {}: response is not JSON", path));
+    };
+
+    let nodes = json_path.query(document).all();
+    match (nodes.first(), equals) {
+        (None, _) => Some(format!("json_path {}: not found", path)),
+        (Some(_), None) => None,
+        (Some(_), Some(expected)) if nodes.contains(&expected) => None,
+        (Some(actual), Some(expected)) => Some(format!("json_path {}: expected {}, got {}", path, expected, actual)),
+    }
+}
+
+/// Parse "200", "2xx" or "200-299" into an inclusive range
+fn parse_status_range(code: &str) -> Result<(u16, u16), String> {
+    let code = code.trim();
+    let invalid = || format!("Invalid status code '{}': use e.g. 200, 2xx or 200-299", code);
+    let parse = |value: &str| value.trim().parse::<u16>().ok().filter(|c| (100..=599).contains(c));
+
+    let range = if let Some(class) = code.strip_suffix("xx").or_else(|| code.strip_suffix("XX")) {
+        let class = class.parse::<u16>().ok().filter(|c| (1..=5).contains(c)).ok_or_else(invalid)?;
+        (class * 100, class * 100 + 99)
+    } else if let Some((low, high)) = code.split_once('-') {
+        (parse(low).ok_or_else(invalid)?, parse(high).ok_or_else(invalid)?)
+    } else {
+        let code = parse(code).ok_or_else(invalid)?;
+        (code, code)
+    };
+
+    if range.0 > range.1 {
+        return Err(invalid());
+    }
+    Ok(range)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn parse_assertions(value: Option<&Value>) -> Result<Vec<Assertion>, String> {
+        let assertions: Vec<Assertion> = match value {
+            None => return Ok(Vec::new()),
+            Some(value) => serde_json::from_value(value.clone()).map_err(|e| e.to_string())?,
+        };
+        for assertion in &assertions {
+            assertion.validate()?;
+        }
+        Ok(assertions)
+    }
+
+    #[test]
+    fn test_parse_assertions() {
+        let value = json!([
+            {"type": "status", "codes": ["200", "3xx", "401-403"]},
+            {"type": "json_path", "path": "$.status", "equals": "up"},
+            {"type": "regex", "pattern": "^\\{"},
+            {"type": "header", "name": "Content-Type", "value": "json"},
+            {"type": "max_size", "bytes": 1024}
+        ]);
+        assert_eq!(parse_assertions(Some(&value)).unwrap().len(), 5);
+        assert!(parse_assertions(None).unwrap().is_empty());
+
+        let invalid = [
+            json!([{"type": "status", "codes": ["6xx"]}]),
+            json!([{"type": "status", "codes": ["299-200"]}]),
+            json!([{"type": "json_path", "path": "status"}]),
+            json!([{"type": "regex", "pattern": "("}]),
+            json!([{"type": "unknown"}]),
+        ];
+        for value in invalid {
+            assert!(parse_assertions(Some(&value)).is_err(), "{}", value);
+        }
+    }
+
+    #[test]
+    fn test_each_failed_assertion_is_reported() {
+        let assertions = parse_assertions(Some(&json!([
+            {"type": "status", "codes": ["2xx"]},
+            {"type": "json_path", "path": "$.status", "equals": "up"},
+            {"type": "json_path", "path": "$.checks[*].name", "equals": "db"},
+            {"type": "json_path", "path": "$.version"},
+            {"type": "regex", "pattern": "\"status\":\\s*\"\\w+\""},
+            {"type": "header", "name": "content-type", "value": "application/json"},
+            {"type": "header", "name": "x-request-id"},
+            {"type": "max_size", "bytes": 16}
+        ])))
+        .unwrap();
+        let assertions = CompiledAssertions::compile(&assertions).unwrap();
+        let mut headers = HeaderMap::new();
+        headers.insert("content-type", "application/json; charset=utf-8".parse().unwrap());
+        let body = r#"{"status": "down", "checks": [{"name": "cache"}, {"name": "db"}]}"#;
+
+        let failures = assertions.evaluate(200, &headers, body, body.len() as u64, false);
+        assert_eq!(
+            failures,
+            vec![
+                "json_path $.status: expected \"up\", got \"down\"".to_string(),
+                "json_path $.version: not found".to_string(),
+                "header x-request-id: missing".to_string(),
+                format!("max_size: body is {} bytes, limit 16", body.len()),
+            ]
+        );
+
+        let failures = assertions.evaluate(503, &headers, "{}", 2, false);
+        assert_eq!(failures[0], "status: expected 2xx, got 503");
+        let failures = assertions.evaluate(200, &headers, "<html>", 6, false);
+        assert_eq!(failures[0], "json_path $.status: response is not JSON");
+
+        // Only the part up to the size limit was read: the content is not checked
+        let failures = assertions.evaluate(200, &headers, &body[..17], 17, true);
+        assert_eq!(
+            failures,
+            vec![
+                "header x-request-id: missing".to_string(),
+                "max_size: body is over the limit of 16 bytes".to_string(),
+            ]
+        );
+        assert_eq!(assertions.max_body_size(), Some(16));
+
+        // The size is the one received, not the one of the decoded text
+        let bytes = [0xff; 10];
+        let body = String::from_utf8_lossy(&bytes);
+        assert_eq!(body.len(), 30);
+        let failures = assertions.evaluate(200, &headers, &body, bytes.len() as u64, false);
+        assert!(!failures.iter().any(|failure| failure.starts_with("max_size")), "{:?}", failures);
+    }
+}
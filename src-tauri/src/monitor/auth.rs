@@ -0,0 +1,346 @@
+use reqwest::{Client, RequestBuilder};
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+
+/// Tokens are refreshed this long before they expire
+const REFRESH_MARGIN: Duration = Duration::from_secs(30);
+/// Lifetime assumed when the token response has no expires_in
+const DEFAULT_TOKEN_LIFETIME: Duration = Duration::from_secs(300);
+
+/// How an API monitor authenticates. Credentials are ${secret:NAME} references,
+/// resolved right before each check so they are never stored in the monitor.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ApiAuth {
+    Bearer {
+        token: String,
+    },
+    Basic {
+        username: String,
+        password: String,
+    },
+    ApiKey {
+        #[serde(default = "default_api_key_header")]
+        header: String,
+        value: String,
+    },
+    /// OAuth2 client credentials grant; the access token is sent as a bearer token
+    #[serde(rename = "oauth2")]
+    OAuth2 {
+        token_url: String,
+        client_id: String,
+        client_secret: String,
+        #[serde(default)]
+        scope: Option<String>,
+    },
+}
+
+fn default_api_key_header() -> String {
+    "X-API-Key".to_string()
+}
+
+impl ApiAuth {
+    /// Fields holding credentials, by name, which must be secret references
+    pub fn credentials(&self) -> Vec<(&'static str, &str)> {
+        match self {
+            ApiAuth::Bearer { token } => vec![("token", token)],
+            ApiAuth::Basic { password, .. } => vec![("password", password)],
+            ApiAuth::ApiKey { value, .. } => vec![("value", value)],
+            ApiAuth::OAuth2 { client_secret, .. } => vec![("client_secret", client_secret)],
+        }
+    }
+
+    /// Apply `resolve` to every value that may contain secret references
+    pub fn resolve_strings(&self, resolve: &impl Fn(&str) -> Result<String, String>) -> Result<Self, String> {
+        Ok(match self {
+            ApiAuth::Bearer { token } => ApiAuth::Bearer { token: resolve(token)? },
+            ApiAuth::Basic { username, password } => ApiAuth::Basic {
+                username: resolve(username)?,
+                password: resolve(password)?,
+            },
+            ApiAuth::ApiKey { header, value } => ApiAuth::ApiKey {
+                header: header.clone(),
+                value: resolve(value)?,
+            },
+            ApiAuth::OAuth2 {
+                token_url,
+                client_id,
+                client_secret,
+                scope,
+            } => ApiAuth::OAuth2 {
+                token_url: resolve(token_url)?,
+                client_id: resolve(client_id)?,
+                client_secret: resolve(client_secret)?,
+                scope: scope.clone(),
+            },
+        })
+    }
+}
+
+/// Credentials added to one request, after OAuth2 tokens have been fetched
+#[derive(Debug, Clone, PartialEq)]
+pub enum RequestAuth {
+    Bearer(String),
+    Basic { username: String, password: String },
+    Header { name: String, value: String },
+}
+
+impl RequestAuth {
+    pub fn apply(&self, request: RequestBuilder) -> RequestBuilder {
+        match self {
+            RequestAuth::Bearer(token) => request.bearer_auth(token),
+            RequestAuth::Basic { username, password } => request.basic_auth(username, Some(password)),
+            RequestAuth::Header { name, value } => request.header(name.as_str(), value.as_str()),
+        }
+    }
+}
+
+struct CachedToken {
+    access_token: String,
+    refresh_at: Instant,
+}
+
+/// Cached token of one key; locked while the token is fetched
+type TokenSlot = Arc<Mutex<Option<CachedToken>>>;
+
+/// OAuth2 access tokens shared by all monitors, keyed by token URL, client credentials and scope
+#[derive(Default)]
+pub struct TokenCache {
+    tokens: Mutex<HashMap<String, TokenSlot>>,
+}
+
+impl TokenCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Request credentials for resolved auth settings, fetching an OAuth2 token if none is cached.
+    /// Tokens are requested with `client`, so they go through the proxy and TLS settings of the monitor.
+    pub async fn request_auth(&self, auth: &ApiAuth, client: &Client) -> Result<RequestAuth, String> {
+        Ok(match auth {
+            ApiAuth::Bearer { token } => RequestAuth::Bearer(token.clone()),
+            ApiAuth::Basic { username, password } => RequestAuth::Basic {
+                username: username.clone(),
+                password: password.clone(),
+            },
+            ApiAuth::ApiKey { header, value } => RequestAuth::Header {
+                name: header.clone(),
+                value: value.clone(),
+            },
+            ApiAuth::OAuth2 {
+                token_url,
+                client_id,
+                client_secret,
+                scope,
+            } => RequestAuth::Bearer(self.token(client, token_url, client_id, client_secret, scope.as_deref()).await?),
+        })
+    }
+
+    /// Drop the cached token of the auth settings, e.g. after the API rejected it
+    pub async fn invalidate(&self, auth: &ApiAuth) {
+        if let ApiAuth::OAuth2 {
+            token_url,
+            client_id,
+            client_secret,
+            scope,
+        } = auth
+        {
+            let key = cache_key(token_url, client_id, client_secret, scope.as_deref());
+            self.tokens.lock().await.remove(&key);
+        }
+    }
+
+    async fn token(
+        &self,
+        client: &Client,
+        token_url: &str,
+        client_id: &str,
+        client_secret: &str,
+        scope: Option<&str>,
+    ) -> Result<String, String> {
+        let key = cache_key(token_url, client_id, client_secret, scope);
+        let slot = self.tokens.lock().await.entry(key).or_default().clone();
+        // Held while fetching so concurrent checks do not request the same token twice,
+        // while checks using other tokens go on
+        let mut slot = slot.lock().await;
+        if let Some(cached) = slot.as_ref() {
+            if Instant::now() < cached.refresh_at {
+                return Ok(cached.access_token.clone());
+            }
+        }
+
+        let mut form = vec![
+            ("grant_type", "client_credentials"),
+            ("client_id", client_id),
+            ("client_secret", client_secret),
+        ];
+        if let Some(scope) = scope.filter(|scope| !scope.is_empty()) {
+            form.push(("scope", scope));
+        }
+
+        let response = client
+            .post(token_url)
+            .form(&form)
+            .send()
+            .await
+            .map_err(|e| format!("Token request failed: {}", e))?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(format!("Token request failed: HTTP {}", status.as_u16()));
+        }
+        let body: TokenResponse = response
+            .json()
+            .await
+            .map_err(|e| format!("Invalid token response: {}", e))?;
+
+        let lifetime = body.expires_in.map_or(DEFAULT_TOKEN_LIFETIME, Duration::from_secs);
+        *slot = Some(CachedToken {
+            access_token: body.access_token.clone(),
+            refresh_at: Instant::now() + lifetime.saturating_sub(REFRESH_MARGIN),
+        });
+        Ok(body.access_token)
+    }
+}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    #[serde(default)]
+    expires_in: Option<u64>,
+}
+
+/// The secret is part of the key (as a hash), so a rotated or mistyped secret never reuses an older token
+fn cache_key(token_url: &str, client_id: &str, client_secret: &str, scope: Option<&str>) -> String {
+    let mut secret_hash = DefaultHasher::new();
+    client_secret.hash(&mut secret_hash);
+    format!(
+        "{}\n{}\n{:016x}\n{}",
+        token_url,
+        client_id,
+        secret_hash.finish(),
+        scope.unwrap_or_default()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_http::MockServer;
+
+    fn token_response(token: &str) -> String {
+        let body = format!("{{\"access_token\":\"{}\",\"expires_in\":3600}}", token);
+        format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            body.len(),
+            body
+        )
+    }
+
+    #[test]
+    fn test_oauth2_tokens_are_cached_until_invalidated() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let server = MockServer::serve([token_response("token-0"), token_response("token-1")]);
+        let auth = ApiAuth::OAuth2 {
+            token_url: server.url("/token"),
+            client_id: "monitor".to_string(),
+            client_secret: "s3cret".to_string(),
+            scope: Some("health:read".to_string()),
+        };
+        let cache = TokenCache::new();
+        let client = Client::new();
+
+        let first = runtime.block_on(cache.request_auth(&auth, &client)).unwrap();
+        assert_eq!(first, RequestAuth::Bearer("token-0".to_string()));
+        let request = server.next_request();
+        assert!(request.head.starts_with("POST /token "), "{}", request.head);
+        let body = request.body;
+        assert!(body.contains("grant_type=client_credentials"), "{}", body);
+        assert!(body.contains("scope=health%3Aread"), "{}", body);
+
+        // Served from the cache, no second request
+        assert_eq!(runtime.block_on(cache.request_auth(&auth, &client)).unwrap(), first);
+        assert!(server.try_next_request().is_none());
+
+        runtime.block_on(cache.invalidate(&auth));
+        let refreshed = runtime.block_on(cache.request_auth(&auth, &client)).unwrap();
+        assert_eq!(refreshed, RequestAuth::Bearer("token-1".to_string()));
+    }
+
+    #[test]
+    fn test_tokens_are_cached_per_client_secret() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let server = MockServer::serve([token_response("token-0"), token_response("token-1")]);
+        let auth = |client_secret: &str| ApiAuth::OAuth2 {
+            token_url: server.url("/token"),
+            client_id: "monitor".to_string(),
+            client_secret: client_secret.to_string(),
+            scope: None,
+        };
+        let cache = TokenCache::new();
+        let client = Client::new();
+
+        let old = runtime.block_on(cache.request_auth(&auth("old-secret"), &client)).unwrap();
+        assert_eq!(old, RequestAuth::Bearer("token-0".to_string()));
+        let rotated = runtime.block_on(cache.request_auth(&auth("new-secret"), &client)).unwrap();
+        assert_eq!(rotated, RequestAuth::Bearer("token-1".to_string()));
+        assert!(server.next_request().body.contains("client_secret=old-secret"));
+        assert!(server.next_request().body.contains("client_secret=new-secret"));
+    }
+
+    #[test]
+    fn test_fetching_a_token_does_not_block_other_tokens() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        // Accepts the connection but never answers
+        let stalled_server = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let stalled = ApiAuth::OAuth2 {
+            token_url: format!("http://{}/token", stalled_server.local_addr().unwrap()),
+            client_id: "monitor".to_string(),
+            client_secret: "s3cret".to_string(),
+            scope: None,
+        };
+        let server = MockServer::serve([token_response("token-0")]);
+        let auth = ApiAuth::OAuth2 {
+            token_url: server.url("/token"),
+            client_id: "monitor".to_string(),
+            client_secret: "s3cret".to_string(),
+            scope: None,
+        };
+        let cache = TokenCache::new();
+        let client = Client::new();
+
+        runtime.block_on(async {
+            let stalled = cache.request_auth(&stalled, &client);
+            tokio::pin!(stalled);
+            assert!(tokio::time::timeout(Duration::from_millis(200), &mut stalled).await.is_err());
+
+            let other = tokio::time::timeout(Duration::from_secs(5), cache.request_auth(&auth, &client))
+                .await
+                .expect("the stalled token request blocked the cache");
+            assert_eq!(other.unwrap(), RequestAuth::Bearer("token-0".to_string()));
+        });
+    }
+
+    #[test]
+    fn test_request_auth_headers() {
+        let client = Client::new();
+        let build = |auth: RequestAuth| auth.apply(client.get("http://localhost/")).build().unwrap();
+
+        let request = build(RequestAuth::Bearer("abc".to_string()));
+        assert_eq!(request.headers()["authorization"], "Bearer abc");
+        let request = build(RequestAuth::Basic {
+            username: "user".to_string(),
+            password: "pass".to_string(),
+        });
+        assert_eq!(request.headers()["authorization"], "Basic dXNlcjpwYXNz");
+        let request = build(RequestAuth::Header {
+            name: "X-API-Key".to_string(),
+            value: "key".to_string(),
+        });
+        assert_eq!(request.headers()["x-api-key"], "key");
+    }
+}
@@ -0,0 +1,142 @@
+use std::net::{TcpStream, ToSocketAddrs};
+use std::time::{Duration, Instant};
+
+use super::outcome::{tls_failure_kind, CheckErrorKind, CheckOutcome};
+
+const SECONDS_PER_DAY: i64 = 86_400;
+
+/// Certificate monitor for checking when the TLS certificate of a server expires
+pub struct CertificateMonitor;
+
+impl CertificateMonitor {
+    /// Create a new CertificateMonitor
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Check that the leaf certificate of host:port is valid for at least `warn_days` more days
+    pub fn check_certificate(&self, host: &str, port: u16, warn_days: u32) -> CheckOutcome {
+        self.check_certificate_with_timeout(host, port, warn_days, Duration::from_secs(10))
+    }
+
+    /// Check the certificate with a custom timeout; the latency covers connecting and the handshake
+    pub fn check_certificate_with_timeout(&self, host: &str, port: u16, warn_days: u32, timeout: Duration) -> CheckOutcome {
+        let started = Instant::now();
+        let not_after = match fetch_expiry(host, port, timeout) {
+            Ok(not_after) => not_after,
+            Err(outcome) => return outcome,
+        };
+        let latency = started.elapsed();
+
+        let now = chrono::Utc::now().timestamp();
+        let date = chrono::DateTime::from_timestamp(not_after, 0)
+            .map(|date| date.format("%Y-%m-%d %H:%M UTC").to_string())
+            .unwrap_or_else(|| not_after.to_string());
+        let days_left = (not_after - now).div_euclid(SECONDS_PER_DAY);
+
+        let outcome = if not_after <= now {
+            CheckOutcome::failed(CheckErrorKind::Certificate, format!("Certificate of {}:{} expired on {}", host, port, date))
+        } else if days_left < i64::from(warn_days) {
+            CheckOutcome::failed(
+                CheckErrorKind::Certificate,
+                format!(
+                    "Certificate of {}:{} expires in {} days on {} (warning at {} days)",
+                    host, port, days_left, date, warn_days
+                ),
+            )
+        } else {
+            CheckOutcome::healthy(None)
+        };
+        outcome.with_latency(latency).with_certificate_expiry(not_after)
+    }
+}
+
+impl Default for CertificateMonitor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Connect, complete a TLS handshake and return the expiry (Unix time) of the leaf certificate.
+/// Invalid and expired certificates are accepted so their expiry can still be reported.
+fn fetch_expiry(host: &str, port: u16, timeout: Duration) -> Result<i64, CheckOutcome> {
+    let address = format!("{}:{}", host, port);
+    let addr = address
+        .to_socket_addrs()
+        .map_err(|e| CheckOutcome::failed(CheckErrorKind::Dns, format!("Failed to resolve {}: {}", host, e)))?
+        .next()
+        .ok_or_else(|| CheckOutcome::failed(CheckErrorKind::Dns, format!("No address found for {}", host)))?;
+
+    let stream = TcpStream::connect_timeout(&addr, timeout).map_err(|e| {
+        let kind = if e.kind() == std::io::ErrorKind::TimedOut {
+            CheckErrorKind::Timeout
+        } else {
+            CheckErrorKind::Connection
+        };
+        CheckOutcome::failed(kind, format!("Failed to connect to {}: {}", addr, e))
+    })?;
+    let _ = stream.set_read_timeout(Some(timeout));
+    let _ = stream.set_write_timeout(Some(timeout));
+
+    let tls_error = |message: String| CheckOutcome::failed(CheckErrorKind::Certificate, message);
+    let connector = native_tls::TlsConnector::builder()
+        .danger_accept_invalid_certs(true)
+        .danger_accept_invalid_hostnames(true)
+        .build()
+        .map_err(|e| CheckOutcome::failed(CheckErrorKind::Config, format!("Failed to set up TLS: {}", e)))?;
+    let tls = connector.connect(host, stream).map_err(|e| {
+        let kind = match &e {
+            native_tls::HandshakeError::Failure(error) => tls_failure_kind(error),
+            // The socket timeouts ran out in the middle of the handshake
+            native_tls::HandshakeError::WouldBlock(_) => CheckErrorKind::Timeout,
+        };
+        CheckOutcome::failed(kind, format!("TLS handshake with {} failed: {}", address, e))
+    })?;
+
+    let der = tls
+        .peer_certificate()
+        .map_err(|e| tls_error(format!("Failed to read the certificate of {}: {}", address, e)))?
+        .ok_or_else(|| tls_error(format!("{} sent no certificate", address)))?
+        .to_der()
+        .map_err(|e| tls_error(format!("Failed to read the certificate of {}: {}", address, e)))?;
+    let (_, certificate) = x509_parser::parse_x509_certificate(&der)
+        .map_err(|e| tls_error(format!("Invalid certificate from {}: {}", address, e)))?;
+
+    Ok(certificate.validity().not_after.timestamp())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_http::MockServer;
+
+    #[test]
+    fn test_alerts_when_expiry_is_near() {
+        let response = "HTTP/1.1 200 OK\r\nContent-Length: 0\r\nConnection: close\r\n\r\n";
+        let monitor = CertificateMonitor::new();
+
+        let server = MockServer::serve_tls(90, [response; 2]);
+        let outcome = monitor.check_certificate("127.0.0.1", server.port, 30);
+        assert!(outcome.healthy, "{:?}", outcome.error);
+        let days_left = (outcome.certificate_expires_at.unwrap() - chrono::Utc::now().timestamp()) / SECONDS_PER_DAY;
+        assert!((88..=90).contains(&days_left), "{}", days_left);
+
+        let outcome = monitor.check_certificate("127.0.0.1", server.port, 120);
+        assert_eq!(outcome.error_kind, Some(CheckErrorKind::Certificate));
+        assert!(outcome.error.unwrap().contains("(warning at 120 days)"));
+
+        let server = MockServer::serve_tls(-3, [response]);
+        let outcome = monitor.check_certificate("127.0.0.1", server.port, 14);
+        assert!(outcome.error.unwrap().contains("expired on"));
+    }
+
+    #[test]
+    fn test_silent_servers_time_out() {
+        // Connections are queued by the listener but the handshake is never answered
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let outcome = CertificateMonitor::new().check_certificate_with_timeout("127.0.0.1", port, 14, Duration::from_millis(200));
+        assert_eq!(outcome.error_kind, Some(CheckErrorKind::Timeout), "{:?}", outcome.error);
+        drop(listener);
+    }
+}
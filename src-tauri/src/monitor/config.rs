@@ -0,0 +1,653 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::database::models::Monitor;
+use super::assertions::Assertion;
+use super::auth::ApiAuth;
+use super::port_monitor::parse_port_target;
+use super::process_monitor::ProcessTarget;
+
+/// HTTP methods an API monitor can send
+pub const HTTP_METHODS: [&str; 5] = ["GET", "POST", "PUT", "DELETE", "PATCH"];
+/// Longest request timeout of an API monitor (seconds)
+pub const MAX_TIMEOUT_SECS: u64 = 300;
+/// Most redirects an API monitor can be set to follow
+pub const MAX_REDIRECTS: u32 = 20;
+/// Certificate monitors warn this many days before expiry unless configured otherwise
+pub const DEFAULT_WARN_DAYS: u32 = 14;
+
+/// What a monitor checks, stored as JSON in the `config` column
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum MonitorConfig {
+    Process(ProcessConfig),
+    Api(Box<ApiConfig>),
+    Port(PortConfig),
+    Certificate(CertificateConfig),
+}
+
+/// Process to look for; exactly one of `name` and `pid` is set
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProcessConfig {
+    #[serde(default)]
+    pub name: Option<String>, // Matches processes whose name contains this (case-insensitive)
+    #[serde(default)]
+    pub pid: Option<u32>,
+}
+
+/// HTTP request and the conditions its response must meet
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ApiConfig {
+    #[serde(default)]
+    pub url: String,
+    #[serde(default = "default_method")]
+    pub method: String,
+    #[serde(default)]
+    pub headers: Vec<HttpHeader>,
+    #[serde(default)]
+    pub body: Option<String>,
+    #[serde(default)]
+    pub assertions: Vec<Assertion>,
+    #[serde(default = "default_timeout_secs")]
+    pub timeout_secs: u64,
+    #[serde(default)]
+    pub auth: Option<ApiAuth>,
+    #[serde(default)]
+    pub tls: TlsOptions,
+    #[serde(default)]
+    pub http: HttpOptions,
+}
+
+/// TLS settings of an API monitor; files are PEM encoded
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct TlsOptions {
+    #[serde(default)]
+    pub accept_invalid_certs: bool, // Accept self-signed, expired and mismatched certificates
+    #[serde(default)]
+    pub ca_bundle: Option<String>, // File with further CA certificates to trust
+    #[serde(default)]
+    pub client_cert: Option<String>, // File with the client certificate for mTLS
+    #[serde(default)]
+    pub client_key: Option<String>, // File with the PKCS#8 private key of the client certificate
+}
+
+/// Connection settings of an API monitor
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct HttpOptions {
+    #[serde(default)]
+    pub proxy: Option<String>, // http:// or https:// proxy URL; the system proxy is used when unset
+    #[serde(default = "default_max_redirects")]
+    pub max_redirects: u32, // 0 reports redirects as they are instead of following them
+    #[serde(default)]
+    pub http_version: HttpVersion,
+}
+
+impl Default for HttpOptions {
+    fn default() -> Self {
+        Self {
+            proxy: None,
+            max_redirects: default_max_redirects(),
+            http_version: HttpVersion::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HttpVersion {
+    #[default]
+    Auto,
+    Http1,
+    Http2, // HTTP/2 with prior knowledge, without negotiating it first
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HttpHeader {
+    pub key: String,
+    pub value: String,
+}
+
+/// TCP port that must accept connections
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PortConfig {
+    #[serde(default = "default_host")]
+    pub host: String,
+    #[serde(default)]
+    pub port: u16,
+}
+
+/// Server whose TLS certificate must not expire within `warn_days`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CertificateConfig {
+    #[serde(default)]
+    pub host: String,
+    #[serde(default = "default_tls_port")]
+    pub port: u16,
+    #[serde(default = "default_warn_days")]
+    pub warn_days: u32,
+}
+
+fn default_method() -> String {
+    "GET".to_string()
+}
+
+fn default_timeout_secs() -> u64 {
+    10
+}
+
+fn default_max_redirects() -> u32 {
+    10
+}
+
+fn default_host() -> String {
+    "127.0.0.1".to_string()
+}
+
+fn default_tls_port() -> u16 {
+    443
+}
+
+fn default_warn_days() -> u32 {
+    DEFAULT_WARN_DAYS
+}
+
+/// List field of a legacy target; missing means empty
+fn legacy_list<T: serde::de::DeserializeOwned>(value: &Value) -> serde_json::Result<Vec<T>> {
+    match value {
+        Value::Null => Ok(Vec::new()),
+        value => serde_json::from_value(value.clone()),
+    }
+}
+
+/// Invalid value of a monitor field; `field` is the path in the monitor, e.g. "config.url"
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FieldError {
+    pub field: String,
+    pub message: String,
+}
+
+impl FieldError {
+    fn new(field: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            field: field.into(),
+            message: message.into(),
+        }
+    }
+}
+
+/// Error of the monitor commands, listing the invalid fields when validation failed
+#[derive(Debug, Serialize)]
+pub struct MonitorError {
+    pub message: String,
+    pub fields: Vec<FieldError>, // Empty for errors that are not about a field
+}
+
+impl MonitorError {
+    pub fn invalid(fields: Vec<FieldError>) -> Self {
+        let details: Vec<String> = fields.iter().map(|f| format!("{}: {}", f.field, f.message)).collect();
+        Self {
+            message: format!("Invalid monitor: {}", details.join("; ")),
+            fields,
+        }
+    }
+}
+
+impl From<String> for MonitorError {
+    fn from(message: String) -> Self {
+        Self {
+            message,
+            fields: Vec::new(),
+        }
+    }
+}
+
+impl MonitorConfig {
+    /// Convert the target string used before typed configs existed. Unusable values are kept as they are
+    /// so validation can point them out; unknown types and headers or assertions that cannot be read are an error.
+    pub fn from_legacy(monitor_type: &str, target: &str) -> Result<Self, String> {
+        let config = match monitor_type {
+            "api" => {
+                let Ok(json) = serde_json::from_str::<Value>(target) else {
+                    return Ok(MonitorConfig::Api(Box::new(ApiConfig {
+                        url: target.to_string(),
+                        method: default_method(),
+                        headers: Vec::new(),
+                        body: None,
+                        assertions: Vec::new(),
+                        timeout_secs: default_timeout_secs(),
+                        auth: None,
+                        tls: TlsOptions::default(),
+                        http: HttpOptions::default(),
+                    })));
+                };
+                MonitorConfig::Api(Box::new(ApiConfig {
+                    url: json["url"].as_str().unwrap_or_default().to_string(),
+                    // Legacy checks sent unknown methods as GET
+                    method: json["method"]
+                        .as_str()
+                        .map(|method| method.trim().to_ascii_uppercase())
+                        .filter(|method| HTTP_METHODS.contains(&method.as_str()))
+                        .unwrap_or_else(default_method),
+                    headers: legacy_list(&json["headers"]).map_err(|e| format!("Unreadable headers: {}", e))?,
+                    body: json["body"].as_str().map(str::to_string),
+                    assertions: legacy_list(&json["assertions"]).map_err(|e| format!("Unreadable assertions: {}", e))?,
+                    timeout_secs: default_timeout_secs(),
+                    auth: None,
+                    tls: TlsOptions::default(),
+                    http: HttpOptions::default(),
+                }))
+            }
+            "port" => {
+                let (host, port) = parse_port_target(target).unwrap_or_else(|_| (target.to_string(), 0));
+                MonitorConfig::Port(PortConfig { host, port })
+            }
+            "process" => match ProcessTarget::from_string(target) {
+                ProcessTarget::Pid(pid) => MonitorConfig::Process(ProcessConfig { name: None, pid: Some(pid) }),
+                ProcessTarget::Name(name) => MonitorConfig::Process(ProcessConfig { name: Some(name), pid: None }),
+            },
+            other => return Err(format!("Unknown monitor type: {}", other)),
+        };
+        Ok(config)
+    }
+
+    /// Value of the `monitor_type` column
+    pub fn monitor_type(&self) -> &'static str {
+        match self {
+            MonitorConfig::Process(_) => "process",
+            MonitorConfig::Api(_) => "api",
+            MonitorConfig::Port(_) => "port",
+            MonitorConfig::Certificate(_) => "certificate",
+        }
+    }
+
+    /// Short description kept in the `target` column for display
+    pub fn summary(&self) -> String {
+        match self {
+            MonitorConfig::Process(process) => match (&process.name, process.pid) {
+                (_, Some(pid)) => pid.to_string(),
+                (Some(name), None) => name.clone(),
+                (None, None) => String::new(),
+            },
+            MonitorConfig::Api(api) => api.url.clone(),
+            MonitorConfig::Port(port) => format!("{}:{}", port.host, port.port),
+            MonitorConfig::Certificate(certificate) => format!("{}:{}", certificate.host, certificate.port),
+        }
+    }
+
+    /// Check every field, returning all problems at once
+    pub fn validate(&self) -> Vec<FieldError> {
+        let mut errors = Vec::new();
+        match self {
+            MonitorConfig::Process(process) => {
+                let name = process.name.as_deref().map(str::trim).filter(|name| !name.is_empty());
+                match (name, process.pid) {
+                    (None, None) => errors.push(FieldError::new("config.name", "Process name or PID is required")),
+                    (Some(_), Some(_)) => errors.push(FieldError::new("config.pid", "Set either a process name or a PID, not both")),
+                    (None, Some(0)) => errors.push(FieldError::new("config.pid", "PID must be greater than 0")),
+                    _ => {}
+                }
+            }
+            MonitorConfig::Api(api) => {
+                if let Some(message) = validate_url(&api.url) {
+                    errors.push(FieldError::new("config.url", message));
+                }
+                if !HTTP_METHODS.contains(&api.method.as_str()) {
+                    errors.push(FieldError::new(
+                        "config.method",
+                        format!("Unsupported method '{}', expected one of: {}", api.method, HTTP_METHODS.join(", ")),
+                    ));
+                }
+                for (index, header) in api.headers.iter().enumerate() {
+                    if reqwest::header::HeaderName::from_bytes(header.key.as_bytes()).is_err() {
+                        errors.push(FieldError::new(
+                            format!("config.headers[{}].key", index),
+                            format!("Invalid header name '{}'", header.key),
+                        ));
+                    }
+                }
+                for (index, assertion) in api.assertions.iter().enumerate() {
+                    if let Err(message) = assertion.validate() {
+                        errors.push(FieldError::new(format!("config.assertions[{}]", index), message));
+                    }
+                }
+                if !(1..=MAX_TIMEOUT_SECS).contains(&api.timeout_secs) {
+                    errors.push(FieldError::new(
+                        "config.timeout_secs",
+                        format!("Timeout must be between 1 and {} seconds", MAX_TIMEOUT_SECS),
+                    ));
+                }
+                if let Some(auth) = &api.auth {
+                    validate_auth(auth, &mut errors);
+                }
+                validate_tls(&api.tls, &mut errors);
+                if let Some(proxy) = api.http.proxy.as_deref().filter(|proxy| !proxy.trim().is_empty()) {
+                    if let Some(message) = validate_url(proxy) {
+                        errors.push(FieldError::new("config.http.proxy", message));
+                    }
+                }
+                if api.http.max_redirects > MAX_REDIRECTS {
+                    errors.push(FieldError::new(
+                        "config.http.max_redirects",
+                        format!("At most {} redirects can be followed", MAX_REDIRECTS),
+                    ));
+                }
+            }
+            MonitorConfig::Port(port) => {
+                if port.host.trim().is_empty() {
+                    errors.push(FieldError::new("config.host", "Host is required"));
+                }
+                if port.port == 0 {
+                    errors.push(FieldError::new("config.port", "Port must be between 1 and 65535"));
+                }
+            }
+            MonitorConfig::Certificate(certificate) => {
+                if certificate.host.trim().is_empty() {
+                    errors.push(FieldError::new("config.host", "Host is required"));
+                }
+                if certificate.port == 0 {
+                    errors.push(FieldError::new("config.port", "Port must be between 1 and 65535"));
+                }
+                if certificate.warn_days == 0 {
+                    errors.push(FieldError::new("config.warn_days", "Warning window must be at least 1 day"));
+                }
+            }
+        }
+        errors
+    }
+
+    /// Apply `resolve` to the values that may contain ${secret:NAME} references
+    pub fn resolve_strings(&self, resolve: impl Fn(&str) -> Result<String, String>) -> Result<Self, String> {
+        let mut resolved = self.clone();
+        match &mut resolved {
+            MonitorConfig::Process(process) => {
+                if let Some(name) = &mut process.name {
+                    *name = resolve(name)?;
+                }
+            }
+            MonitorConfig::Api(api) => {
+                api.url = resolve(&api.url)?;
+                for header in &mut api.headers {
+                    header.value = resolve(&header.value)?;
+                }
+                if let Some(body) = &mut api.body {
+                    *body = resolve(body)?;
+                }
+                if let Some(auth) = &mut api.auth {
+                    *auth = auth.resolve_strings(&resolve)?;
+                }
+                if let Some(proxy) = &mut api.http.proxy {
+                    *proxy = resolve(proxy)?;
+                }
+            }
+            MonitorConfig::Port(port) => {
+                port.host = resolve(&port.host)?;
+            }
+            MonitorConfig::Certificate(certificate) => {
+                certificate.host = resolve(&certificate.host)?;
+            }
+        }
+        Ok(resolved)
+    }
+}
+
+/// Problem with an API URL, if any. URLs with secret references are only checked once resolved.
+fn validate_url(url: &str) -> Option<String> {
+    let url = url.trim();
+    if url.is_empty() {
+        return Some("URL is required".to_string());
+    }
+    if url.contains("${secret:") {
+        return None;
+    }
+    match reqwest::Url::parse(url) {
+        Ok(parsed) if matches!(parsed.scheme(), "http" | "https") => None,
+        Ok(parsed) => Some(format!("Unsupported URL scheme '{}', use http or https", parsed.scheme())),
+        Err(e) => Some(format!("Invalid URL: {}", e)),
+    }
+}
+
+/// Check the auth settings of an API monitor. Credentials must be secret references
+/// so they are kept in the secret store rather than in the monitor config.
+fn validate_auth(auth: &ApiAuth, errors: &mut Vec<FieldError>) {
+    let required = |field: &str, value: &str, label: &str| {
+        value
+            .trim()
+            .is_empty()
+            .then(|| FieldError::new(format!("config.auth.{}", field), format!("{} is required", label)))
+    };
+    match auth {
+        ApiAuth::Bearer { .. } => {}
+        ApiAuth::Basic { username, .. } => errors.extend(required("username", username, "Username")),
+        ApiAuth::ApiKey { header, .. } => {
+            if reqwest::header::HeaderName::from_bytes(header.as_bytes()).is_err() {
+                errors.push(FieldError::new("config.auth.header", format!("Invalid header name '{}'", header)));
+            }
+        }
+        ApiAuth::OAuth2 { token_url, client_id, .. } => {
+            if let Some(message) = validate_url(token_url) {
+                errors.push(FieldError::new("config.auth.token_url", message));
+            }
+            errors.extend(required("client_id", client_id, "Client ID"));
+        }
+    }
+
+    for (field, value) in auth.credentials() {
+        if !value.contains("${secret:") {
+            errors.push(FieldError::new(
+                format!("config.auth.{}", field),
+                "Store the credential as a secret and reference it with ${secret:NAME}",
+            ));
+        }
+    }
+}
+
+/// Check the TLS files of an API monitor
+fn validate_tls(tls: &TlsOptions, errors: &mut Vec<FieldError>) {
+    let files = [
+        ("ca_bundle", &tls.ca_bundle),
+        ("client_cert", &tls.client_cert),
+        ("client_key", &tls.client_key),
+    ];
+    for (field, path) in files {
+        if let Some(path) = path.as_deref().filter(|path| !path.trim().is_empty()) {
+            if !std::path::Path::new(path).is_file() {
+                errors.push(FieldError::new(format!("config.tls.{}", field), format!("File not found: {}", path)));
+            }
+        }
+    }
+
+    let set = |path: &Option<String>| path.as_deref().is_some_and(|path| !path.trim().is_empty());
+    if set(&tls.client_cert) != set(&tls.client_key) {
+        let field = if set(&tls.client_cert) { "client_key" } else { "client_cert" };
+        errors.push(FieldError::new(
+            format!("config.tls.{}", field),
+            "Client certificate and key must be set together",
+        ));
+    }
+}
+
+impl Monitor {
+    /// Typed config of the monitor, derived from the target when it has none
+    pub fn typed_config(&self) -> Result<MonitorConfig, String> {
+        match &self.config {
+            Some(config) => Ok(config.clone()),
+            None => MonitorConfig::from_legacy(&self.monitor_type, &self.target),
+        }
+    }
+}
+
+/// Validate a monitor and fill in its config, type and target summary before it is stored
+pub fn prepare_monitor(monitor: &mut Monitor) -> Result<(), MonitorError> {
+    let config = monitor
+        .typed_config()
+        .map_err(|e| MonitorError::invalid(vec![FieldError::new("config", e)]))?;
+
+    let mut errors = Vec::new();
+    if monitor.name.trim().is_empty() {
+        errors.push(FieldError::new("name", "Name is required"));
+    }
+    if monitor.check_interval < 1 {
+        errors.push(FieldError::new("check_interval", "Check interval must be at least 1 second"));
+    }
+    errors.extend(config.validate());
+    if !errors.is_empty() {
+        return Err(MonitorError::invalid(errors));
+    }
+
+    monitor.monitor_type = config.monitor_type().to_string();
+    monitor.target = config.summary();
+    monitor.config = Some(config);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_from_legacy_targets() {
+        assert_eq!(
+            MonitorConfig::from_legacy("process", "1234").unwrap(),
+            MonitorConfig::Process(ProcessConfig { name: None, pid: Some(1234) })
+        );
+        assert_eq!(
+            MonitorConfig::from_legacy("port", "db.local:5432").unwrap(),
+            MonitorConfig::Port(PortConfig { host: "db.local".to_string(), port: 5432 })
+        );
+
+        let target = json!({
+            "url": "https://example.com/health",
+            "method": "POST",
+            "headers": [{"key": "Accept", "value": "application/json"}],
+            "body": "{}",
+            "assertions": [{"type": "status", "codes": ["200"]}]
+        });
+        let MonitorConfig::Api(api) = MonitorConfig::from_legacy("api", &target.to_string()).unwrap() else {
+            panic!("expected an API config");
+        };
+        assert_eq!(api.method, "POST");
+        assert_eq!(api.headers.len(), 1);
+        assert_eq!(api.assertions.len(), 1);
+        assert_eq!(api.timeout_secs, 10);
+
+        // Plain URL, and a broken port target kept for validation to report
+        let api = MonitorConfig::from_legacy("api", "http://localhost:8080").unwrap();
+        assert_eq!(api.summary(), "http://localhost:8080");
+        let port = MonitorConfig::from_legacy("port", "db:http").unwrap();
+        assert_eq!(port.validate(), vec![FieldError::new("config.port", "Port must be between 1 and 65535")]);
+
+        // Methods are sent the way the legacy check did
+        let method = |method: &str| match MonitorConfig::from_legacy("api", &json!({"url": "http://a", "method": method}).to_string()) {
+            Ok(MonitorConfig::Api(api)) => api.method,
+            other => panic!("expected an API config, got {:?}", other),
+        };
+        assert_eq!(method(" post"), "POST");
+        assert_eq!(method("TRACE"), "GET");
+
+        // Headers that cannot be read are not dropped silently
+        let target = json!({"url": "http://a", "headers": {"Authorization": "Bearer x"}});
+        let error = MonitorConfig::from_legacy("api", &target.to_string()).unwrap_err();
+        assert!(error.starts_with("Unreadable headers"), "{}", error);
+
+        // An unknown type is not turned into a process check on its target
+        assert_eq!(
+            MonitorConfig::from_legacy("ping", "10.0.0.1").unwrap_err(),
+            "Unknown monitor type: ping"
+        );
+    }
+
+    #[test]
+    fn test_validate_reports_each_field() {
+        let config: MonitorConfig = serde_json::from_value(json!({
+            "type": "api",
+            "url": "",
+            "method": "TRACE",
+            "headers": [{"key": "bad header", "value": "x"}],
+            "assertions": [{"type": "regex", "pattern": "("}],
+            "timeout_secs": 0
+        }))
+        .unwrap();
+        let fields: Vec<String> = config.validate().into_iter().map(|e| e.field).collect();
+        assert_eq!(
+            fields,
+            vec!["config.url", "config.method", "config.headers[0].key", "config.assertions[0]", "config.timeout_secs"]
+        );
+
+        let config: MonitorConfig = serde_json::from_value(json!({"type": "process", "name": " "})).unwrap();
+        assert_eq!(config.validate()[0].field, "config.name");
+        let config: MonitorConfig = serde_json::from_value(json!({"type": "api", "url": "ftp://example.com"})).unwrap();
+        assert_eq!(config.validate()[0].field, "config.url");
+        let config: MonitorConfig =
+            serde_json::from_value(json!({"type": "api", "url": "https://${secret:HOST}/health"})).unwrap();
+        assert!(config.validate().is_empty());
+
+        let config: MonitorConfig = serde_json::from_value(json!({
+            "type": "api",
+            "url": "https://example.com",
+            "tls": {"ca_bundle": "/nonexistent/ca.pem", "client_key": "/nonexistent/client.key"}
+        }))
+        .unwrap();
+        let fields: Vec<String> = config.validate().into_iter().map(|e| e.field).collect();
+        assert_eq!(fields, vec!["config.tls.ca_bundle", "config.tls.client_key", "config.tls.client_cert"]);
+
+        let config: MonitorConfig = serde_json::from_value(json!({
+            "type": "api",
+            "url": "https://example.com",
+            "http": {"proxy": "socks5://proxy:1080", "max_redirects": 50, "http_version": "http2"}
+        }))
+        .unwrap();
+        let fields: Vec<String> = config.validate().into_iter().map(|e| e.field).collect();
+        assert_eq!(fields, vec!["config.http.proxy", "config.http.max_redirects"]);
+
+        let config: MonitorConfig = serde_json::from_value(json!({"type": "certificate", "host": "example.com"})).unwrap();
+        assert_eq!(config.summary(), "example.com:443");
+        assert!(config.validate().is_empty());
+        let config: MonitorConfig =
+            serde_json::from_value(json!({"type": "certificate", "host": "", "warn_days": 0})).unwrap();
+        let fields: Vec<String> = config.validate().into_iter().map(|e| e.field).collect();
+        assert_eq!(fields, vec!["config.host", "config.warn_days"]);
+    }
+
+    #[test]
+    fn test_auth_credentials_must_be_secret_references() {
+        let api = |auth: Value| -> MonitorConfig {
+            serde_json::from_value(json!({"type": "api", "url": "https://example.com", "auth": auth})).unwrap()
+        };
+
+        let config = api(json!({"type": "bearer", "token": "${secret:API_TOKEN}"}));
+        assert!(config.validate().is_empty());
+        let config = api(json!({"type": "api_key", "value": "${secret:API_KEY}"}));
+        let MonitorConfig::Api(api_config) = &config else {
+            panic!("expected an API config");
+        };
+        let Some(ApiAuth::ApiKey { header, .. }) = &api_config.auth else {
+            panic!("expected an API key");
+        };
+        assert_eq!(header, "X-API-Key");
+        assert!(config.validate().is_empty());
+
+        let fields = |config: MonitorConfig| -> Vec<String> { config.validate().into_iter().map(|e| e.field).collect() };
+        assert_eq!(
+            fields(api(json!({"type": "basic", "username": "", "password": "hunter2"}))),
+            vec!["config.auth.username", "config.auth.password"]
+        );
+        assert_eq!(
+            fields(api(json!({
+                "type": "oauth2",
+                "token_url": "not a url",
+                "client_id": "monitor",
+                "client_secret": "raw-secret"
+            }))),
+            vec!["config.auth.token_url", "config.auth.client_secret"]
+        );
+
+        // Credentials are resolved along with the rest of the config
+        let config = api(json!({"type": "bearer", "token": "${secret:API_TOKEN}"}));
+        let resolved = config.resolve_strings(|value| Ok(value.replace("${secret:API_TOKEN}", "abc"))).unwrap();
+        let MonitorConfig::Api(api_config) = resolved else {
+            panic!("expected an API config");
+        };
+        assert_eq!(api_config.auth.unwrap(), ApiAuth::Bearer { token: "abc".to_string() });
+    }
+}
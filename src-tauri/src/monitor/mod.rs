@@ -1,9 +1,17 @@
+mod alerting;
+mod assertions;
+mod auth;
+mod config;
 mod process_monitor;
 mod api_monitor;
 mod port_monitor;
+mod certificate_monitor;
 mod monitor_manager;
+mod outcome;
+mod remediation;
 
 pub use process_monitor::{ProcessMonitor, ProcessTarget};
 pub use api_monitor::{ApiMonitor, ApiCheckConfig};
+pub use config::{prepare_monitor, MonitorConfig, MonitorError};
 pub use port_monitor::{PortMonitor, parse_port_target};
-pub use monitor_manager::{MonitorManager, MonitorStatus};
+pub use monitor_manager::{auto_start_enabled, MonitorManager, MonitorStatus, SETTING_AUTO_START_MONITORS};
@@ -6,31 +6,54 @@ use tokio::task::JoinHandle;
 use tokio::time;
 use tauri::{Emitter, Manager};
 
+use crate::commands::SecretStoreState;
 use crate::database::models::{LogEntry, Monitor};
 use crate::database::repository;
-use super::api_monitor::{ApiCheckConfig, ApiMonitor};
+use crate::database::Database;
+use crate::executor::preflight;
+use crate::executor::runner::{self, Trigger};
+use crate::notifications::{self, ChannelMessage};
+use crate::redaction;
+use super::api_monitor::{ApiCheckConfig, ApiMonitor, ClientPool};
+use super::assertions::CompiledAssertions;
+use super::auth::{ApiAuth, TokenCache};
+use super::config::{ApiConfig, CertificateConfig, MonitorConfig, PortConfig, ProcessConfig};
 use super::process_monitor::{ProcessMonitor, ProcessTarget};
-use super::port_monitor::{PortMonitor, parse_port_target};
+use super::port_monitor::PortMonitor;
+use super::certificate_monitor::CertificateMonitor;
+use super::alerting::{AlertEvent, AlertPolicy, AlertState};
+use super::outcome::{CheckErrorKind, CheckOutcome};
+use super::remediation::{RemediationDecision, RemediationPolicy, RemediationState};
+
+/// Settings key of the backend stored auto-start flag ("true"/"false", default on)
+pub const SETTING_AUTO_START_MONITORS: &str = "monitors.auto_start";
 
 /// Monitor status for event emission
 #[derive(Debug, Clone, serde::Serialize)]
 pub struct MonitorStatus {
     pub monitor_id: String,
-    pub status: String,        // "running", "stopped", "error", "checking"
+    pub status: String,        // "running", "degraded", "stopped", "error", "checking"
     pub last_check_time: i64,
     pub message: Option<String>,
+    pub outcome: Option<CheckOutcome>, // Result of the check that produced this status
 }
 
 /// Manager for all active monitors
 pub struct MonitorManager {
     active_monitors: Arc<Mutex<HashMap<String, JoinHandle<()>>>>,
+    db: Database,
+    oauth_tokens: Arc<TokenCache>, // OAuth2 tokens of API monitors, kept between checks
+    http_clients: Arc<ClientPool>, // HTTP clients of API monitors, so connections are kept alive between checks
 }
 
 impl MonitorManager {
-    /// Create a new MonitorManager
-    pub fn new() -> Self {
+    /// Create a new MonitorManager writing status and logs to the shared database
+    pub fn new(db: Database) -> Self {
         Self {
             active_monitors: Arc::new(Mutex::new(HashMap::new())),
+            db,
+            oauth_tokens: Arc::new(TokenCache::new()),
+            http_clients: Arc::new(ClientPool::new()),
         }
     }
 
@@ -39,7 +62,6 @@ impl MonitorManager {
         &self,
         monitor: Monitor,
         app_handle: tauri::AppHandle,
-        db_path: String,
     ) -> Result<(), String> {
         let monitor_id = monitor.id.clone();
 
@@ -52,11 +74,13 @@ impl MonitorManager {
         // Clone data for the async task
         let monitor_clone = monitor.clone();
         let app_handle_clone = app_handle.clone();
-        let db_path_clone = db_path.clone();
+        let db = self.db.clone();
+        let oauth_tokens = self.oauth_tokens.clone();
+        let http_clients = self.http_clients.clone();
 
         // Spawn background task based on monitor type
         let handle = tokio::spawn(async move {
-            Self::monitor_loop(monitor_clone, app_handle_clone, db_path_clone).await;
+            Self::monitor_loop(monitor_clone, app_handle_clone, db, oauth_tokens, http_clients).await;
         });
 
         // Store the task handle
@@ -70,12 +94,14 @@ impl MonitorManager {
                 status: "running".to_string(),
                 last_check_time: chrono::Utc::now().timestamp(),
                 message: Some(format!("Monitor '{}' started", monitor.name)),
+                outcome: None,
             },
         );
 
         // Log monitor start
         Self::log_monitor_event(
-            &db_path,
+            &app_handle,
+            &self.db,
             &monitor.id,
             "info",
             &format!("Monitor '{}' started", monitor.name),
@@ -85,6 +111,44 @@ impl MonitorManager {
         Ok(())
     }
 
+    /// Restart monitors that were active when the app last exited (called at app launch).
+    /// Monitors that are not restarted are reconciled to inactive / "stopped".
+    pub async fn restore_active_monitors(&self, app_handle: tauri::AppHandle) {
+        let loaded = self
+            .db
+            .read(|conn| {
+                repository::get_all_monitors(conn)
+                    .map(|monitors| (monitors, auto_start_enabled(conn)))
+                    .map_err(|e| e.to_string())
+            })
+            .await;
+        let (monitors, auto_start) = match loaded {
+            Ok(loaded) => loaded,
+            Err(e) => {
+                eprintln!("Failed to load monitors: {}", e);
+                return;
+            }
+        };
+
+        for monitor in monitors {
+            let monitor_id = monitor.id.clone();
+            if monitor.is_active && auto_start {
+                match self.start_monitor(monitor, app_handle.clone()).await {
+                    Ok(()) => continue,
+                    Err(e) => eprintln!("Failed to restore monitor {}: {}", monitor_id, e),
+                }
+                self.db.write_detached(move |conn| {
+                    repository::mark_monitor_stopped(conn, &monitor_id).map_err(|e| e.to_string())
+                });
+            } else if monitor.last_status.as_deref().is_some_and(|s| s != "stopped") {
+                // With auto-start off, active monitors stay active and start again once it is turned back on
+                self.db.write_detached(move |conn| {
+                    repository::reset_monitor_status(conn, &monitor_id).map_err(|e| e.to_string())
+                });
+            }
+        }
+    }
+
     /// Stop a monitor task
     pub async fn stop_monitor(&self, monitor_id: &str) -> Result<(), String> {
         let mut monitors = self.active_monitors.lock().await;
@@ -112,9 +176,30 @@ impl MonitorManager {
     }
 
     /// Main monitoring loop
-    async fn monitor_loop(monitor: Monitor, app_handle: tauri::AppHandle, db_path: String) {
+    async fn monitor_loop(
+        monitor: Monitor,
+        app_handle: tauri::AppHandle,
+        db: Database,
+        oauth_tokens: Arc<TokenCache>,
+        http_clients: Arc<ClientPool>,
+    ) {
         let interval = Duration::from_secs(monitor.check_interval as u64);
         let mut interval_timer = time::interval(interval);
+        let remediation_policy = RemediationPolicy {
+            threshold: monitor.remediation_threshold,
+            cooldown_secs: monitor.remediation_cooldown_secs,
+        };
+        let mut remediation = Self::load_remediation_state(&db, &monitor.id).await;
+        let alert_policy = AlertPolicy {
+            threshold: monitor.alert_threshold,
+            renotify_secs: monitor.alert_renotify_secs,
+        };
+        let mut alert_state = Self::load_alert_state(&db, &monitor.id).await;
+        // Patterns of the API assertions are parsed once for all checks
+        let assertions = match monitor.typed_config() {
+            Ok(MonitorConfig::Api(api)) => CompiledAssertions::compile(&api.assertions).map(Arc::new),
+            _ => Ok(Arc::default()),
+        };
 
         loop {
             interval_timer.tick().await;
@@ -127,91 +212,276 @@ impl MonitorManager {
                     status: "checking".to_string(),
                     last_check_time: chrono::Utc::now().timestamp(),
                     message: None,
+                    outcome: None,
                 },
             );
 
-            // Perform check based on monitor type
-            let check_result = match monitor.monitor_type.as_str() {
-                "process" => Self::check_process(&monitor).await,
-                "api" => Self::check_api(&monitor).await,
-                "port" => Self::check_port(&monitor).await,
-                _ => {
-                    eprintln!("Unknown monitor type: {}", monitor.monitor_type);
-                    continue;
-                }
+            // Perform check based on the monitor config, with ${secret:NAME} references resolved
+            let outcome = match Self::resolve_config(&app_handle, &monitor) {
+                Ok(MonitorConfig::Process(config)) => Self::check_process(&config).await,
+                Ok(MonitorConfig::Api(config)) => match &assertions {
+                    Ok(assertions) => {
+                        let expected_content = monitor.expected_result.clone();
+                        Self::check_api(&config, assertions.clone(), expected_content, &oauth_tokens, &http_clients).await
+                    }
+                    Err(e) => CheckOutcome::failed(CheckErrorKind::Config, e.clone()),
+                },
+                Ok(MonitorConfig::Port(config)) => Self::check_port(&config).await,
+                Ok(MonitorConfig::Certificate(config)) => Self::check_certificate(&config).await,
+                Err(e) => CheckOutcome::failed(CheckErrorKind::Config, e),
             };
 
             let timestamp = chrono::Utc::now().timestamp();
+            let healthy = outcome.healthy;
+            let degraded = outcome.is_degraded(monitor.degraded_threshold_ms);
+
+            let (status, log_level, message) = if degraded {
+                let message = format!(
+                    "Monitor '{}': Check passed but slow - {} ms exceeds {} ms",
+                    monitor.name,
+                    outcome.latency_ms.unwrap_or_default(),
+                    monitor.degraded_threshold_ms.unwrap_or_default()
+                );
+                ("degraded", "warning", message)
+            } else if healthy {
+                let message = match outcome.latency_ms {
+                    Some(latency) => format!("Monitor '{}': Check passed ({} ms)", monitor.name, latency),
+                    None => format!("Monitor '{}': Check passed", monitor.name),
+                };
+                ("running", "info", message)
+            } else {
+                // Request errors may echo resolved secrets (e.g. in URLs)
+                let reason = outcome.error.as_deref().unwrap_or("target not responding");
+                let message = format!("Monitor '{}': Check failed - {}", monitor.name, Self::redact(&app_handle, reason));
+                ("error", "error", message)
+            };
 
-            match check_result {
-                Ok(is_healthy) => {
-                    let status = if is_healthy { "running" } else { "error" };
-                    let message = if is_healthy {
-                        format!("Monitor '{}': Check passed", monitor.name)
-                    } else {
-                        format!("Monitor '{}': Check failed - target not responding", monitor.name)
-                    };
-
-                    // Update database status
-                    Self::update_db_status(&db_path, &monitor.id, status, timestamp).await;
-
-                    // Emit status update
-                    let _ = app_handle.emit(
-                        "monitor-status-update",
-                        MonitorStatus {
-                            monitor_id: monitor.id.clone(),
-                            status: status.to_string(),
-                            last_check_time: timestamp,
-                            message: Some(message.clone()),
-                        },
-                    );
-
-                    // Log the check result
-                    let log_level = if is_healthy { "info" } else { "error" };
-                    Self::log_monitor_event(&db_path, &monitor.id, log_level, &message).await;
-
-                    // If alert_on_failure is enabled and check failed, emit alert
-                    if !is_healthy && monitor.alert_on_failure {
-                        let _ = app_handle.emit(
-                            "monitor-alert",
-                            MonitorStatus {
-                                monitor_id: monitor.id.clone(),
-                                status: "alert".to_string(),
-                                last_check_time: timestamp,
-                                message: Some(format!("ALERT: {}", message)),
-                            },
-                        );
-                    }
+            // Update database status and check history
+            Self::update_db_status(&db, &monitor.id, status, timestamp).await;
+            let detail = (!healthy).then(|| message.clone());
+            Self::record_check(&db, &monitor.id, timestamp, healthy, outcome.latency_ms, detail);
+
+            // Emit status update
+            let _ = app_handle.emit(
+                "monitor-status-update",
+                MonitorStatus {
+                    monitor_id: monitor.id.clone(),
+                    status: status.to_string(),
+                    last_check_time: timestamp,
+                    message: Some(message.clone()),
+                    outcome: Some(outcome),
+                },
+            );
+
+            // Log the check result
+            Self::log_monitor_event(&app_handle, &db, &monitor.id, log_level, &message).await;
+
+            // Alert on state changes rather than on every failed check
+            let previous_state = alert_state.clone();
+            let event = alert_state.on_check(alert_policy, healthy, timestamp);
+            if alert_state != previous_state {
+                Self::save_alert_state(&db, &monitor.id, &alert_state, timestamp);
+            }
+            if monitor.alert_on_failure {
+                Self::notify(&app_handle, &db, &monitor, event, &message, timestamp).await;
+            }
+
+            // Run the remediation button once the target has been down long enough
+            if monitor.remediation_button_id.is_some() {
+                let previous_state = remediation.clone();
+                let decision = remediation.on_check(remediation_policy, healthy, timestamp);
+                if remediation != previous_state {
+                    Self::save_remediation_state(&db, &monitor.id, &remediation, timestamp);
                 }
-                Err(error_msg) => {
-                    // Update database status
-                    Self::update_db_status(&db_path, &monitor.id, "error", timestamp).await;
-
-                    // Emit error status
-                    let _ = app_handle.emit(
-                        "monitor-status-update",
-                        MonitorStatus {
-                            monitor_id: monitor.id.clone(),
-                            status: "error".to_string(),
-                            last_check_time: timestamp,
-                            message: Some(error_msg.clone()),
-                        },
-                    );
-
-                    // Log the error
-                    Self::log_monitor_event(&db_path, &monitor.id, "error", &error_msg).await;
-
-                    // Emit alert if enabled
-                    if monitor.alert_on_failure {
-                        let _ = app_handle.emit(
-                            "monitor-alert",
-                            MonitorStatus {
-                                monitor_id: monitor.id.clone(),
-                                status: "alert".to_string(),
-                                last_check_time: timestamp,
-                                message: Some(format!("ALERT: {}", error_msg)),
-                            },
+                Self::remediate(&app_handle, &db, &monitor, decision).await;
+            }
+        }
+    }
+
+    /// Emit (and for state changes, log) the notification for an alert event
+    async fn notify(
+        app_handle: &tauri::AppHandle,
+        db: &Database,
+        monitor: &Monitor,
+        event: AlertEvent,
+        check_message: &str,
+        timestamp: i64,
+    ) {
+        let (status, message) = match event {
+            AlertEvent::None => return,
+            AlertEvent::Alert => ("alert", format!("ALERT: {}", check_message)),
+            AlertEvent::Renotify => ("alert", format!("ALERT (still failing): {}", check_message)),
+            AlertEvent::Recovered => ("recovered", format!("Monitor '{}': recovered", monitor.name)),
+            AlertEvent::Flapping => (
+                "flapping",
+                format!("Monitor '{}': flapping, alerts are paused until it settles", monitor.name),
+            ),
+            AlertEvent::FlappingEnded => ("stable", format!("Monitor '{}': no longer flapping", monitor.name)),
+        };
+
+        // Alerts repeat the failed check that is already logged
+        let log_level = match status {
+            "recovered" | "stable" => Some("info"),
+            "flapping" => Some("warning"),
+            _ => None,
+        };
+        if let Some(level) = log_level {
+            Self::log_monitor_event(app_handle, db, &monitor.id, level, &message).await;
+        }
+
+        // Desktop notification, so alerts are seen while the window is minimized
+        let notification = match event {
+            AlertEvent::Alert => Some((
+                format!("🔴 {} 监控告警", monitor.name),
+                format!("检查失败：{}", check_message),
+            )),
+            AlertEvent::Renotify => Some((
+                format!("🔴 {} 监控告警", monitor.name),
+                format!("仍然失败：{}", check_message),
+            )),
+            AlertEvent::Recovered => Some((format!("✅ {} 已恢复", monitor.name), "检查恢复正常".to_string())),
+            AlertEvent::Flapping => Some((
+                format!("⚠️ {} 状态频繁变化", monitor.name),
+                "状态稳定前暂停告警".to_string(),
+            )),
+            _ => None,
+        };
+        if let Some((title, body)) = notification {
+            if !monitor.notifications_muted {
+                notifications::notify(app_handle, &title, &body).await;
+            }
+            // Team channels (Slack, webhooks...) get the same events
+            notifications::dispatch(
+                app_handle,
+                ChannelMessage {
+                    event: format!("monitor_{}", status),
+                    title,
+                    message: body,
+                    source: monitor.name.clone(),
+                    timestamp,
+                },
+            )
+            .await;
+        }
+
+        let _ = app_handle.emit(
+            "monitor-alert",
+            MonitorStatus {
+                monitor_id: monitor.id.clone(),
+                status: status.to_string(),
+                last_check_time: timestamp,
+                message: Some(message),
+                outcome: None,
+            },
+        );
+    }
+
+    /// Load the persisted alert state of a monitor (fresh state if none or unreadable)
+    async fn load_alert_state(db: &Database, monitor_id: &str) -> AlertState {
+        let id = monitor_id.to_string();
+        db.read(move |conn| {
+            repository::get_monitor_alert_state(conn, &id).map_err(|e| e.to_string())
+        })
+        .await
+        .ok()
+        .flatten()
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+    }
+
+    /// Persist the alert state of a monitor
+    fn save_alert_state(db: &Database, monitor_id: &str, state: &AlertState, timestamp: i64) {
+        let Ok(json) = serde_json::to_string(state) else { return };
+        let monitor_id = monitor_id.to_string();
+        db.write_detached(move |conn| {
+            repository::set_monitor_alert_state(conn, &monitor_id, &json, timestamp).map_err(|e| e.to_string())
+        });
+    }
+
+    /// Load the persisted remediation state of a monitor (fresh state if none or unreadable)
+    async fn load_remediation_state(db: &Database, monitor_id: &str) -> RemediationState {
+        let id = monitor_id.to_string();
+        db.read(move |conn| {
+            repository::get_monitor_remediation_state(conn, &id).map_err(|e| e.to_string())
+        })
+        .await
+        .ok()
+        .flatten()
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+    }
+
+    /// Persist the remediation state of a monitor
+    fn save_remediation_state(db: &Database, monitor_id: &str, state: &RemediationState, timestamp: i64) {
+        let Ok(json) = serde_json::to_string(state) else { return };
+        let monitor_id = monitor_id.to_string();
+        db.write_detached(move |conn| {
+            repository::set_monitor_remediation_state(conn, &monitor_id, &json, timestamp).map_err(|e| e.to_string())
+        });
+    }
+
+    /// Act on a remediation decision, logging against both the monitor and the button
+    async fn remediate(
+        app_handle: &tauri::AppHandle,
+        db: &Database,
+        monitor: &Monitor,
+        decision: RemediationDecision,
+    ) {
+        let Some(button_id) = monitor.remediation_button_id.as_deref() else { return };
+
+        match decision {
+            RemediationDecision::None => {}
+            RemediationDecision::CoolingDown(remaining) => {
+                let message = format!(
+                    "Monitor '{}': remediation skipped, cooling down for another {}s",
+                    monitor.name,
+                    remaining.as_secs()
+                );
+                Self::write_log(app_handle, db, &monitor.id, Some(button_id), None, "warning", &message).await;
+            }
+            RemediationDecision::Trigger => {
+                // The confirmation is read fresh, it may have been renewed since the loop started
+                let (monitor_id, id) = (monitor.id.clone(), button_id.to_string());
+                let button = db
+                    .read(move |conn| {
+                        let monitor = repository::get_monitor_by_id(conn, &monitor_id).map_err(|e| e.to_string())?;
+                        let button = repository::get_button_by_id(conn, &id).map_err(|e| e.to_string())?;
+                        Ok((button, monitor.remediation_confirmed_script))
+                    })
+                    .await;
+
+                let result = button.and_then(|(button, confirmed)| {
+                    preflight::check_unattended(&button, confirmed.as_deref())?;
+                    let button_name = button.name.clone();
+                    runner::start_execution(
+                        app_handle,
+                        button,
+                        &HashMap::new(),
+                        Trigger::remediation(&monitor.id, &monitor.name),
+                    )
+                    .map(|started| (button_name, started.execution_id))
+                });
+
+                match result {
+                    Ok((button_name, execution_id)) => {
+                        let message = format!(
+                            "Monitor '{}': running remediation button '{}' after {} consecutive failed checks",
+                            monitor.name, button_name, monitor.remediation_threshold
                         );
+                        Self::write_log(
+                            app_handle,
+                            db,
+                            &monitor.id,
+                            Some(button_id),
+                            Some(execution_id),
+                            "warning",
+                            &message,
+                        )
+                        .await;
+                    }
+                    Err(e) => {
+                        let message = format!("Monitor '{}': failed to run remediation button: {}", monitor.name, e);
+                        Self::write_log(app_handle, db, &monitor.id, Some(button_id), None, "error", &message).await;
                     }
                 }
             }
@@ -219,85 +489,220 @@ impl MonitorManager {
     }
 
     /// Check process monitor
-    async fn check_process(monitor: &Monitor) -> Result<bool, String> {
-        let target = ProcessTarget::from_string(&monitor.target);
+    async fn check_process(config: &ProcessConfig) -> CheckOutcome {
+        let target = match (config.pid, &config.name) {
+            (Some(pid), _) => ProcessTarget::Pid(pid),
+            (None, name) => ProcessTarget::Name(name.clone().unwrap_or_default()),
+        };
         let mut process_monitor = ProcessMonitor::new();
-        Ok(process_monitor.check_process(&target))
+        if process_monitor.check_process(&target) {
+            CheckOutcome::healthy(None)
+        } else {
+            CheckOutcome::failed(CheckErrorKind::NotRunning, format!("Process '{}' is not running", target.to_string()))
+        }
     }
 
-    /// Check API monitor
-    async fn check_api(monitor: &Monitor) -> Result<bool, String> {
-        // Parse target as JSON config or simple URL
-        let config = if let Ok(json) = serde_json::from_str::<serde_json::Value>(&monitor.target) {
-            // Parse from JSON
-            ApiCheckConfig {
-                url: json["url"].as_str().unwrap_or("").to_string(),
-                method: json["method"].as_str().unwrap_or("GET").to_string(),
-                headers: json["headers"].as_array().map(|arr| {
-                    arr.iter()
-                        .filter_map(|h| {
-                            Some((
-                                h["key"].as_str()?.to_string(),
-                                h["value"].as_str()?.to_string(),
-                            ))
-                        })
-                        .collect()
-                }),
-                body: json["body"].as_str().map(|s| s.to_string()),
-                expected_content: monitor.expected_result.clone(),
-                timeout_secs: 10,
-            }
-        } else {
-            // Simple URL string
-            ApiCheckConfig {
-                url: monitor.target.clone(),
-                method: "GET".to_string(),
-                headers: None,
-                body: None,
-                expected_content: monitor.expected_result.clone(),
-                timeout_secs: 10,
-            }
+    /// Check API monitor. A rejected OAuth2 token is dropped and the check retried once with a new one.
+    async fn check_api(
+        config: &ApiConfig,
+        assertions: Arc<CompiledAssertions>,
+        expected_content: Option<String>,
+        oauth_tokens: &TokenCache,
+        http_clients: &Arc<ClientPool>,
+    ) -> CheckOutcome {
+        let headers = config
+            .headers
+            .iter()
+            .map(|header| (header.key.clone(), header.value.clone()))
+            .collect::<Vec<_>>();
+        let mut check_config = ApiCheckConfig {
+            url: config.url.clone(),
+            method: config.method.clone(),
+            headers: (!headers.is_empty()).then_some(headers),
+            body: config.body.clone(),
+            expected_content,
+            assertions,
+            timeout_secs: config.timeout_secs,
+            auth: None,
+            tls: config.tls.clone(),
+            http: config.http.clone(),
+        };
+
+        let api_monitor = ApiMonitor::with_clients(http_clients.clone());
+        let Some(auth) = &config.auth else {
+            return api_monitor.check_api(&check_config).await;
+        };
+        let client = match http_clients.client(&check_config).await {
+            Ok(client) => client,
+            Err(e) => return CheckOutcome::failed(CheckErrorKind::Config, e),
         };
 
-        let api_monitor = ApiMonitor::new();
-        api_monitor.check_api(&config).await
+        let mut retried = !matches!(auth, ApiAuth::OAuth2 { .. });
+        loop {
+            check_config.auth = match oauth_tokens.request_auth(auth, &client).await {
+                Ok(request_auth) => Some(request_auth),
+                Err(e) => return CheckOutcome::failed(CheckErrorKind::Auth, e),
+            };
+            let outcome = api_monitor.check_api(&check_config).await;
+            if !retried && outcome.status_code == Some(401) {
+                oauth_tokens.invalidate(auth).await;
+                retried = true;
+                continue;
+            }
+            return outcome;
+        }
     }
 
     /// Check port monitor
-    async fn check_port(monitor: &Monitor) -> Result<bool, String> {
-        // Parse target as "host:port" or just "port"
-        let (host, port) = parse_port_target(&monitor.target)
-            .map_err(|e| format!("Invalid port target: {}", e))?;
+    async fn check_port(config: &PortConfig) -> CheckOutcome {
+        let (host, port) = (config.host.clone(), config.port);
+
+        // The connect blocks, keep it off the async workers
+        tokio::task::spawn_blocking(move || PortMonitor::new().check_port(&host, port))
+            .await
+            .unwrap_or_else(|e| CheckOutcome::failed(CheckErrorKind::Connection, format!("Port check failed: {}", e)))
+    }
+
+    /// Check certificate monitor
+    async fn check_certificate(config: &CertificateConfig) -> CheckOutcome {
+        let (host, port, warn_days) = (config.host.clone(), config.port, config.warn_days);
 
-        let port_monitor = PortMonitor::new();
-        Ok(port_monitor.check_port(&host, port))
+        // The handshake blocks, keep it off the async workers
+        tokio::task::spawn_blocking(move || CertificateMonitor::new().check_certificate(&host, port, warn_days))
+            .await
+            .unwrap_or_else(|e| CheckOutcome::failed(CheckErrorKind::Connection, format!("Certificate check failed: {}", e)))
     }
 
     /// Update monitor status in database
-    async fn update_db_status(db_path: &str, monitor_id: &str, status: &str, timestamp: i64) {
-        if let Ok(conn) = rusqlite::Connection::open(db_path) {
-            let _ = repository::update_monitor_status(&conn, monitor_id, status, timestamp);
+    async fn update_db_status(db: &Database, monitor_id: &str, status: &str, timestamp: i64) {
+        let monitor_id = monitor_id.to_string();
+        let status = status.to_string();
+        db.write_detached(move |conn| {
+            repository::update_monitor_status(conn, &monitor_id, &status, timestamp).map_err(|e| e.to_string())
+        });
+    }
+
+    /// Append a check result to the monitor's history
+    fn record_check(
+        db: &Database,
+        monitor_id: &str,
+        timestamp: i64,
+        healthy: bool,
+        latency_ms: Option<u64>,
+        detail: Option<String>,
+    ) {
+        let monitor_id = monitor_id.to_string();
+        db.write_detached(move |conn| {
+            let latency_ms = latency_ms.map(|ms| ms as i64);
+            repository::create_monitor_check(conn, &monitor_id, timestamp, healthy, latency_ms, detail.as_deref())
+                .map(|_| ())
+                .map_err(|e| e.to_string())
+        });
+    }
+
+    /// Validate the monitor config and resolve the secret references in it
+    fn resolve_config(app_handle: &tauri::AppHandle, monitor: &Monitor) -> Result<MonitorConfig, String> {
+        let config = monitor.typed_config().map_err(|e| format!("Invalid monitor config: {}", e))?;
+        let errors = config.validate();
+        if !errors.is_empty() {
+            let details: Vec<String> = errors.iter().map(|e| format!("{}: {}", e.field, e.message)).collect();
+            return Err(format!("Invalid monitor config: {}", details.join("; ")));
+        }
+        match app_handle.try_state::<SecretStoreState>() {
+            Some(secrets) => config.resolve_strings(|text| secrets.0.resolve(text)),
+            None => Ok(config),
         }
     }
 
+    /// Mask secret values and sensitive patterns in a message
+    fn redact<R: tauri::Runtime>(app_handle: &tauri::AppHandle<R>, message: &str) -> String {
+        redaction::redact_log_message(app_handle, message)
+    }
+
     /// Log monitor event to database
-    async fn log_monitor_event(db_path: &str, monitor_id: &str, level: &str, message: &str) {
-        if let Ok(conn) = rusqlite::Connection::open(db_path) {
-            let log = LogEntry {
-                id: uuid::Uuid::new_v4().to_string(),
-                button_id: None,
-                monitor_id: Some(monitor_id.to_string()),
-                level: level.to_string(),
-                message: message.to_string(),
-                timestamp: chrono::Utc::now().timestamp(),
-            };
-            let _ = repository::create_log(&conn, &log);
-        }
+    async fn log_monitor_event<R: tauri::Runtime>(
+        app_handle: &tauri::AppHandle<R>,
+        db: &Database,
+        monitor_id: &str,
+        level: &str,
+        message: &str,
+    ) {
+        Self::write_log(app_handle, db, monitor_id, None, None, level, message).await;
+    }
+
+    /// Write a monitor log entry, optionally linked to a button run
+    async fn write_log<R: tauri::Runtime>(
+        app_handle: &tauri::AppHandle<R>,
+        db: &Database,
+        monitor_id: &str,
+        button_id: Option<&str>,
+        execution_id: Option<String>,
+        level: &str,
+        message: &str,
+    ) {
+        db.insert_log(LogEntry {
+            id: uuid::Uuid::new_v4().to_string(),
+            button_id: button_id.map(|id| id.to_string()),
+            monitor_id: Some(monitor_id.to_string()),
+            level: level.to_string(),
+            message: Self::redact(app_handle, message),
+            timestamp: chrono::Utc::now().timestamp(),
+            execution_id,
+        });
     }
 }
 
-impl Default for MonitorManager {
-    fn default() -> Self {
-        Self::new()
+/// Whether active monitors should be restarted at launch
+pub fn auto_start_enabled(conn: &rusqlite::Connection) -> bool {
+    repository::get_setting(conn, SETTING_AUTO_START_MONITORS)
+        .ok()
+        .flatten()
+        .is_none_or(|value| value != "false")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::TestDb;
+
+    #[test]
+    fn test_monitor_logs_readable_through_command() {
+        let db = TestDb::open();
+
+        let monitor: Monitor = serde_json::from_value(serde_json::json!({
+            "id": "monitor-1",
+            "name": "api",
+            "icon": null,
+            "monitor_type": "api",
+            "target": "http://localhost:8080/health",
+            "check_interval": 60,
+            "expected_result": null,
+            "alert_on_failure": false,
+            "is_active": true,
+            "last_check_time": null,
+            "last_status": null,
+            "folder_id": null,
+            "position": 0,
+            "created_at": 0
+        }))
+        .unwrap();
+        repository::create_monitor(&db.conn().unwrap(), &monitor).unwrap();
+
+        // Monitor tasks write through the same pool the commands read from
+        let app = tauri::test::mock_app();
+        app.manage(db.clone());
+        let logs = tauri::async_runtime::block_on(async {
+            MonitorManager::log_monitor_event(app.handle(), &db, &monitor.id, "error", "Monitor 'api': Check failed")
+                .await;
+            db.flush().await.unwrap();
+            crate::commands::get_monitor_logs(app.state(), monitor.id.clone()).await
+        })
+        .unwrap();
+
+        assert_eq!(logs.len(), 1);
+        assert_eq!(logs[0].level, "error");
+        assert_eq!(logs[0].message, "Monitor 'api': Check failed");
+
+        drop(app);
     }
 }
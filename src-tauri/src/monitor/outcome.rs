@@ -0,0 +1,128 @@
+use serde::Serialize;
+use std::error::Error;
+use std::io;
+use std::time::Duration;
+
+/// Why a check failed
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckErrorKind {
+    Config,     // The monitor target could not be used (bad URL, port, secret...)
+    Dns,        // The host name did not resolve
+    Connection, // Connecting failed or the connection was refused
+    Auth,       // No credentials could be obtained, e.g. the OAuth2 token request failed
+    Certificate, // The TLS certificate was rejected, expired or expires soon
+    Timeout,
+    Status,     // The response status was not 2xx
+    Content,    // The expected content was missing from the response
+    Assertion,  // One or more response assertions failed
+    Body,       // The response body could not be read
+    NotRunning, // The process was not found
+}
+
+/// Structured result of one monitor check
+#[derive(Debug, Clone, Serialize)]
+pub struct CheckOutcome {
+    pub healthy: bool,
+    pub latency_ms: Option<u64>, // None when the target was never reached
+    pub status_code: Option<u16>,
+    pub bytes: Option<u64>,      // Size of the response body
+    pub error_kind: Option<CheckErrorKind>,
+    pub error: Option<String>,
+    pub failed_assertions: Vec<String>, // One message per failed assertion
+    pub certificate_expires_at: Option<i64>, // Expiry of the certificate checked by a certificate monitor
+}
+
+impl CheckOutcome {
+    /// A passed check
+    pub fn healthy(latency: Option<Duration>) -> Self {
+        Self {
+            healthy: true,
+            latency_ms: latency.map(duration_ms),
+            status_code: None,
+            bytes: None,
+            error_kind: None,
+            error: None,
+            failed_assertions: Vec::new(),
+            certificate_expires_at: None,
+        }
+    }
+
+    /// A failed check
+    pub fn failed(kind: CheckErrorKind, error: impl Into<String>) -> Self {
+        Self {
+            healthy: false,
+            latency_ms: None,
+            status_code: None,
+            bytes: None,
+            error_kind: Some(kind),
+            error: Some(error.into()),
+            failed_assertions: Vec::new(),
+            certificate_expires_at: None,
+        }
+    }
+
+    pub fn with_latency(mut self, latency: Duration) -> Self {
+        self.latency_ms = Some(duration_ms(latency));
+        self
+    }
+
+    pub fn with_response(mut self, status_code: u16, bytes: Option<u64>) -> Self {
+        self.status_code = Some(status_code);
+        self.bytes = bytes;
+        self
+    }
+
+    pub fn with_failed_assertions(mut self, failures: Vec<String>) -> Self {
+        self.failed_assertions = failures;
+        self
+    }
+
+    pub fn with_certificate_expiry(mut self, expires_at: i64) -> Self {
+        self.certificate_expires_at = Some(expires_at);
+        self
+    }
+
+    /// Healthy, but slower than the threshold
+    pub fn is_degraded(&self, threshold_ms: Option<u64>) -> bool {
+        self.healthy
+            && matches!((self.latency_ms, threshold_ms), (Some(latency), Some(threshold)) if latency > threshold)
+    }
+}
+
+/// Kind of a failed TLS handshake. An I/O error in the source chain means the connection broke off,
+/// anything else is the certificate or the TLS negotiation being rejected.
+pub fn tls_failure_kind(error: &(dyn Error + 'static)) -> CheckErrorKind {
+    let mut source = Some(error);
+    while let Some(cause) = source {
+        if let Some(io) = cause.downcast_ref::<io::Error>() {
+            return match io.kind() {
+                io::ErrorKind::TimedOut | io::ErrorKind::WouldBlock => CheckErrorKind::Timeout,
+                _ => CheckErrorKind::Connection,
+            };
+        }
+        source = cause.source();
+    }
+    CheckErrorKind::Certificate
+}
+
+fn duration_ms(duration: Duration) -> u64 {
+    duration.as_millis().try_into().unwrap_or(u64::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_degraded_only_when_healthy_and_slow() {
+        let slow = CheckOutcome::healthy(Some(Duration::from_millis(900)));
+        assert!(slow.is_degraded(Some(500)));
+        assert!(!slow.is_degraded(Some(900)));
+        assert!(!slow.is_degraded(None));
+
+        let failed = CheckOutcome::failed(CheckErrorKind::Status, "HTTP 503").with_latency(Duration::from_secs(2));
+        assert!(!failed.is_degraded(Some(500)));
+        assert!(!CheckOutcome::healthy(None).is_degraded(Some(500)));
+    }
+}
@@ -1,5 +1,8 @@
+use std::io::ErrorKind;
 use std::net::{TcpStream, ToSocketAddrs};
-use std::time::Duration;
+use std::time::{Duration, Instant};
+
+use super::outcome::{CheckErrorKind, CheckOutcome};
 
 /// Port monitor for checking if a port is open/listening
 pub struct PortMonitor;
@@ -10,38 +13,46 @@ impl PortMonitor {
         Self
     }
 
-    /// Check if a port is open on a given host
-    /// Returns true if the port is open (accepting connections), false otherwise
-    pub fn check_port(&self, host: &str, port: u16) -> bool {
+    /// Check if a port is open on a given host (accepting connections)
+    pub fn check_port(&self, host: &str, port: u16) -> CheckOutcome {
         self.check_port_with_timeout(host, port, Duration::from_secs(3))
     }
 
-    /// Check if a port is open with a custom timeout
-    pub fn check_port_with_timeout(&self, host: &str, port: u16, timeout: Duration) -> bool {
+    /// Check if a port is open with a custom timeout; the latency is the time to connect
+    pub fn check_port_with_timeout(&self, host: &str, port: u16, timeout: Duration) -> CheckOutcome {
         let address = format!("{}:{}", host, port);
 
         // Try to resolve the address
         let socket_addrs = match address.to_socket_addrs() {
             Ok(addrs) => addrs,
-            Err(_) => return false,
+            Err(e) => return CheckOutcome::failed(CheckErrorKind::Dns, format!("Failed to resolve {}: {}", host, e)),
         };
 
-        // Try to connect to any of the resolved addresses
+        // Try to connect to any of the resolved addresses, reporting the last failure
+        let mut outcome = CheckOutcome::failed(CheckErrorKind::Dns, format!("No address found for {}", host));
         for addr in socket_addrs {
+            let started = Instant::now();
             match TcpStream::connect_timeout(&addr, timeout) {
-                Ok(_) => return true,
-                Err(_) => continue,
+                Ok(_) => return CheckOutcome::healthy(Some(started.elapsed())),
+                Err(e) => {
+                    let kind = if e.kind() == ErrorKind::TimedOut {
+                        CheckErrorKind::Timeout
+                    } else {
+                        CheckErrorKind::Connection
+                    };
+                    outcome = CheckOutcome::failed(kind, format!("Failed to connect to {}: {}", addr, e));
+                }
             }
         }
 
-        false
+        outcome
     }
 
     /// Get detailed information about a port check (for logging/debugging)
     pub fn get_port_info(&self, host: &str, port: u16) -> String {
-        let is_open = self.check_port(host, port);
+        let outcome = self.check_port(host, port);
 
-        if is_open {
+        if outcome.healthy {
             format!("Port {}:{} is OPEN (accepting connections)", host, port)
         } else {
             format!("Port {}:{} is CLOSED or unreachable", host, port)
@@ -74,6 +85,7 @@ pub fn parse_port_target(target: &str) -> Result<(String, u16), String> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::net::TcpListener;
 
     #[test]
     fn test_parse_port_target() {
@@ -101,10 +113,16 @@ mod tests {
     fn test_port_monitor() {
         let monitor = PortMonitor::new();
 
-        // Test checking a port (this will likely be closed)
-        let is_open = monitor.check_port("127.0.0.1", 9999);
-        // We can't assert the result since we don't know if the port is open
-        // Just make sure it doesn't panic
-        println!("Port 9999 open: {}", is_open);
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let open = monitor.check_port("127.0.0.1", port);
+        assert!(open.healthy);
+        assert!(open.latency_ms.is_some());
+
+        // Nothing listens on the port once the listener is gone
+        drop(listener);
+        let closed = monitor.check_port("127.0.0.1", port);
+        assert!(!closed.healthy);
+        assert_eq!(closed.error_kind, Some(CheckErrorKind::Connection));
     }
 }
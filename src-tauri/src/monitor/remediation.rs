@@ -0,0 +1,111 @@
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// What the monitor loop should do after a check
+#[derive(Debug, PartialEq)]
+pub enum RemediationDecision {
+    None,
+    Trigger,                // Run the remediation button now
+    CoolingDown(Duration),  // Threshold reached but the last run was too recent (logged once per cooldown)
+}
+
+/// When a monitor remediates, taken from its settings
+#[derive(Debug, Clone, Copy)]
+pub struct RemediationPolicy {
+    pub threshold: u32,     // Consecutive failed checks before remediating
+    pub cooldown_secs: u64, // Minimum time between two remediation runs
+}
+
+/// Consecutive failures of a monitor and when remediation last ran,
+/// persisted so a restart neither resets the count nor cuts the cooldown short
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RemediationState {
+    pub consecutive_failures: u32,
+    pub last_triggered_at: Option<i64>,
+    pub cooldown_reported: bool,
+}
+
+impl RemediationState {
+    /// Record a check result at `now` (unix seconds) and decide whether to remediate
+    pub fn on_check(&mut self, policy: RemediationPolicy, healthy: bool, now: i64) -> RemediationDecision {
+        if healthy {
+            self.consecutive_failures = 0;
+            return RemediationDecision::None;
+        }
+
+        self.consecutive_failures = self.consecutive_failures.saturating_add(1);
+        if self.consecutive_failures < policy.threshold.max(1) {
+            return RemediationDecision::None;
+        }
+
+        if let Some(last) = self.last_triggered_at {
+            let elapsed = u64::try_from(now - last).unwrap_or_default();
+            if elapsed < policy.cooldown_secs {
+                if self.cooldown_reported {
+                    return RemediationDecision::None;
+                }
+                self.cooldown_reported = true;
+                return RemediationDecision::CoolingDown(Duration::from_secs(policy.cooldown_secs - elapsed));
+            }
+        }
+
+        self.last_triggered_at = Some(now);
+        self.cooldown_reported = false;
+        RemediationDecision::Trigger
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const POLICY: RemediationPolicy = RemediationPolicy {
+        threshold: 3,
+        cooldown_secs: 60,
+    };
+
+    #[test]
+    fn test_triggers_after_consecutive_failures() {
+        let mut state = RemediationState::default();
+        let now = 1_000;
+
+        assert_eq!(state.on_check(POLICY, false, now), RemediationDecision::None);
+        assert_eq!(state.on_check(POLICY, false, now), RemediationDecision::None);
+        // A healthy check resets the count
+        assert_eq!(state.on_check(POLICY, true, now), RemediationDecision::None);
+        assert_eq!(state.on_check(POLICY, false, now), RemediationDecision::None);
+        assert_eq!(state.on_check(POLICY, false, now), RemediationDecision::None);
+        assert_eq!(state.on_check(POLICY, false, now), RemediationDecision::Trigger);
+    }
+
+    #[test]
+    fn test_cooldown_prevents_restart_loops() {
+        let policy = RemediationPolicy { threshold: 1, ..POLICY };
+        let mut state = RemediationState::default();
+        let start = 1_000;
+
+        assert_eq!(state.on_check(policy, false, start), RemediationDecision::Trigger);
+        assert_eq!(
+            state.on_check(policy, false, start + 10),
+            RemediationDecision::CoolingDown(Duration::from_secs(50))
+        );
+        // Only reported once per cooldown window
+        assert_eq!(state.on_check(policy, false, start + 20), RemediationDecision::None);
+        assert_eq!(state.on_check(policy, false, start + 61), RemediationDecision::Trigger);
+    }
+
+    #[test]
+    fn test_state_survives_a_restart() {
+        let policy = RemediationPolicy { threshold: 2, ..POLICY };
+        let mut state = RemediationState::default();
+        assert_eq!(state.on_check(policy, false, 1_000), RemediationDecision::None);
+        assert_eq!(state.on_check(policy, false, 1_010), RemediationDecision::Trigger);
+
+        let mut restored: RemediationState = serde_json::from_str(&serde_json::to_string(&state).unwrap()).unwrap();
+        assert_eq!(restored, state);
+        assert_eq!(
+            restored.on_check(policy, false, 1_030),
+            RemediationDecision::CoolingDown(Duration::from_secs(40))
+        );
+    }
+}
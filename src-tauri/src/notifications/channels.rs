@@ -0,0 +1,289 @@
+use reqwest::Client;
+use serde::Serialize;
+use serde_json::Value;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use crate::database::models::NotificationChannel;
+
+/// Supported channel types
+pub const CHANNEL_TYPES: [&str; 5] = ["slack", "mattermost", "feishu", "dingtalk", "webhook"];
+
+/// Deliveries are attempted this many times before giving up
+pub const MAX_ATTEMPTS: u32 = 3;
+/// Delay before the first retry, doubled for each further retry
+const RETRY_DELAY: Duration = Duration::from_secs(1);
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Event posted to the channels
+#[derive(Debug, Clone, Serialize)]
+pub struct ChannelMessage {
+    pub event: String,   // "monitor_alert", "monitor_recovered", "monitor_flapping", "execution_failed", "test"
+    pub title: String,
+    pub message: String,
+    pub source: String,  // Name of the monitor or button
+    pub timestamp: i64,
+}
+
+/// Payload sent when a channel has no template of its own.
+/// Placeholders: {{title}}, {{message}}, {{text}} (title and message), {{source}}, {{event}}, {{timestamp}}
+pub fn default_template(channel_type: &str) -> &'static str {
+    match channel_type {
+        "slack" | "mattermost" => r#"{"text": "{{text}}"}"#,
+        "feishu" => r#"{"msg_type": "text", "content": {"text": "{{text}}"}}"#,
+        "dingtalk" => r#"{"msgtype": "text", "text": {"content": "{{text}}"}}"#,
+        _ => {
+            r#"{"event": "{{event}}", "title": "{{title}}", "message": "{{message}}", "source": "{{source}}", "timestamp": {{timestamp}}}"#
+        }
+    }
+}
+
+/// Fill the placeholders of a template and parse the result as JSON.
+/// Values are JSON-escaped, so string placeholders belong inside quotes.
+pub fn render(template: &str, message: &ChannelMessage) -> Result<Value, String> {
+    let escape = |value: &str| {
+        let quoted = Value::String(value.to_string()).to_string();
+        quoted[1..quoted.len() - 1].to_string()
+    };
+
+    // One pass over the template, so placeholders inside the values are left alone
+    let mut rendered = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        rendered.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let value = after.find("}}").and_then(|end| {
+            let value = match &after[..end] {
+                "title" => escape(&message.title),
+                "message" => escape(&message.message),
+                "text" => escape(&format!("{}\n{}", message.title, message.message)),
+                "source" => escape(&message.source),
+                "event" => escape(&message.event),
+                "timestamp" => message.timestamp.to_string(),
+                _ => return None,
+            };
+            Some((value, end))
+        });
+        match value {
+            Some((value, end)) => {
+                rendered.push_str(&value);
+                rest = &after[end + 2..];
+            }
+            None => {
+                rendered.push_str("{{");
+                rest = after;
+            }
+        }
+    }
+    rendered.push_str(rest);
+
+    serde_json::from_str(&rendered).map_err(|e| format!("Template does not produce valid JSON: {}", e))
+}
+
+/// Check a channel before it is saved
+pub fn validate_channel(channel: &NotificationChannel) -> Result<(), String> {
+    if channel.name.trim().is_empty() {
+        return Err("Channel name cannot be empty".to_string());
+    }
+    if !CHANNEL_TYPES.contains(&channel.channel_type.as_str()) {
+        return Err(format!(
+            "Unknown channel type '{}', expected one of: {}",
+            channel.channel_type,
+            CHANNEL_TYPES.join(", ")
+        ));
+    }
+    if !(channel.url.starts_with("http://") || channel.url.starts_with("https://")) {
+        return Err("Channel URL must start with http:// or https://".to_string());
+    }
+
+    let sample = ChannelMessage {
+        event: "test".to_string(),
+        title: "Test".to_string(),
+        message: "Test message".to_string(),
+        source: channel.name.clone(),
+        timestamp: 0,
+    };
+    render(template_of(channel), &sample).map(|_| ())
+}
+
+fn template_of(channel: &NotificationChannel) -> &str {
+    channel
+        .template
+        .as_deref()
+        .filter(|template| !template.trim().is_empty())
+        .unwrap_or_else(|| default_template(&channel.channel_type))
+}
+
+fn client() -> &'static Client {
+    static CLIENT: OnceLock<Client> = OnceLock::new();
+    CLIENT.get_or_init(|| {
+        Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .unwrap_or_else(|_| Client::new())
+    })
+}
+
+/// Failed delivery attempt
+struct DeliveryError {
+    message: String,
+    retryable: bool, // Network errors, 429 and 5xx may succeed on a later attempt
+}
+
+/// Post a message to a channel, retrying transient failures.
+/// The channel URL must already have its secret references resolved.
+/// Returns the number of attempts it took.
+pub async fn send(channel: &NotificationChannel, message: &ChannelMessage) -> Result<u32, String> {
+    send_with_delay(channel, message, RETRY_DELAY).await
+}
+
+async fn send_with_delay(
+    channel: &NotificationChannel,
+    message: &ChannelMessage,
+    retry_delay: Duration,
+) -> Result<u32, String> {
+    let payload = render(template_of(channel), message)?;
+
+    let mut delay = retry_delay;
+    let mut attempt = 1;
+    loop {
+        match send_once(channel, &payload).await {
+            Ok(()) => return Ok(attempt),
+            Err(e) if e.retryable && attempt < MAX_ATTEMPTS => {
+                tokio::time::sleep(delay).await;
+                delay *= 2;
+                attempt += 1;
+            }
+            Err(e) => return Err(format!("{} (attempt {}/{})", e.message, attempt, MAX_ATTEMPTS)),
+        }
+    }
+}
+
+async fn send_once(channel: &NotificationChannel, payload: &Value) -> Result<(), DeliveryError> {
+    let response = client()
+        .post(&channel.url)
+        .json(payload)
+        .send()
+        .await
+        .map_err(|e| DeliveryError {
+            message: format!("Request failed: {}", e),
+            retryable: true,
+        })?;
+
+    let status = response.status();
+    if !status.is_success() {
+        return Err(DeliveryError {
+            message: format!("HTTP {}", status.as_u16()),
+            retryable: status.is_server_error() || status.as_u16() == 429,
+        });
+    }
+
+    // Feishu and DingTalk answer 200 and report errors in the body
+    let error_field = match channel.channel_type.as_str() {
+        "feishu" => "code",
+        "dingtalk" => "errcode",
+        _ => return Ok(()),
+    };
+    let body: Value = response.json().await.unwrap_or(Value::Null);
+    match body.get(error_field).and_then(Value::as_i64) {
+        Some(code) if code != 0 => Err(DeliveryError {
+            message: format!(
+                "Channel rejected the message ({} {}): {}",
+                error_field,
+                code,
+                body.get("msg").or_else(|| body.get("errmsg")).and_then(Value::as_str).unwrap_or("unknown error")
+            ),
+            retryable: false,
+        }),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_http::MockServer;
+
+    fn message() -> ChannelMessage {
+        ChannelMessage {
+            event: "monitor_alert".to_string(),
+            title: "🔴 API 监控告警".to_string(),
+            message: "检查失败：\"api\" down".to_string(),
+            source: "API".to_string(),
+            timestamp: 1_700_000_000,
+        }
+    }
+
+    fn channel(channel_type: &str, url: String, template: Option<&str>) -> NotificationChannel {
+        NotificationChannel {
+            id: "c1".to_string(),
+            name: "team".to_string(),
+            channel_type: channel_type.to_string(),
+            url,
+            template: template.map(str::to_string),
+            enabled: true,
+            created_at: 0,
+            updated_at: 0,
+        }
+    }
+
+    #[test]
+    fn test_render_escapes_values() {
+        let payload = render(default_template("dingtalk"), &message()).unwrap();
+        assert_eq!(payload["msgtype"], "text");
+        assert_eq!(payload["text"]["content"], "🔴 API 监控告警\n检查失败：\"api\" down");
+
+        let payload = render(default_template("webhook"), &message()).unwrap();
+        assert_eq!(payload["timestamp"], 1_700_000_000);
+        assert_eq!(payload["source"], "API");
+
+        assert!(render("{\"text\": {{title}}}", &message()).is_err());
+
+        // Placeholders in the values and unknown ones are kept as written
+        let mut quoted = message();
+        quoted.title = "{{source}} {{timestamp}}".to_string();
+        let payload = render(r#"{"title": "{{title}}", "other": "{{other}} {{"}"#, &quoted).unwrap();
+        assert_eq!(payload["title"], "{{source}} {{timestamp}}");
+        assert_eq!(payload["other"], "{{other}} {{");
+        assert!(validate_channel(&channel("slack", "ftp://example.com".to_string(), None)).is_err());
+        assert!(validate_channel(&channel("teams", "https://example.com".to_string(), None)).is_err());
+    }
+
+    #[test]
+    fn test_send_retries_server_errors() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let server = MockServer::serve([
+            "HTTP/1.1 502 Bad Gateway\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+            "HTTP/1.1 200 OK\r\nContent-Length: 2\r\nConnection: close\r\n\r\nok",
+        ]);
+        let slack = channel("slack", server.url("/hook"), Some(r#"{"text": "[{{source}}] {{message}}"}"#));
+
+        let attempts = runtime
+            .block_on(send_with_delay(&slack, &message(), Duration::from_millis(10)))
+            .unwrap();
+        assert_eq!(attempts, 2);
+        let body: Value = serde_json::from_str(&server.next_request().body).unwrap();
+        assert_eq!(body["text"], "[API] 检查失败：\"api\" down");
+        assert_eq!(server.next_request().body, body.to_string());
+    }
+
+    #[test]
+    fn test_send_reports_rejected_messages() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+
+        // Client errors are not retried
+        let server = MockServer::serve(["HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"]);
+        let error = runtime
+            .block_on(send_with_delay(&channel("webhook", server.url("/hook"), None), &message(), Duration::from_millis(10)))
+            .unwrap_err();
+        assert!(error.contains("HTTP 404 (attempt 1/3)"), "{}", error);
+
+        let server = MockServer::serve([
+            "HTTP/1.1 200 OK\r\nContent-Length: 42\r\nConnection: close\r\n\r\n{\"errcode\":310000,\"errmsg\":\"keywords not\"}",
+        ]);
+        let error = runtime
+            .block_on(send_with_delay(&channel("dingtalk", server.url("/hook"), None), &message(), Duration::from_millis(10)))
+            .unwrap_err();
+        assert!(error.contains("errcode 310000"), "{}", error);
+    }
+}
@@ -0,0 +1,106 @@
+use rusqlite::Connection;
+use std::time::Duration;
+use tauri::Manager;
+use tauri_plugin_notification::NotificationExt;
+
+use crate::commands::SecretStoreState;
+use crate::database::models::NotificationChannel;
+use crate::database::repository;
+use crate::database::Database;
+use crate::redaction;
+
+pub mod channels;
+
+pub use channels::ChannelMessage;
+
+/// Settings key of the desktop notification switch ("true"/"false", default on)
+pub const SETTING_NOTIFICATIONS_ENABLED: &str = "notifications.enabled";
+
+/// Runs that finish faster than this do not produce a notification
+pub const LONG_RUN_THRESHOLD: Duration = Duration::from_secs(30);
+
+/// Whether desktop notifications are shown
+pub fn notifications_enabled(conn: &Connection) -> bool {
+    repository::get_setting(conn, SETTING_NOTIFICATIONS_ENABLED)
+        .ok()
+        .flatten()
+        .is_none_or(|value| value != "false")
+}
+
+/// Show a desktop notification unless they are disabled in settings.
+/// Failures are only reported on stderr; the in-app events are sent regardless.
+pub async fn notify<R: tauri::Runtime>(app_handle: &tauri::AppHandle<R>, title: &str, body: &str) {
+    let Some(db) = app_handle.try_state::<Database>() else { return };
+    let enabled = db
+        .read(|conn| Ok(notifications_enabled(conn)))
+        .await
+        .unwrap_or(true);
+    if !enabled {
+        return;
+    }
+
+    if let Err(e) = app_handle.notification().builder().title(title).body(body).show() {
+        eprintln!("Failed to show notification: {}", e);
+    }
+}
+
+/// Post a message to every enabled notification channel.
+/// Deliveries run in the background so retries never hold up the caller; failures go to stderr.
+pub async fn dispatch<R: tauri::Runtime>(app_handle: &tauri::AppHandle<R>, message: ChannelMessage) {
+    let Some(db) = app_handle.try_state::<Database>() else { return };
+    let channels = match db
+        .read(|conn| {
+            repository::get_enabled_notification_channels(conn)
+                .map_err(|e| format!("Failed to get notification channels: {}", e))
+        })
+        .await
+    {
+        Ok(channels) => channels,
+        Err(e) => {
+            eprintln!("{}", e);
+            return;
+        }
+    };
+
+    for channel in channels {
+        let app_handle = app_handle.clone();
+        let message = message.clone();
+        tauri::async_runtime::spawn(async move {
+            if let Err(e) = send_to_channel(&app_handle, &channel, &message).await {
+                eprintln!("Failed to notify channel '{}': {}", channel.name, e);
+            }
+        });
+    }
+}
+
+/// Resolve secret references in the channel URL and post the message.
+/// Returns the number of attempts; errors are redacted since they may echo the URL.
+pub async fn send_to_channel<R: tauri::Runtime>(
+    app_handle: &tauri::AppHandle<R>,
+    channel: &NotificationChannel,
+    message: &ChannelMessage,
+) -> Result<u32, String> {
+    let mut resolved = channel.clone();
+    if let Some(secrets) = app_handle.try_state::<SecretStoreState>() {
+        resolved.url = secrets.0.resolve(&channel.url)?;
+    }
+    channels::send(&resolved, message)
+        .await
+        .map_err(|e| redaction::redact_log_message(app_handle, &e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::TestDb;
+
+    #[test]
+    fn test_notifications_enabled_by_default() {
+        let db = TestDb::open();
+
+        let conn = db.conn().unwrap();
+        assert!(notifications_enabled(&conn));
+        repository::set_setting(&conn, SETTING_NOTIFICATIONS_ENABLED, "false").unwrap();
+        assert!(!notifications_enabled(&conn));
+    }
+}
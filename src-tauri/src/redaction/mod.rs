@@ -0,0 +1,33 @@
+mod redactor;
+
+pub use redactor::{validate_config, RedactionConfig, Redactor, REDACTED};
+
+use rusqlite::Connection;
+use tauri::Manager;
+
+use crate::commands::{RedactorState, SecretStoreState};
+use crate::database::repository;
+
+/// Settings key holding the JSON encoded `RedactionConfig`
+pub const SETTING_REDACTION_CONFIG: &str = "redaction.config";
+
+/// Load the redaction config from settings, falling back to defaults
+pub fn load_config(conn: &Connection) -> RedactionConfig {
+    repository::get_setting(conn, SETTING_REDACTION_CONFIG)
+        .ok()
+        .flatten()
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+/// Mask secret values and sensitive patterns before a log line is stored or emitted
+pub fn redact_log_message<R: tauri::Runtime>(app_handle: &tauri::AppHandle<R>, message: &str) -> String {
+    let mut redacted = match app_handle.try_state::<SecretStoreState>() {
+        Some(secrets) => secrets.0.redact(message),
+        None => message.to_string(),
+    };
+    if let Some(redactor) = app_handle.try_state::<RedactorState>() {
+        redacted = redactor.0.redact(&redacted);
+    }
+    redacted
+}
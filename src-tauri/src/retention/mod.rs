@@ -0,0 +1,293 @@
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tauri::{Emitter, Manager};
+
+use crate::database::repository;
+use crate::database::Database;
+
+/// Settings key holding the JSON encoded `RetentionConfig`
+pub const SETTING_RETENTION_CONFIG: &str = "logs.retention";
+/// Settings key holding the JSON encoded `PruneReport` of the last run
+pub const SETTING_LAST_PRUNE: &str = "logs.last_prune";
+
+/// How often logs are pruned in the background (the first run happens at launch)
+const PRUNE_INTERVAL: Duration = Duration::from_secs(60 * 60);
+const MAX_AGE_DAYS_LIMIT: u32 = 3650;
+
+/// How free pages are given back to the file system after pruning
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VacuumMode {
+    #[default]
+    None,
+    Incremental, // PRAGMA incremental_vacuum, once the database was converted with `enable_incremental_vacuum`
+    Full,        // VACUUM, rewrites the whole file
+}
+
+/// Log retention limits stored in settings; None disables a limit.
+/// Nothing is pruned until limits are set.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RetentionConfig {
+    #[serde(default)]
+    pub max_age_days: Option<u32>,
+    #[serde(default)]
+    pub max_rows: Option<u64>,            // All logs together
+    #[serde(default)]
+    pub max_rows_per_source: Option<u64>, // Per button and per monitor
+    #[serde(default)]
+    pub vacuum: VacuumMode,
+}
+
+/// Rows removed by one pruning run, per limit
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PruneReport {
+    pub by_age: usize,
+    pub by_source: usize,
+    pub by_total: usize,
+    pub total: usize,
+    #[serde(default)]
+    pub checks_by_age: usize, // Monitor check history removed by the age limit (not part of total)
+    pub vacuumed: bool,
+    pub ran_at: i64,
+}
+
+/// Load the retention config from settings, falling back to defaults
+pub fn load_config(conn: &Connection) -> RetentionConfig {
+    repository::get_setting(conn, SETTING_RETENTION_CONFIG)
+        .ok()
+        .flatten()
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+/// Load the report of the last pruning run, if any
+pub fn load_last_report(conn: &Connection) -> Option<PruneReport> {
+    repository::get_setting(conn, SETTING_LAST_PRUNE)
+        .ok()
+        .flatten()
+        .and_then(|json| serde_json::from_str(&json).ok())
+}
+
+/// Check the limits before they are saved
+pub fn validate_config(config: &RetentionConfig) -> Result<(), String> {
+    if config.max_age_days.is_some_and(|days| days == 0 || days > MAX_AGE_DAYS_LIMIT) {
+        return Err(format!("max_age_days must be between 1 and {}", MAX_AGE_DAYS_LIMIT));
+    }
+    if config.max_rows == Some(0) || config.max_rows_per_source == Some(0) {
+        return Err("Row limits must be greater than 0".to_string());
+    }
+    Ok(())
+}
+
+/// Apply the retention limits. Age goes first so the row limits only count logs that are kept.
+pub fn prune_logs(conn: &Connection, config: &RetentionConfig, now: i64) -> Result<PruneReport, String> {
+    let mut report = PruneReport {
+        ran_at: now,
+        ..Default::default()
+    };
+
+    let tx = conn
+        .unchecked_transaction()
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+    if let Some(days) = config.max_age_days {
+        let cutoff = now - i64::from(days) * 86_400;
+        report.by_age = repository::delete_logs_before(&tx, cutoff)
+            .map_err(|e| format!("Failed to prune old logs: {}", e))?;
+        report.checks_by_age = repository::delete_monitor_checks_before(&tx, cutoff)
+            .map_err(|e| format!("Failed to prune old monitor checks: {}", e))?;
+    }
+    if let Some(keep) = config.max_rows_per_source {
+        report.by_source = repository::prune_logs_per_source(&tx, keep)
+            .map_err(|e| format!("Failed to prune logs per source: {}", e))?;
+    }
+    if let Some(keep) = config.max_rows {
+        report.by_total = repository::prune_logs_total(&tx, keep)
+            .map_err(|e| format!("Failed to prune logs: {}", e))?;
+    }
+    tx.commit().map_err(|e| format!("Failed to commit pruning: {}", e))?;
+
+    report.total = report.by_age + report.by_source + report.by_total;
+    if report.total > 0 || report.checks_by_age > 0 {
+        report.vacuumed = vacuum(conn, config.vacuum)?;
+    }
+    Ok(report)
+}
+
+/// Release free pages; returns whether anything was run
+fn vacuum(conn: &Connection, mode: VacuumMode) -> Result<bool, String> {
+    match mode {
+        VacuumMode::None => Ok(false),
+        VacuumMode::Full => {
+            conn.execute_batch("VACUUM;")
+                .map_err(|e| format!("Failed to vacuum database: {}", e))?;
+            rebuild_search_index(conn)?;
+            Ok(true)
+        }
+        // Databases that were not converted have no free page list to shrink
+        VacuumMode::Incremental if !incremental_vacuum_enabled(conn)? => Ok(false),
+        VacuumMode::Incremental => {
+            conn.execute_batch("PRAGMA incremental_vacuum;")
+                .map_err(|e| format!("Failed to vacuum database: {}", e))?;
+            Ok(true)
+        }
+    }
+}
+
+/// Whether the database keeps free pages for `PRAGMA incremental_vacuum`
+pub fn incremental_vacuum_enabled(conn: &Connection) -> Result<bool, String> {
+    let auto_vacuum: i64 = conn
+        .query_row("PRAGMA auto_vacuum", [], |row| row.get(0))
+        .map_err(|e| format!("Failed to read auto_vacuum: {}", e))?;
+    // 0 = none, 1 = full, 2 = incremental
+    Ok(auto_vacuum == 2)
+}
+
+/// Switch the database to incremental vacuum. This rewrites the whole file once, so it only runs
+/// when the user asks for it. Returns false if the database was already converted.
+pub fn enable_incremental_vacuum(conn: &Connection) -> Result<bool, String> {
+    if incremental_vacuum_enabled(conn)? {
+        return Ok(false);
+    }
+    conn.execute_batch("PRAGMA auto_vacuum = INCREMENTAL; VACUUM;")
+        .map_err(|e| format!("Failed to convert database: {}", e))?;
+    rebuild_search_index(conn)?;
+    Ok(true)
+}
+
+/// VACUUM may renumber the log rowids the search index points at
+fn rebuild_search_index(conn: &Connection) -> Result<(), String> {
+    repository::rebuild_log_search_index(conn)
+        .map_err(|e| format!("Failed to rebuild log search index: {}", e))
+}
+
+/// Prune with the stored config on the writer thread, remember the report and notify the UI
+pub async fn run_pruning<R: tauri::Runtime>(
+    app_handle: &tauri::AppHandle<R>,
+    db: &Database,
+) -> Result<PruneReport, String> {
+    let report = db
+        .write(|conn| {
+            let report = prune_logs(conn, &load_config(conn), chrono::Utc::now().timestamp())?;
+            let json = serde_json::to_string(&report).map_err(|e| e.to_string())?;
+            repository::set_setting(conn, SETTING_LAST_PRUNE, &json)
+                .map_err(|e| format!("Failed to save prune report: {}", e))?;
+            Ok(report)
+        })
+        .await?;
+
+    if report.total > 0 {
+        let _ = app_handle.emit("logs-pruned", &report);
+    }
+    Ok(report)
+}
+
+/// Prune logs at launch and then periodically
+pub fn spawn_pruning_task(app_handle: tauri::AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut interval = tokio::time::interval(PRUNE_INTERVAL);
+        loop {
+            // The first tick completes immediately
+            interval.tick().await;
+
+            let Some(db) = app_handle.try_state::<Database>() else { return };
+            let db = db.inner().clone();
+            if let Err(e) = run_pruning(&app_handle, &db).await {
+                eprintln!("Failed to prune logs: {}", e);
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::{insert_button, insert_log, TestDb};
+
+    const NOW: i64 = 1_700_000_000;
+
+    fn count(conn: &Connection, sql: &str) -> i64 {
+        conn.query_row(sql, [], |row| row.get(0)).unwrap()
+    }
+
+    #[test]
+    fn test_prune_by_age_and_total() {
+        let db = TestDb::open();
+        let conn = db.conn().unwrap();
+        for day in 0..10 {
+            insert_log(&conn, None, "info", "line", NOW - day * 86_400 - 1);
+        }
+
+        let config = RetentionConfig {
+            max_age_days: Some(7),
+            max_rows: Some(5),
+            max_rows_per_source: None,
+            vacuum: VacuumMode::Incremental,
+        };
+        let report = prune_logs(&conn, &config, NOW).unwrap();
+
+        assert_eq!(report.by_age, 3);
+        assert_eq!(report.by_total, 2);
+        assert_eq!(report.total, 5);
+        assert_eq!(count(&conn, "SELECT COUNT(*) FROM logs"), 5);
+        // The newest logs are kept
+        assert_eq!(count(&conn, "SELECT MIN(timestamp) FROM logs"), NOW - 4 * 86_400 - 1);
+        // Incremental vacuum does nothing until the database is converted
+        assert!(!report.vacuumed);
+        assert_eq!(count(&conn, "PRAGMA auto_vacuum"), 0);
+
+        assert!(enable_incremental_vacuum(&conn).unwrap());
+        assert!(!enable_incremental_vacuum(&conn).unwrap());
+        assert_eq!(count(&conn, "PRAGMA auto_vacuum"), 2);
+        insert_log(&conn, None, "info", "line", NOW - 9 * 86_400);
+        let report = prune_logs(&conn, &config, NOW).unwrap();
+        assert_eq!(report.by_age, 1);
+        assert!(report.vacuumed);
+    }
+
+    #[test]
+    fn test_prune_per_source() {
+        let db = TestDb::open();
+        let conn = db.conn().unwrap();
+        for id in ["a", "b"] {
+            insert_button(&conn, id, id);
+        }
+        for i in 0..6 {
+            insert_log(&conn, Some("a"), "info", "line", NOW - i);
+        }
+        insert_log(&conn, Some("b"), "info", "line", NOW);
+        insert_log(&conn, None, "info", "line", NOW);
+
+        let config = RetentionConfig {
+            max_age_days: None,
+            max_rows: None,
+            max_rows_per_source: Some(2),
+            vacuum: VacuumMode::None,
+        };
+        let report = prune_logs(&conn, &config, NOW).unwrap();
+
+        assert_eq!(report.by_source, 4);
+        assert!(!report.vacuumed);
+        assert_eq!(count(&conn, "SELECT COUNT(*) FROM logs WHERE button_id = 'a'"), 2);
+        assert_eq!(count(&conn, "SELECT COUNT(*) FROM logs"), 4);
+    }
+
+    #[test]
+    fn test_validate_config() {
+        // Nothing is pruned or vacuumed without a saved config
+        let config = RetentionConfig::default();
+        assert!(config.max_age_days.is_none() && config.max_rows.is_none() && config.max_rows_per_source.is_none());
+        assert_eq!(config.vacuum, VacuumMode::None);
+        assert!(validate_config(&config).is_ok());
+        assert!(validate_config(&RetentionConfig {
+            max_age_days: Some(0),
+            ..Default::default()
+        })
+        .is_err());
+        assert!(validate_config(&RetentionConfig {
+            max_rows_per_source: Some(0),
+            ..Default::default()
+        })
+        .is_err());
+    }
+}
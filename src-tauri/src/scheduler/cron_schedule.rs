@@ -0,0 +1,84 @@
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+use std::str::FromStr;
+
+/// A parsed cron expression bound to a timezone
+#[derive(Debug, Clone)]
+pub struct CronSchedule {
+    schedule: cron::Schedule,
+    timezone: Tz,
+}
+
+impl CronSchedule {
+    /// Parse a cron expression in the given IANA timezone (e.g. "Asia/Shanghai").
+    /// Standard 5-field expressions run at second 0; 6/7-field expressions start with seconds.
+    pub fn parse(expression: &str, timezone: &str) -> Result<Self, String> {
+        let fields = expression.split_whitespace().count();
+        let normalized = match fields {
+            5 => format!("0 {}", expression.trim()),
+            6 | 7 => expression.trim().to_string(),
+            _ => {
+                return Err(format!(
+                    "Invalid cron expression '{}': expected 5 to 7 fields, got {}",
+                    expression, fields
+                ))
+            }
+        };
+
+        let schedule = cron::Schedule::from_str(&normalized)
+            .map_err(|e| format!("Invalid cron expression '{}': {}", expression, e))?;
+        let timezone = Tz::from_str(timezone).map_err(|_| format!("Unknown timezone: {}", timezone))?;
+
+        Ok(Self { schedule, timezone })
+    }
+
+    /// First fire time strictly after the given instant
+    pub fn next_after(&self, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        self.schedule
+            .after(&after.with_timezone(&self.timezone))
+            .next()
+            .map(|time| time.with_timezone(&Utc))
+    }
+
+    /// The next `count` fire times after the given instant
+    pub fn upcoming(&self, after: DateTime<Utc>, count: usize) -> Vec<DateTime<Utc>> {
+        self.schedule
+            .after(&after.with_timezone(&self.timezone))
+            .take(count)
+            .map(|time| time.with_timezone(&Utc))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_five_field_expression_in_timezone() {
+        // Every day at 02:30 in Shanghai (UTC+8) is 18:30 UTC the day before
+        let schedule = CronSchedule::parse("30 2 * * *", "Asia/Shanghai").unwrap();
+        let now = Utc.with_ymd_and_hms(2024, 3, 1, 12, 0, 0).unwrap();
+        assert_eq!(
+            schedule.next_after(now).unwrap(),
+            Utc.with_ymd_and_hms(2024, 3, 1, 18, 30, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_seconds_precision() {
+        let schedule = CronSchedule::parse("*/15 * * * * *", "UTC").unwrap();
+        let now = Utc.with_ymd_and_hms(2024, 3, 1, 12, 0, 1).unwrap();
+        let upcoming = schedule.upcoming(now, 2);
+        assert_eq!(upcoming[0], Utc.with_ymd_and_hms(2024, 3, 1, 12, 0, 15).unwrap());
+        assert_eq!(upcoming[1], Utc.with_ymd_and_hms(2024, 3, 1, 12, 0, 30).unwrap());
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        assert!(CronSchedule::parse("* * *", "UTC").is_err());
+        assert!(CronSchedule::parse("61 * * * *", "UTC").is_err());
+        assert!(CronSchedule::parse("0 * * * *", "Mars/Olympus").is_err());
+    }
+}
@@ -0,0 +1,5 @@
+mod cron_schedule;
+mod schedule_manager;
+
+pub use cron_schedule::CronSchedule;
+pub use schedule_manager::ScheduleManager;
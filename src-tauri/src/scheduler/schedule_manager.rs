@@ -0,0 +1,172 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use tauri::{Emitter, Manager};
+use tokio::sync::Mutex;
+use tokio::task::JoinHandle;
+
+use crate::database::models::{Button, Schedule};
+use crate::database::repository;
+use crate::database::Database;
+use crate::executor::preflight;
+use crate::executor::runner::{self, Trigger};
+use crate::notifications;
+use super::cron_schedule::CronSchedule;
+
+/// Runs buttons on their cron schedules, one background task per enabled schedule
+pub struct ScheduleManager {
+    active_schedules: Arc<Mutex<HashMap<String, JoinHandle<()>>>>,
+}
+
+impl ScheduleManager {
+    /// Create a new ScheduleManager
+    pub fn new() -> Self {
+        Self {
+            active_schedules: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Start every enabled schedule stored in the database (called at app launch)
+    pub async fn start_all(&self, app_handle: tauri::AppHandle) {
+        let schedules = match Self::read_db(&app_handle, repository::get_all_schedules).await {
+            Some(Ok(schedules)) => schedules,
+            Some(Err(e)) => {
+                eprintln!("Failed to load schedules: {}", e);
+                return;
+            }
+            None => return,
+        };
+
+        for schedule in schedules.into_iter().filter(|s| s.enabled) {
+            let id = schedule.id.clone();
+            if let Err(e) = self.start_schedule(schedule, app_handle.clone()).await {
+                eprintln!("Failed to start schedule {}: {}", id, e);
+            }
+        }
+    }
+
+    /// Start (or restart) the task of a schedule
+    pub async fn start_schedule(&self, schedule: Schedule, app_handle: tauri::AppHandle) -> Result<(), String> {
+        let cron = CronSchedule::parse(&schedule.cron_expression, &schedule.timezone)?;
+
+        let mut schedules = self.active_schedules.lock().await;
+        if let Some(handle) = schedules.remove(&schedule.id) {
+            handle.abort();
+        }
+
+        let schedule_id = schedule.id.clone();
+        let handle = tokio::spawn(async move {
+            Self::schedule_loop(schedule, cron, app_handle).await;
+        });
+        schedules.insert(schedule_id, handle);
+
+        Ok(())
+    }
+
+    /// Stop the task of a schedule if it is running
+    pub async fn stop_schedule(&self, schedule_id: &str) {
+        let mut schedules = self.active_schedules.lock().await;
+        if let Some(handle) = schedules.remove(schedule_id) {
+            handle.abort();
+        }
+    }
+
+    /// Sleep until each fire time and run the button. Fire times are skipped while the previous run
+    /// of the schedule is still going, and the schedule pauses once its button needs a new confirmation.
+    async fn schedule_loop(schedule: Schedule, cron: CronSchedule, app_handle: tauri::AppHandle) {
+        let mut previous_run: Option<JoinHandle<()>> = None;
+
+        loop {
+            let now = chrono::Utc::now();
+            let Some(next_run) = cron.next_after(now) else {
+                // The expression has no future fire times (e.g. a past year)
+                break;
+            };
+
+            let delay = (next_run - now).to_std().unwrap_or_default();
+            tokio::time::sleep(delay).await;
+
+            if previous_run.as_ref().is_some_and(|run| !run.is_finished()) {
+                eprintln!("Schedule {}: previous run is still in progress, skipping this fire time", schedule.id);
+                continue;
+            }
+
+            let button_id = schedule.button_id.clone();
+            let button = match Self::read_db(&app_handle, move |conn| repository::get_button_by_id(conn, &button_id)).await {
+                Some(Ok(button)) => button,
+                Some(Err(e)) => {
+                    eprintln!("Schedule {}: failed to load button: {}", schedule.id, e);
+                    continue;
+                }
+                None => continue,
+            };
+
+            // The confirmation given when the schedule was saved only covers the script it was given for
+            if let Err(e) = preflight::check_unattended(&button, schedule.confirmed_script.as_deref()) {
+                Self::pause(&app_handle, &schedule, &button, &e).await;
+                break;
+            }
+
+            previous_run = Self::run_button(&app_handle, &schedule, button);
+        }
+    }
+
+    fn run_button(app_handle: &tauri::AppHandle, schedule: &Schedule, button: Button) -> Option<JoinHandle<()>> {
+        let run = match runner::start_execution(app_handle, button, &HashMap::new(), Trigger::schedule(&schedule.id)) {
+            Ok(started) => Some(started.task),
+            Err(e) => {
+                eprintln!("Schedule {}: failed to start execution: {}", schedule.id, e);
+                None
+            }
+        };
+
+        let now = chrono::Utc::now().timestamp();
+        if let Some(db) = app_handle.try_state::<Database>() {
+            let schedule_id = schedule.id.clone();
+            db.write_detached(move |conn| {
+                repository::update_schedule_last_run(conn, &schedule_id, now).map_err(|e| e.to_string())
+            });
+        }
+        run
+    }
+
+    /// Disable a schedule whose button can no longer run unattended and tell the user
+    async fn pause(app_handle: &tauri::AppHandle, schedule: &Schedule, button: &Button, reason: &str) {
+        eprintln!("Schedule {}: paused, {}", schedule.id, reason);
+
+        if let Some(db) = app_handle.try_state::<Database>() {
+            let schedule_id = schedule.id.clone();
+            let now = chrono::Utc::now().timestamp();
+            if let Err(e) = db
+                .write(move |conn| {
+                    repository::set_schedule_enabled(conn, &schedule_id, false, now).map_err(|e| e.to_string())
+                })
+                .await
+            {
+                eprintln!("Schedule {}: failed to disable: {}", schedule.id, e);
+            }
+        }
+
+        let _ = app_handle.emit("schedule-paused", &schedule.id);
+        notifications::notify(
+            app_handle,
+            &format!("⏸️ {} 的定时运行已暂停", button.name),
+            "脚本已修改或需要确认，请在定时计划中重新确认后启用",
+        )
+        .await;
+    }
+
+    /// Run a query against the shared database off the async runtime
+    async fn read_db<T: Send + 'static>(
+        app_handle: &tauri::AppHandle,
+        f: impl FnOnce(&rusqlite::Connection) -> rusqlite::Result<T> + Send + 'static,
+    ) -> Option<Result<T, String>> {
+        let db = app_handle.try_state::<Database>()?.inner().clone();
+        Some(db.read(move |conn| f(conn).map_err(|e| e.to_string())).await)
+    }
+}
+
+impl Default for ScheduleManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
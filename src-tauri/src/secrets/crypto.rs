@@ -0,0 +1,101 @@
+use argon2::Argon2;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use chacha20poly1305::aead::rand_core::RngCore;
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::{Key, XChaCha20Poly1305, XNonce};
+
+const KEY_LEN: usize = 32;
+const NONCE_LEN: usize = 24;
+const SALT_LEN: usize = 16;
+
+/// Symmetric key used to encrypt secret values at rest
+#[derive(Clone)]
+pub struct MasterKey([u8; KEY_LEN]);
+
+impl MasterKey {
+    /// Generate a new random key
+    pub fn generate() -> Self {
+        let key = XChaCha20Poly1305::generate_key(&mut OsRng);
+        Self(key.into())
+    }
+
+    /// Derive a key from a master password with Argon2id
+    pub fn from_password(password: &str, salt: &[u8]) -> Result<Self, String> {
+        let mut key = [0u8; KEY_LEN];
+        Argon2::default()
+            .hash_password_into(password.as_bytes(), salt, &mut key)
+            .map_err(|e| format!("Failed to derive key from password: {}", e))?;
+        Ok(Self(key))
+    }
+
+    /// Decode a key stored as base64 (keyring entry or key file)
+    pub fn from_base64(encoded: &str) -> Result<Self, String> {
+        let bytes = STANDARD
+            .decode(encoded.trim())
+            .map_err(|e| format!("Invalid master key encoding: {}", e))?;
+        let key: [u8; KEY_LEN] = bytes
+            .try_into()
+            .map_err(|_| "Invalid master key length".to_string())?;
+        Ok(Self(key))
+    }
+
+    /// Encode the key as base64 for storage
+    pub fn to_base64(&self) -> String {
+        STANDARD.encode(self.0)
+    }
+
+    /// Encrypt a value, returning (nonce, ciphertext)
+    pub fn encrypt(&self, plaintext: &[u8]) -> Result<(Vec<u8>, Vec<u8>), String> {
+        let cipher = XChaCha20Poly1305::new(Key::from_slice(&self.0));
+        let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let ciphertext = cipher
+            .encrypt(&nonce, plaintext)
+            .map_err(|_| "Failed to encrypt secret".to_string())?;
+        Ok((nonce.to_vec(), ciphertext))
+    }
+
+    /// Decrypt a value produced by `encrypt`
+    pub fn decrypt(&self, nonce: &[u8], ciphertext: &[u8]) -> Result<Vec<u8>, String> {
+        if nonce.len() != NONCE_LEN {
+            return Err("Invalid nonce length".to_string());
+        }
+        let cipher = XChaCha20Poly1305::new(Key::from_slice(&self.0));
+        cipher
+            .decrypt(XNonce::from_slice(nonce), ciphertext)
+            .map_err(|_| "Failed to decrypt secret (wrong key or corrupted data)".to_string())
+    }
+}
+
+/// Generate a random salt for password based key derivation
+pub fn generate_salt() -> Vec<u8> {
+    let mut salt = vec![0u8; SALT_LEN];
+    OsRng.fill_bytes(&mut salt);
+    salt
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encrypt_roundtrip() {
+        let key = MasterKey::generate();
+        let (nonce, ciphertext) = key.encrypt(b"ghp_token").unwrap();
+        assert_ne!(ciphertext, b"ghp_token");
+        assert_eq!(key.decrypt(&nonce, &ciphertext).unwrap(), b"ghp_token");
+
+        // A different key cannot decrypt
+        assert!(MasterKey::generate().decrypt(&nonce, &ciphertext).is_err());
+    }
+
+    #[test]
+    fn test_password_key_is_deterministic() {
+        let salt = generate_salt();
+        let a = MasterKey::from_password("hunter2", &salt).unwrap();
+        let b = MasterKey::from_password("hunter2", &salt).unwrap();
+        let c = MasterKey::from_password("hunter3", &salt).unwrap();
+        assert_eq!(a.to_base64(), b.to_base64());
+        assert_ne!(a.to_base64(), c.to_base64());
+        assert_eq!(MasterKey::from_base64(&a.to_base64()).unwrap().to_base64(), a.to_base64());
+    }
+}
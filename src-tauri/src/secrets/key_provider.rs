@@ -0,0 +1,146 @@
+use serde::Serialize;
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+
+use super::crypto::MasterKey;
+
+const KEYRING_SERVICE: &str = "com.quyanchun.dev-tools-temp";
+const KEYRING_USER: &str = "secrets-master-key";
+const KEY_FILE_NAME: &str = "secrets.key";
+
+/// Where the master key of the secret store comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KeySource {
+    Keyring,  // Random key kept in the OS keyring
+    File,     // Random key kept in a file next to the database
+    Password, // Key derived from a master password, never stored
+}
+
+impl KeySource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            KeySource::Keyring => "keyring",
+            KeySource::File => "file",
+            KeySource::Password => "password",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "keyring" => Some(KeySource::Keyring),
+            "file" => Some(KeySource::File),
+            "password" => Some(KeySource::Password),
+            _ => None,
+        }
+    }
+}
+
+/// Load the system managed key from its known source, or create one on first use.
+/// A new key goes to the OS keyring when available and falls back to a key file.
+pub fn load_or_create_system_key(
+    source: Option<KeySource>,
+    app_dir: &Path,
+) -> Result<(MasterKey, KeySource), String> {
+    let key_file = app_dir.join(KEY_FILE_NAME);
+
+    match source {
+        Some(KeySource::Keyring) => Ok((read_keyring()?, KeySource::Keyring)),
+        Some(KeySource::File) => Ok((read_key_file(&key_file)?, KeySource::File)),
+        Some(KeySource::Password) => Err("Secret store is protected by a master password".to_string()),
+        None => {
+            let key = MasterKey::generate();
+            match write_keyring(&key) {
+                Ok(()) => Ok((key, KeySource::Keyring)),
+                Err(e) => {
+                    eprintln!("OS keyring unavailable ({}), falling back to key file", e);
+                    write_key_file(&key_file, &key)?;
+                    Ok((key, KeySource::File))
+                }
+            }
+        }
+    }
+}
+
+/// Remove the system managed key once a master password takes over
+pub fn remove_system_key(source: KeySource, app_dir: &Path) {
+    match source {
+        KeySource::Keyring => {
+            // Keyring backends may block on their own runtime, keep them off the async workers
+            std::thread::spawn(|| {
+                if let Ok(entry) = keyring::Entry::new(KEYRING_SERVICE, KEYRING_USER) {
+                    let _ = entry.delete_credential();
+                }
+            });
+        }
+        KeySource::File => {
+            let _ = fs::remove_file(app_dir.join(KEY_FILE_NAME));
+        }
+        KeySource::Password => {}
+    }
+}
+
+fn read_keyring() -> Result<MasterKey, String> {
+    let entry = keyring::Entry::new(KEYRING_SERVICE, KEYRING_USER)
+        .map_err(|e| format!("Failed to open keyring entry: {}", e))?;
+    let encoded = entry
+        .get_password()
+        .map_err(|e| format!("Failed to read master key from keyring: {}", e))?;
+    MasterKey::from_base64(&encoded)
+}
+
+fn write_keyring(key: &MasterKey) -> Result<(), String> {
+    let entry = keyring::Entry::new(KEYRING_SERVICE, KEYRING_USER).map_err(|e| e.to_string())?;
+    entry.set_password(&key.to_base64()).map_err(|e| e.to_string())?;
+
+    // Some platforms accept the write but do not persist it, so read it back
+    let stored = entry.get_password().map_err(|e| e.to_string())?;
+    if stored != key.to_base64() {
+        return Err("keyring did not persist the master key".to_string());
+    }
+    Ok(())
+}
+
+fn read_key_file(path: &Path) -> Result<MasterKey, String> {
+    let encoded = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read key file {}: {}", path.display(), e))?;
+    MasterKey::from_base64(&encoded)
+}
+
+fn write_key_file(path: &Path, key: &MasterKey) -> Result<(), String> {
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+
+    // Only the current user may read the key file
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+
+    let mut file = options
+        .open(path)
+        .map_err(|e| format!("Failed to create key file {}: {}", path.display(), e))?;
+    file.write_all(key.to_base64().as_bytes())
+        .map_err(|e| format!("Failed to write key file {}: {}", path.display(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_key_file_roundtrip() {
+        let dir = std::env::temp_dir().join(format!("devtools-secrets-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+
+        let key = MasterKey::generate();
+        write_key_file(&dir.join(KEY_FILE_NAME), &key).unwrap();
+        let (loaded, source) = load_or_create_system_key(Some(KeySource::File), &dir).unwrap();
+        assert_eq!(source, KeySource::File);
+        assert_eq!(loaded.to_base64(), key.to_base64());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
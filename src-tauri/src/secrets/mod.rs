@@ -0,0 +1,5 @@
+mod crypto;
+mod key_provider;
+mod store;
+
+pub use store::{SecretStatus, SecretStore};
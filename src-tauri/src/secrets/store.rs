@@ -0,0 +1,363 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+use regex::Regex;
+use rusqlite::Connection;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{OnceLock, RwLock};
+
+use crate::database::models::SecretRecord;
+use crate::database::repository;
+use crate::redaction::REDACTED;
+use super::crypto::{generate_salt, MasterKey};
+use super::key_provider::{load_or_create_system_key, remove_system_key, KeySource};
+
+const SETTING_KEY_SOURCE: &str = "secrets.key_source";
+const SETTING_PASSWORD_SALT: &str = "secrets.password_salt";
+const SETTING_PASSWORD_CHECK: &str = "secrets.password_check";
+const PASSWORD_CHECK_PLAINTEXT: &[u8] = b"dev-tools-secrets";
+
+/// Secret store status for the frontend
+#[derive(Debug, Clone, Serialize)]
+pub struct SecretStatus {
+    pub locked: bool,
+    pub key_source: Option<KeySource>,
+    pub secret_count: usize,
+}
+
+#[derive(Default)]
+struct StoreState {
+    key: Option<MasterKey>,
+    source: Option<KeySource>,
+    app_dir: Option<PathBuf>,
+    values: HashMap<String, String>, // Decrypted values, only present while unlocked
+}
+
+/// Encrypted-at-rest credential store.
+/// Values are decrypted into memory once unlocked so they can be resolved
+/// into scripts/monitor targets and redacted from logs.
+pub struct SecretStore {
+    state: RwLock<StoreState>,
+}
+
+impl SecretStore {
+    /// Create a locked, uninitialized store
+    pub fn new() -> Self {
+        Self {
+            state: RwLock::new(StoreState::default()),
+        }
+    }
+
+    /// Load the master key (keyring or key file) and decrypt all secrets.
+    /// Password protected stores stay locked until `unlock` is called.
+    pub fn initialize(&self, conn: &Connection, app_dir: &Path) -> Result<(), String> {
+        let source = repository::get_setting(conn, SETTING_KEY_SOURCE)
+            .map_err(|e| format!("Failed to read secret settings: {}", e))?
+            .and_then(|value| KeySource::parse(&value));
+
+        let mut state = self.state.write().map_err(|e| e.to_string())?;
+        state.app_dir = Some(app_dir.to_path_buf());
+
+        if source == Some(KeySource::Password) {
+            state.source = source;
+            return Ok(());
+        }
+
+        let (key, source) = load_or_create_system_key(source, app_dir)?;
+        repository::set_setting(conn, SETTING_KEY_SOURCE, source.as_str())
+            .map_err(|e| format!("Failed to save secret settings: {}", e))?;
+
+        state.values = decrypt_all(conn, &key)?;
+        state.key = Some(key);
+        state.source = Some(source);
+        Ok(())
+    }
+
+    /// Unlock a password protected store
+    pub fn unlock(&self, conn: &Connection, password: &str) -> Result<(), String> {
+        let mut state = self.state.write().map_err(|e| e.to_string())?;
+        if state.source != Some(KeySource::Password) {
+            return Err("Secret store is not protected by a master password".to_string());
+        }
+
+        let salt = read_base64_setting(conn, SETTING_PASSWORD_SALT)?;
+        let check = read_base64_setting(conn, SETTING_PASSWORD_CHECK)?;
+        let key = MasterKey::from_password(password, &salt)?;
+
+        let (nonce, ciphertext) = check.split_at(check.len().min(24));
+        key.decrypt(nonce, ciphertext)
+            .map_err(|_| "Incorrect master password".to_string())?;
+
+        state.values = decrypt_all(conn, &key)?;
+        state.key = Some(key);
+        Ok(())
+    }
+
+    /// Protect the store with a master password, re-encrypting every secret.
+    /// The previous system managed key is removed afterwards.
+    pub fn set_master_password(&self, conn: &Connection, password: &str) -> Result<(), String> {
+        if password.is_empty() {
+            return Err("Master password must not be empty".to_string());
+        }
+
+        let mut state = self.state.write().map_err(|e| e.to_string())?;
+        if state.key.is_none() {
+            return Err("Secret store is locked".to_string());
+        }
+
+        let salt = generate_salt();
+        let key = MasterKey::from_password(password, &salt)?;
+        let (check_nonce, check_ciphertext) = key.encrypt(PASSWORD_CHECK_PLAINTEXT)?;
+        let now = chrono::Utc::now().timestamp();
+
+        let tx = conn
+            .unchecked_transaction()
+            .map_err(|e| format!("Failed to start transaction: {}", e))?;
+        for record in repository::get_all_secrets(&tx).map_err(|e| e.to_string())? {
+            let value = state.values.get(&record.name).cloned().unwrap_or_default();
+            let (nonce, ciphertext) = key.encrypt(value.as_bytes())?;
+            repository::upsert_secret(
+                &tx,
+                &SecretRecord {
+                    nonce,
+                    ciphertext,
+                    updated_at: now,
+                    ..record
+                },
+            )
+            .map_err(|e| format!("Failed to re-encrypt secret: {}", e))?;
+        }
+        repository::set_setting(&tx, SETTING_PASSWORD_SALT, &STANDARD.encode(&salt))
+            .and_then(|_| {
+                repository::set_setting(
+                    &tx,
+                    SETTING_PASSWORD_CHECK,
+                    &STANDARD.encode([check_nonce, check_ciphertext].concat()),
+                )
+            })
+            .and_then(|_| repository::set_setting(&tx, SETTING_KEY_SOURCE, KeySource::Password.as_str()))
+            .map_err(|e| format!("Failed to save secret settings: {}", e))?;
+        tx.commit().map_err(|e| format!("Failed to commit transaction: {}", e))?;
+
+        if let (Some(previous), Some(app_dir)) = (state.source, state.app_dir.as_deref()) {
+            remove_system_key(previous, app_dir);
+        }
+        state.key = Some(key);
+        state.source = Some(KeySource::Password);
+        Ok(())
+    }
+
+    /// Current lock state and key source
+    pub fn status(&self) -> SecretStatus {
+        let state = self.state.read().unwrap_or_else(|e| e.into_inner());
+        SecretStatus {
+            locked: state.key.is_none(),
+            key_source: state.source,
+            secret_count: state.values.len(),
+        }
+    }
+
+    /// Encrypt and save a secret
+    pub fn set_secret(&self, conn: &Connection, name: &str, value: &str) -> Result<(), String> {
+        validate_name(name)?;
+
+        let mut state = self.state.write().map_err(|e| e.to_string())?;
+        let key = state.key.as_ref().ok_or("Secret store is locked")?;
+        let (nonce, ciphertext) = key.encrypt(value.as_bytes())?;
+        let now = chrono::Utc::now().timestamp();
+
+        repository::upsert_secret(
+            conn,
+            &SecretRecord {
+                name: name.to_string(),
+                nonce,
+                ciphertext,
+                created_at: now,
+                updated_at: now,
+            },
+        )
+        .map_err(|e| format!("Failed to save secret: {}", e))?;
+
+        state.values.insert(name.to_string(), value.to_string());
+        Ok(())
+    }
+
+    /// Delete a secret
+    pub fn delete_secret(&self, conn: &Connection, name: &str) -> Result<(), String> {
+        repository::delete_secret(conn, name).map_err(|e| format!("Failed to delete secret: {}", e))?;
+
+        let mut state = self.state.write().map_err(|e| e.to_string())?;
+        state.values.remove(name);
+        Ok(())
+    }
+
+    /// Replace `${secret:NAME}` references with their values
+    pub fn resolve(&self, text: &str) -> Result<String, String> {
+        if !reference_regex().is_match(text) {
+            return Ok(text.to_string());
+        }
+
+        let state = self.state.read().map_err(|e| e.to_string())?;
+        if state.key.is_none() {
+            return Err("Secret store is locked, unlock it to use ${secret:...} references".to_string());
+        }
+        resolve_references(text, &state.values)
+    }
+
+    /// Value of a single secret, failing when the store is locked or the name is unknown
+    pub fn lookup(&self, name: &str) -> Result<String, String> {
+        let state = self.state.read().map_err(|e| e.to_string())?;
+        if state.key.is_none() {
+            return Err("Secret store is locked, unlock it to use ${secret:...} references".to_string());
+        }
+        state
+            .values
+            .get(name)
+            .cloned()
+            .ok_or_else(|| format!("Unknown secret(s): {}", name))
+    }
+
+    /// Mask every known secret value in a log line
+    pub fn redact(&self, text: &str) -> String {
+        let state = self.state.read().unwrap_or_else(|e| e.into_inner());
+        redact_values(text, state.values.values())
+    }
+}
+
+impl Default for SecretStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn reference_regex() -> &'static Regex {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    REGEX.get_or_init(|| Regex::new(r"\$\{secret:([A-Za-z_][A-Za-z0-9_]*)\}").unwrap())
+}
+
+fn validate_name(name: &str) -> Result<(), String> {
+    let valid = name
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+
+    if valid {
+        Ok(())
+    } else {
+        Err(format!(
+            "Invalid secret name '{}': use letters, digits and underscores, not starting with a digit",
+            name
+        ))
+    }
+}
+
+fn read_base64_setting(conn: &Connection, key: &str) -> Result<Vec<u8>, String> {
+    let value = repository::get_setting(conn, key)
+        .map_err(|e| format!("Failed to read secret settings: {}", e))?
+        .ok_or_else(|| format!("Missing secret setting '{}'", key))?;
+    STANDARD
+        .decode(value)
+        .map_err(|e| format!("Invalid secret setting '{}': {}", key, e))
+}
+
+fn decrypt_all(conn: &Connection, key: &MasterKey) -> Result<HashMap<String, String>, String> {
+    let records = repository::get_all_secrets(conn)
+        .map_err(|e| format!("Failed to load secrets: {}", e))?;
+
+    records
+        .into_iter()
+        .map(|record| {
+            let plaintext = key.decrypt(&record.nonce, &record.ciphertext)?;
+            let value = String::from_utf8(plaintext)
+                .map_err(|_| format!("Secret '{}' is not valid UTF-8", record.name))?;
+            Ok((record.name, value))
+        })
+        .collect()
+}
+
+/// Substitute references from a name -> value map, failing on unknown names
+pub fn resolve_references(text: &str, values: &HashMap<String, String>) -> Result<String, String> {
+    let mut missing = Vec::new();
+    let resolved = reference_regex().replace_all(text, |caps: &regex::Captures| {
+        let name = &caps[1];
+        match values.get(name) {
+            Some(value) => value.clone(),
+            None => {
+                missing.push(name.to_string());
+                caps[0].to_string()
+            }
+        }
+    });
+
+    if missing.is_empty() {
+        Ok(resolved.into_owned())
+    } else {
+        Err(format!("Unknown secret(s): {}", missing.join(", ")))
+    }
+}
+
+/// Replace every occurrence of the given values, longest first
+pub fn redact_values<'a>(text: &str, values: impl Iterator<Item = &'a String>) -> String {
+    let mut values: Vec<&String> = values.filter(|v| !v.is_empty()).collect();
+    values.sort_by_key(|v| std::cmp::Reverse(v.len()));
+
+    let mut redacted = text.to_string();
+    for value in values {
+        if redacted.contains(value.as_str()) {
+            redacted = redacted.replace(value.as_str(), REDACTED);
+        }
+    }
+    redacted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values() -> HashMap<String, String> {
+        HashMap::from([
+            ("GITHUB_TOKEN".to_string(), "ghp_abc123".to_string()),
+            ("API_KEY".to_string(), "k-987".to_string()),
+        ])
+    }
+
+    #[test]
+    fn test_resolve_references() {
+        let resolved = resolve_references(
+            "curl -H 'Authorization: Bearer ${secret:GITHUB_TOKEN}' ?key=${secret:API_KEY}",
+            &values(),
+        )
+        .unwrap();
+        assert_eq!(resolved, "curl -H 'Authorization: Bearer ghp_abc123' ?key=k-987");
+
+        let err = resolve_references("${secret:MISSING}", &values()).unwrap_err();
+        assert!(err.contains("MISSING"));
+    }
+
+    #[test]
+    fn test_redact_values() {
+        let values = values();
+        assert_eq!(
+            redact_values("token=ghp_abc123 key=k-987", values.values()),
+            "token=[REDACTED] key=[REDACTED]"
+        );
+        assert_eq!(redact_values("nothing here", values.values()), "nothing here");
+    }
+
+    #[test]
+    fn test_validate_name() {
+        assert!(validate_name("GITHUB_TOKEN").is_ok());
+        assert!(validate_name("_x1").is_ok());
+        assert!(validate_name("1ABC").is_err());
+        assert!(validate_name("a-b").is_err());
+        assert!(validate_name("").is_err());
+    }
+
+    #[test]
+    fn test_locked_store_only_fails_on_references() {
+        let store = SecretStore::new();
+        assert_eq!(store.resolve("echo hi").unwrap(), "echo hi");
+        assert!(store.resolve("echo ${secret:TOKEN}").is_err());
+    }
+}
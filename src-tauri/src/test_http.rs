@@ -0,0 +1,137 @@
+//! Local HTTP(S) server answering with canned responses, for tests of HTTP clients
+
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::sync::mpsc;
+
+/// Request received by the mock server
+#[derive(Debug)]
+pub struct MockRequest {
+    pub head: String, // Request line and headers
+    pub body: String,
+}
+
+/// Server on a local port; each response is sent once and requests are passed on in order
+pub struct MockServer {
+    pub port: u16,
+    requests: mpsc::Receiver<MockRequest>,
+}
+
+impl MockServer {
+    /// Answer each connection with the next response
+    pub fn serve<S: Into<String>>(responses: impl IntoIterator<Item = S>) -> Self {
+        let responses: Vec<String> = responses.into_iter().map(Into::into).collect();
+        Self::spawn(move |listener, sender| {
+            for response in responses {
+                let Ok((mut stream, _)) = listener.accept() else {
+                    return;
+                };
+                respond(&mut stream, &response, &sender);
+            }
+        })
+    }
+
+    /// Answer all responses on a single connection; later connections are refused
+    pub fn serve_keep_alive<S: Into<String>>(responses: impl IntoIterator<Item = S>) -> Self {
+        let responses: Vec<String> = responses.into_iter().map(Into::into).collect();
+        Self::spawn(move |listener, sender| {
+            let Ok((mut stream, _)) = listener.accept() else {
+                return;
+            };
+            drop(listener);
+            for response in responses {
+                respond(&mut stream, &response, &sender);
+            }
+        })
+    }
+
+    /// Like `serve`, over TLS with a self-signed certificate for "localhost" valid for `days_valid` more days.
+    /// Connections whose handshake fails do not use up a response.
+    pub fn serve_tls<S: Into<String>>(days_valid: i64, responses: impl IntoIterator<Item = S>) -> Self {
+        use chrono::Datelike;
+
+        let expiry = chrono::Utc::now() + chrono::Duration::days(days_valid);
+        let mut params = rcgen::CertificateParams::new(vec!["localhost".to_string()]).unwrap();
+        params.not_before = rcgen::date_time_ymd(2020, 1, 1);
+        params.not_after = rcgen::date_time_ymd(expiry.year(), expiry.month() as u8, expiry.day() as u8);
+        let key = rcgen::KeyPair::generate().unwrap();
+        let certificate = params.self_signed(&key).unwrap();
+        let identity =
+            native_tls::Identity::from_pkcs8(certificate.pem().as_bytes(), key.serialize_pem().as_bytes()).unwrap();
+        let acceptor = native_tls::TlsAcceptor::new(identity).unwrap();
+
+        let responses: Vec<String> = responses.into_iter().map(Into::into).collect();
+        Self::spawn(move |listener, sender| {
+            let mut responses = responses.into_iter();
+            let mut next = responses.next();
+            while let Some(response) = &next {
+                let Ok((stream, _)) = listener.accept() else {
+                    return;
+                };
+                if let Ok(mut stream) = acceptor.accept(stream) {
+                    respond(&mut stream, response, &sender);
+                    let _ = stream.shutdown();
+                    next = responses.next();
+                }
+            }
+        })
+    }
+
+    fn spawn(run: impl FnOnce(TcpListener, mpsc::Sender<MockRequest>) + Send + 'static) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let (sender, requests) = mpsc::channel();
+        std::thread::spawn(move || run(listener, sender));
+        Self { port, requests }
+    }
+
+    /// http:// URL of a path on the server
+    pub fn url(&self, path: &str) -> String {
+        format!("http://127.0.0.1:{}{}", self.port, path)
+    }
+
+    /// Wait for the next request
+    pub fn next_request(&self) -> MockRequest {
+        self.requests.recv().expect("the server received no further request")
+    }
+
+    /// Next request, if one was already received
+    pub fn try_next_request(&self) -> Option<MockRequest> {
+        self.requests.try_recv().ok()
+    }
+}
+
+/// Read one request and answer it; clients that close the connection first get no answer
+fn respond(stream: &mut (impl Read + Write), response: &str, sender: &mpsc::Sender<MockRequest>) {
+    if let Some(request) = read_request(stream) {
+        let _ = sender.send(request);
+    }
+    let _ = stream.write_all(response.as_bytes());
+    let _ = stream.flush();
+}
+
+/// Read until the headers and the announced body are complete
+fn read_request(stream: &mut impl Read) -> Option<MockRequest> {
+    let mut request = Vec::new();
+    let mut buffer = [0u8; 4096];
+    loop {
+        let n = stream.read(&mut buffer).ok().filter(|n| *n > 0)?;
+        request.extend_from_slice(&buffer[..n]);
+        let text = String::from_utf8_lossy(&request);
+        if let Some((head, body)) = text.split_once("\r\n\r\n") {
+            let length = head
+                .lines()
+                .find_map(|line| {
+                    let line = line.to_ascii_lowercase();
+                    line.strip_prefix("content-length:").and_then(|v| v.trim().parse::<usize>().ok())
+                })
+                .unwrap_or(0);
+            if body.len() >= length {
+                return Some(MockRequest {
+                    head: head.to_string(),
+                    body: body.to_string(),
+                });
+            }
+        }
+    }
+}
@@ -1,6 +1,31 @@
 import { invoke } from '@tauri-apps/api/core';
 import { listen, UnlistenFn } from '@tauri-apps/api/event';
-import type { Button, LogEntry, Monitor, Folder } from '../types';
+import type {
+  Button,
+  LogEntry,
+  LogBatch,
+  LogSearchQuery,
+  LogSearchPage,
+  LogExportFormat,
+  Monitor,
+  MonitorCheck,
+  MonitorStats,
+  MonitorCheckHistory,
+  MonitorStatusEvent,
+  NotificationChannel,
+  Folder,
+  PreflightReport,
+  ExecutionPreview,
+  ExecutionRecord,
+  FileTrigger,
+  SecretInfo,
+  SecretStatus,
+  RedactionConfig,
+  RetentionConfig,
+  PruneReport,
+  Schedule,
+  ScheduleInfo,
+} from '../types';
 
 // ============================================================================
 // Test API
@@ -83,8 +108,34 @@ export async function deleteFolder(id: string): Promise<void> {
 // Execution APIs
 // ============================================================================
 
-export async function executeScript(button_id: string): Promise<string> {
-  return await invoke('execute_script', { buttonId: button_id });
+export async function executeScript(
+  button_id: string,
+  confirmed = false,
+  params?: Record<string, string>
+): Promise<string> {
+  return await invoke('execute_script', { buttonId: button_id, confirmed, params });
+}
+
+export async function previewExecution(
+  button_id: string,
+  params?: Record<string, string>
+): Promise<ExecutionPreview> {
+  return await invoke('preview_execution', { buttonId: button_id, params });
+}
+
+export async function preflightScript(
+  button_id: string,
+  params?: Record<string, string>
+): Promise<PreflightReport> {
+  return await invoke('preflight_script', { buttonId: button_id, params });
+}
+
+export async function getButtonExecutions(button_id: string, limit?: number): Promise<ExecutionRecord[]> {
+  return await invoke('get_button_executions', { buttonId: button_id, limit });
+}
+
+export async function getExecutionLogs(execution_id: string): Promise<LogEntry[]> {
+  return await invoke('get_execution_logs', { executionId: execution_id });
 }
 
 // ============================================================================
@@ -99,16 +150,51 @@ export async function getLogsByButton(button_id: string): Promise<LogEntry[]> {
   return await invoke('get_logs_by_button', { buttonId: button_id });
 }
 
+// 文件已存在时报错，确认覆盖后传 overwrite = true 重试
+export async function exportLogs(
+  filter: LogSearchQuery,
+  format: LogExportFormat,
+  path: string,
+  overwrite = false
+): Promise<number> {
+  return await invoke('export_logs', { filter, format, path, overwrite });
+}
+
 export async function clearLogs(): Promise<void> {
   return await invoke('clear_logs');
 }
 
+export async function searchLogs(query: LogSearchQuery): Promise<LogSearchPage> {
+  return await invoke('search_logs', { query });
+}
+
+export async function getLogRetentionConfig(): Promise<RetentionConfig> {
+  return await invoke('get_log_retention_config');
+}
+
+export async function updateLogRetentionConfig(config: RetentionConfig): Promise<PruneReport> {
+  return await invoke('update_log_retention_config', { config });
+}
+
+export async function pruneLogs(): Promise<PruneReport> {
+  return await invoke('prune_logs');
+}
+
+export async function getLastPruneReport(): Promise<PruneReport | null> {
+  return await invoke('get_last_prune_report');
+}
+
+// 一次性重写数据库文件，之后 'incremental' 清理才会释放空间；已转换时返回 false
+export async function enableIncrementalVacuum(): Promise<boolean> {
+  return await invoke('enable_incremental_vacuum');
+}
+
 // ============================================================================
 // Event Listeners
 // ============================================================================
 
-export async function listenToLogs(callback: (log: LogEntry) => void): Promise<UnlistenFn> {
-  return await listen<LogEntry>('log-entry', (event) => {
+export async function listenToLogs(callback: (batch: LogBatch) => void): Promise<UnlistenFn> {
+  return await listen<LogBatch>('log-batch', (event) => {
     callback(event.payload);
   });
 }
@@ -117,8 +203,9 @@ export async function listenToLogs(callback: (log: LogEntry) => void): Promise<U
 // Monitor APIs
 // ============================================================================
 
-export async function createMonitor(monitor: Monitor): Promise<Monitor> {
-  return await invoke('create_monitor', { monitor });
+// 校验失败时抛出 MonitorError；需要确认的修复按钮须传 remediationConfirmed
+export async function createMonitor(monitor: Monitor, remediationConfirmed = false): Promise<Monitor> {
+  return await invoke('create_monitor', { monitor, remediationConfirmed });
 }
 
 export async function getAllMonitors(): Promise<Monitor[]> {
@@ -129,8 +216,9 @@ export async function getMonitor(id: string): Promise<Monitor> {
   return await invoke('get_monitor', { id });
 }
 
-export async function updateMonitor(id: string, monitor: Monitor): Promise<void> {
-  return await invoke('update_monitor', { id, monitor });
+// 校验失败时抛出 MonitorError；需要确认的修复按钮须传 remediationConfirmed
+export async function updateMonitor(id: string, monitor: Monitor, remediationConfirmed = false): Promise<Monitor> {
+  return await invoke('update_monitor', { id, monitor, remediationConfirmed });
 }
 
 export async function deleteMonitor(id: string): Promise<void> {
@@ -149,9 +237,31 @@ export async function getMonitorLogs(monitorId: string): Promise<LogEntry[]> {
   return await invoke('get_monitor_logs', { monitorId });
 }
 
+export async function getMonitorStats(monitorId: string, from: number, to: number): Promise<MonitorStats> {
+  return await invoke('get_monitor_stats', { monitorId, from, to });
+}
+
+export async function getMonitorChecks(
+  monitorId: string,
+  from: number,
+  to: number,
+  limit?: number
+): Promise<MonitorCheck[]> {
+  return await invoke('get_monitor_checks', { monitorId, from, to, limit });
+}
+
+export async function getMonitorCheckHistory(
+  monitorId: string,
+  from: number,
+  to: number,
+  maxPoints?: number
+): Promise<MonitorCheckHistory> {
+  return await invoke('get_monitor_check_history', { monitorId, from, to, maxPoints });
+}
+
 // Monitor event listeners
 export async function listenToMonitorStatus(
-  callback: (status: { monitor_id: string; status: string; last_check_time: number; message?: string }) => void
+  callback: (status: MonitorStatusEvent) => void
 ): Promise<UnlistenFn> {
   return await listen('monitor-status-update', (event) => {
     callback(event.payload as any);
@@ -159,9 +269,145 @@ export async function listenToMonitorStatus(
 }
 
 export async function listenToMonitorAlert(
-  callback: (alert: { monitor_id: string; status: string; last_check_time: number; message?: string }) => void
+  callback: (alert: MonitorStatusEvent) => void
 ): Promise<UnlistenFn> {
   return await listen('monitor-alert', (event) => {
     callback(event.payload as any);
   });
 }
+
+// ============================================================================
+// Secret APIs
+// ============================================================================
+
+export async function listSecrets(): Promise<SecretInfo[]> {
+  return await invoke('list_secrets');
+}
+
+export async function setSecret(name: string, value: string): Promise<void> {
+  return await invoke('set_secret', { name, value });
+}
+
+export async function deleteSecret(name: string): Promise<void> {
+  return await invoke('delete_secret', { name });
+}
+
+export async function getSecretStatus(): Promise<SecretStatus> {
+  return await invoke('get_secret_status');
+}
+
+export async function unlockSecrets(password: string): Promise<SecretStatus> {
+  return await invoke('unlock_secrets', { password });
+}
+
+export async function setMasterPassword(password: string): Promise<SecretStatus> {
+  return await invoke('set_master_password', { password });
+}
+
+// ============================================================================
+// Settings APIs
+// ============================================================================
+
+export async function getRedactionConfig(): Promise<RedactionConfig> {
+  return await invoke('get_redaction_config');
+}
+
+export async function updateRedactionConfig(config: RedactionConfig): Promise<void> {
+  return await invoke('update_redaction_config', { config });
+}
+
+export async function previewRedaction(text: string): Promise<string> {
+  return await invoke('preview_redaction', { text });
+}
+
+export async function getAutoStartMonitors(): Promise<boolean> {
+  return await invoke('get_auto_start_monitors');
+}
+
+export async function setAutoStartMonitors(enabled: boolean): Promise<void> {
+  return await invoke('set_auto_start_monitors', { enabled });
+}
+
+export async function getNotificationsEnabled(): Promise<boolean> {
+  return await invoke('get_notifications_enabled');
+}
+
+export async function setNotificationsEnabled(enabled: boolean): Promise<void> {
+  return await invoke('set_notifications_enabled', { enabled });
+}
+
+// ============================================================================
+// Notification Channel APIs
+// ============================================================================
+
+export async function listNotificationChannels(): Promise<NotificationChannel[]> {
+  return await invoke('list_notification_channels');
+}
+
+export async function createNotificationChannel(channel: NotificationChannel): Promise<NotificationChannel> {
+  return await invoke('create_notification_channel', { channel });
+}
+
+export async function updateNotificationChannel(
+  id: string,
+  channel: NotificationChannel
+): Promise<NotificationChannel> {
+  return await invoke('update_notification_channel', { id, channel });
+}
+
+export async function deleteNotificationChannel(id: string): Promise<void> {
+  return await invoke('delete_notification_channel', { id });
+}
+
+// 发送测试消息，返回尝试次数
+export async function testNotificationChannel(id: string): Promise<number> {
+  return await invoke('test_notification_channel', { id });
+}
+
+// ============================================================================
+// Schedule APIs
+// ============================================================================
+
+export async function listSchedules(): Promise<ScheduleInfo[]> {
+  return await invoke('list_schedules');
+}
+
+export async function createSchedule(schedule: Schedule, confirmed = false): Promise<ScheduleInfo> {
+  return await invoke('create_schedule', { schedule, confirmed });
+}
+
+export async function updateSchedule(id: string, schedule: Schedule, confirmed = false): Promise<ScheduleInfo> {
+  return await invoke('update_schedule', { id, schedule, confirmed });
+}
+
+export async function deleteSchedule(id: string): Promise<void> {
+  return await invoke('delete_schedule', { id });
+}
+
+export async function previewCronExpression(
+  cron_expression: string,
+  timezone: string,
+  count?: number
+): Promise<number[]> {
+  return await invoke('preview_cron_expression', { cronExpression: cron_expression, timezone, count });
+}
+
+// ============================================================================
+// File Trigger APIs
+// ============================================================================
+
+export async function listFileTriggers(): Promise<FileTrigger[]> {
+  return await invoke('list_file_triggers');
+}
+
+export async function createFileTrigger(trigger: FileTrigger, confirmed = false): Promise<FileTrigger> {
+  return await invoke('create_file_trigger', { trigger, confirmed });
+}
+
+export async function updateFileTrigger(id: string, trigger: FileTrigger, confirmed = false): Promise<FileTrigger> {
+  return await invoke('update_file_trigger', { id, trigger, confirmed });
+}
+
+export async function deleteFileTrigger(id: string): Promise<void> {
+  return await invoke('delete_file_trigger', { id });
+}
@@ -7,13 +7,14 @@ import {
   getAllButtons, 
   getAllFolders, 
   executeScript, 
+  preflightScript,
   listenToLogs, 
   listenToMonitorStatus, 
   listenToMonitorAlert,
   deleteButton,
   deleteFolder,
 } from '../../api/tauri';
-import type { Monitor, Button, Folder } from '../../types';
+import type { Monitor, Button, Folder, PreflightReport } from '../../types';
 import LogPanel from './components/LogPanel';
 import MonitorDetailsModal from './components/MonitorDetailsModal';
 import ButtonArea from './components/ButtonArea';
@@ -27,7 +28,7 @@ export default function HomePage() {
   const navigate = useNavigate();
   const { buttons, folders, setButtons, setFolders } = useLauncherStore();
   const { monitors, fetchMonitors, updateMonitorStatus } = useMonitorStore();
-  const { addLog, togglePanel } = useLogStore();
+  const { addLog, addLogs, togglePanel } = useLogStore();
   
   const [buttonStatuses, setButtonStatuses] = useState<Record<string, 'idle' | 'running' | 'success' | 'error'>>({});
   const [isLoading, setIsLoading] = useState(false);
@@ -40,6 +41,7 @@ export default function HomePage() {
   const [deletingButton, setDeletingButton] = useState<Button | null>(null);
   const [deletingFolder, setDeletingFolder] = useState<Folder | null>(null);
   const [isDeleting, setIsDeleting] = useState(false);
+  const [pendingExecution, setPendingExecution] = useState<PreflightReport | null>(null);
 
   // 初始化加载
   useEffect(() => {
@@ -76,7 +78,7 @@ export default function HomePage() {
   useEffect(() => {
     const setup = async () => {
       listenerRef.current?.();
-      listenerRef.current = await listenToLogs(addLog);
+      listenerRef.current = await listenToLogs((batch) => addLogs(batch.entries));
     };
     setup();
     return () => { listenerRef.current?.(); };
@@ -89,7 +91,8 @@ export default function HomePage() {
       });
       monitorAlertListenerRef.current = await listenToMonitorAlert((alert) => {
         if (alert.message) {
-          addLog({ id: crypto.randomUUID(), button_id: null, monitor_id: alert.monitor_id, level: 'error', message: alert.message, timestamp: alert.last_check_time });
+          const level = alert.status === 'alert' ? 'error' : alert.status === 'flapping' ? 'warning' : 'info';
+          addLog({ id: crypto.randomUUID(), button_id: null, monitor_id: alert.monitor_id, level, message: alert.message, timestamp: alert.last_check_time });
           togglePanel(true);
         }
       });
@@ -101,12 +104,36 @@ export default function HomePage() {
     };
   }, [updateMonitorStatus, addLog, togglePanel]);
 
-  // 执行脚本
+  // 执行前检查：需要确认时先弹窗
   const handleExecute = async (buttonId: string) => {
+    try {
+      const report = await preflightScript(buttonId);
+      if (report.requires_confirmation) {
+        setPendingExecution(report);
+        return;
+      }
+    } catch (error) {
+      addLog({ id: crypto.randomUUID(), button_id: buttonId, monitor_id: null, level: 'error', message: `执行前检查失败: ${error}`, timestamp: Math.floor(Date.now() / 1000) });
+      return;
+    }
+    await runScript(buttonId, false);
+  };
+
+  const getConfirmMessage = (report: PreflightReport) => {
+    const name = buttons.find(b => b.id === report.button_id)?.name ?? '';
+    const reasons = report.findings.map(f => `第 ${f.line} 行: ${f.description} (${f.snippet})`);
+    if (report.flagged_by_button) {
+      reasons.unshift('该按钮已设置为执行前需要确认');
+    }
+    return `确定要执行 "${name}" 吗？\n${reasons.join('\n')}`;
+  };
+
+  // 执行脚本
+  const runScript = async (buttonId: string, confirmed: boolean) => {
     try {
       setButtonStatuses(prev => ({ ...prev, [buttonId]: 'running' }));
       togglePanel(true);
-      await executeScript(buttonId);
+      await executeScript(buttonId, confirmed);
       setTimeout(() => {
         setButtonStatuses(prev => ({ ...prev, [buttonId]: 'success' }));
         setTimeout(() => setButtonStatuses(prev => ({ ...prev, [buttonId]: 'idle' })), 3000);
@@ -241,6 +268,21 @@ export default function HomePage() {
         onConfirm={handleDeleteFolder}
         onCancel={() => setDeletingFolder(null)}
       />
+
+      <ConfirmModal
+        isOpen={!!pendingExecution}
+        title="确认执行"
+        message={pendingExecution ? getConfirmMessage(pendingExecution) : ''}
+        confirmText="执行"
+        danger={!!pendingExecution?.findings.length}
+        onConfirm={() => {
+          if (pendingExecution) {
+            runScript(pendingExecution.button_id, true);
+          }
+          setPendingExecution(null);
+        }}
+        onCancel={() => setPendingExecution(null)}
+      />
     </div>
   );
 }
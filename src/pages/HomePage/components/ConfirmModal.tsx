@@ -38,7 +38,7 @@ export default function ConfirmModal({
         onClick={(e) => e.stopPropagation()}
       >
         <h3 className="font-bold text-xl mb-3">{title}</h3>
-        <p className="text-base-content/70 mb-6">{message}</p>
+        <p className="text-base-content/70 mb-6 whitespace-pre-line">{message}</p>
 
         <div className="flex justify-end gap-3">
           <button
@@ -27,6 +27,12 @@ export default function MonitorCard({ monitor, onShowDetails }: MonitorCardProps
             <path strokeLinecap="round" strokeLinejoin="round" strokeWidth={2} d="M8 9l3 3-3 3m5 0h3M5 20h14a2 2 0 002-2V6a2 2 0 00-2-2H5a2 2 0 00-2 2v12a2 2 0 002 2z" />
           </svg>
         );
+      case 'certificate':
+        return (
+          <svg className="w-12 h-12" fill="none" stroke="currentColor" viewBox="0 0 24 24">
+            <path strokeLinecap="round" strokeLinejoin="round" strokeWidth={2} d="M12 15v2m-6 4h12a2 2 0 002-2v-6a2 2 0 00-2-2H6a2 2 0 00-2 2v6a2 2 0 002 2zm10-10V7a4 4 0 00-8 0v4h8z" />
+          </svg>
+        );
       default:
         return (
           <svg className="w-12 h-12" fill="none" stroke="currentColor" viewBox="0 0 24 24">
@@ -46,6 +52,14 @@ export default function MonitorCard({ monitor, onShowDetails }: MonitorCardProps
           text: '运行中',
           color: 'text-success',
         };
+      case 'degraded':
+        return {
+          icon: (
+            <div className="w-2 h-2 rounded-full bg-warning"></div>
+          ),
+          text: '延迟高',
+          color: 'text-warning',
+        };
       case 'error':
         return {
           icon: (
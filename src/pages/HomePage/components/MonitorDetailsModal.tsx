@@ -17,6 +17,8 @@ export default function MonitorDetailsModal({ monitor, onClose }: MonitorDetails
         return '🌐';
       case 'port':
         return '🔌';
+      case 'certificate':
+        return '🔒';
       default:
         return '📊';
     }
@@ -30,6 +32,8 @@ export default function MonitorDetailsModal({ monitor, onClose }: MonitorDetails
         return 'API监控';
       case 'port':
         return '端口监控';
+      case 'certificate':
+        return '证书监控';
       default:
         return monitor.monitor_type;
     }
@@ -43,6 +47,8 @@ export default function MonitorDetailsModal({ monitor, onClose }: MonitorDetails
     switch (monitor.last_status) {
       case 'running':
         return <span className="badge badge-success">运行中</span>;
+      case 'degraded':
+        return <span className="badge badge-warning">延迟高</span>;
       case 'error':
         return <span className="badge badge-error">异常</span>;
       case 'checking':
@@ -58,17 +64,11 @@ export default function MonitorDetailsModal({ monitor, onClose }: MonitorDetails
   };
 
   const getTargetDisplay = () => {
-    if (monitor.monitor_type === 'process') {
-      return monitor.target;
-    } else if (monitor.monitor_type === 'api') {
-      try {
-        const config = JSON.parse(monitor.target);
-        return `${config.method || 'GET'} ${config.url}`;
-      } catch {
-        return monitor.target;
-      }
-    } else if (monitor.monitor_type === 'port') {
-      return monitor.target;
+    if (monitor.config?.type === 'api') {
+      return `${monitor.config.method} ${monitor.config.url}`;
+    }
+    if (monitor.config?.type === 'certificate') {
+      return `${monitor.target}（提前 ${monitor.config.warn_days} 天告警）`;
     }
     return monitor.target;
   };
@@ -154,9 +154,13 @@ export default function MonitorDetailsModal({ monitor, onClose }: MonitorDetails
                 {monitor.last_status && (
                   <div className="flex justify-between">
                     <span className="text-base-content/60">检查结果:</span>
-                    <span className={monitor.last_status === 'running' ? 'text-success' : 'text-error'}>
-                      {monitor.last_status === 'running' ? '正常' : '异常'}
-                    </span>
+                    {monitor.last_status === 'degraded' ? (
+                      <span className="text-warning">正常（延迟高）</span>
+                    ) : (
+                      <span className={monitor.last_status === 'running' ? 'text-success' : 'text-error'}>
+                        {monitor.last_status === 'running' ? '正常' : '异常'}
+                      </span>
+                    )}
                   </div>
                 )}
               </div>
@@ -67,6 +67,8 @@ function MonitorCardContent({
     switch (monitor.last_status) {
       case 'running':
         return { color: 'bg-success', text: '运行中', animate: 'animate-pulse' };
+      case 'degraded':
+        return { color: 'bg-warning', text: '延迟高', animate: '' };
       case 'error':
         return { color: 'bg-error', text: '异常', animate: 'animate-blink' };
       case 'checking':
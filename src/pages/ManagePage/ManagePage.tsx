@@ -143,7 +143,9 @@ export default function ManagePage() {
       setEditingMonitor(null);
     } catch (error) {
       console.error('保存监控失败:', error);
-      alert('保存监控失败: ' + error);
+      // 校验失败时后端返回 MonitorError，message 中列出了各字段的问题
+      const message = typeof error === 'object' && error !== null && 'message' in error ? error.message : error;
+      alert('保存监控失败: ' + message);
     }
   };
 
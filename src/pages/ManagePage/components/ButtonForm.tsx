@@ -12,6 +12,12 @@ export default function ButtonForm({ button, onSave, onCancel }: ButtonFormProps
   const [icon, setIcon] = useState('');
   const [scriptType, setScriptType] = useState<'shell' | 'python' | 'javascript'>('shell');
   const [scriptContent, setScriptContent] = useState('');
+  const [requiresConfirmation, setRequiresConfirmation] = useState(false);
+  const [notificationsMuted, setNotificationsMuted] = useState(false);
+  const [retryEnabled, setRetryEnabled] = useState(false);
+  const [maxAttempts, setMaxAttempts] = useState(3);
+  const [backoffSeconds, setBackoffSeconds] = useState(5);
+  const [retryExitCodes, setRetryExitCodes] = useState('');
   const [errors, setErrors] = useState<{ name?: string; scriptContent?: string }>({});
 
   useEffect(() => {
@@ -20,6 +26,14 @@ export default function ButtonForm({ button, onSave, onCancel }: ButtonFormProps
       setIcon(button.icon || '');
       setScriptType(button.script_type as 'shell' | 'python' | 'javascript');
       setScriptContent(button.script_content);
+      setRequiresConfirmation(button.requires_confirmation ?? false);
+      setNotificationsMuted(button.notifications_muted ?? false);
+      setRetryEnabled(!!button.retry_policy);
+      if (button.retry_policy) {
+        setMaxAttempts(button.retry_policy.max_attempts);
+        setBackoffSeconds(button.retry_policy.backoff_ms / 1000);
+        setRetryExitCodes(button.retry_policy.retry_exit_codes.join(', '));
+      }
     }
   }, [button]);
 
@@ -52,6 +66,19 @@ export default function ButtonForm({ button, onSave, onCancel }: ButtonFormProps
       script_content: scriptContent.trim(),
       folder_id: null,
       position: button?.position || 0,
+      requires_confirmation: requiresConfirmation,
+      notifications_muted: notificationsMuted,
+      retry_policy: retryEnabled
+        ? {
+            max_attempts: maxAttempts,
+            backoff_ms: Math.round(backoffSeconds * 1000),
+            backoff_multiplier: 2,
+            retry_exit_codes: retryExitCodes
+              .split(',')
+              .map((code) => parseInt(code.trim(), 10))
+              .filter((code) => !isNaN(code)),
+          }
+        : null,
     });
   };
 
@@ -147,6 +174,87 @@ export default function ButtonForm({ button, onSave, onCancel }: ButtonFormProps
               </label>
             </div>
           </div>
+
+          {/* 执行确认 */}
+          <div className="form-control">
+            <label className="label cursor-pointer justify-start gap-2">
+              <input
+                type="checkbox"
+                className="checkbox checkbox-primary"
+                checked={requiresConfirmation}
+                onChange={(e) => setRequiresConfirmation(e.target.checked)}
+              />
+              <span className="label-text">执行前需要确认</span>
+            </label>
+          </div>
+
+          {/* 桌面通知 */}
+          <div className="form-control">
+            <label className="label cursor-pointer justify-start gap-2">
+              <input
+                type="checkbox"
+                className="checkbox checkbox-primary"
+                checked={notificationsMuted}
+                onChange={(e) => setNotificationsMuted(e.target.checked)}
+              />
+              <span className="label-text">静音桌面通知</span>
+            </label>
+          </div>
+
+          {/* 失败重试 */}
+          <div className="form-control">
+            <label className="label cursor-pointer justify-start gap-2">
+              <input
+                type="checkbox"
+                className="checkbox checkbox-primary"
+                checked={retryEnabled}
+                onChange={(e) => setRetryEnabled(e.target.checked)}
+              />
+              <span className="label-text">失败时自动重试</span>
+            </label>
+          </div>
+
+          {retryEnabled && (
+            <div className="grid grid-cols-3 gap-2">
+              <div className="form-control">
+                <label className="label">
+                  <span className="label-text">最多尝试次数</span>
+                </label>
+                <input
+                  type="number"
+                  min={1}
+                  max={10}
+                  className="input input-bordered input-sm"
+                  value={maxAttempts}
+                  onChange={(e) => setMaxAttempts(Number(e.target.value))}
+                />
+              </div>
+              <div className="form-control">
+                <label className="label">
+                  <span className="label-text">首次重试间隔（秒）</span>
+                </label>
+                <input
+                  type="number"
+                  min={0}
+                  className="input input-bordered input-sm"
+                  value={backoffSeconds}
+                  onChange={(e) => setBackoffSeconds(Number(e.target.value))}
+                />
+              </div>
+              <div className="form-control">
+                <label className="label">
+                  <span className="label-text">仅重试退出码</span>
+                </label>
+                <input
+                  type="text"
+                  placeholder="留空表示任意失败"
+                  className="input input-bordered input-sm"
+                  value={retryExitCodes}
+                  onChange={(e) => setRetryExitCodes(e.target.value)}
+                />
+              </div>
+            </div>
+          )}
         </div>
       </div>
 
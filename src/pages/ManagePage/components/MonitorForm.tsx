@@ -1,5 +1,6 @@
 import { useState, useEffect } from 'react';
-import type { Monitor } from '../../../types';
+import type { ApiAssertion, ApiAuth, Button, HttpOptions, Monitor, MonitorConfig, TlsOptions } from '../../../types';
+import { getAllButtons } from '../../../api/tauri';
 
 interface MonitorFormProps {
   monitor?: Monitor | null;
@@ -9,9 +10,19 @@ interface MonitorFormProps {
 
 export default function MonitorForm({ monitor, onSave, onCancel }: MonitorFormProps) {
   const [name, setName] = useState('');
-  const [monitorType, setMonitorType] = useState<'process' | 'api' | 'port'>('process');
+  const [monitorType, setMonitorType] = useState<'process' | 'api' | 'port' | 'certificate'>('process');
   const [checkInterval, setCheckInterval] = useState(60);
   const [alertOnFailure, setAlertOnFailure] = useState(true);
+  const [degradedThreshold, setDegradedThreshold] = useState('');
+  const [alertThreshold, setAlertThreshold] = useState(1);
+  const [alertRenotifyMinutes, setAlertRenotifyMinutes] = useState('');
+  const [notificationsMuted, setNotificationsMuted] = useState(false);
+
+  // Remediation fields
+  const [buttons, setButtons] = useState<Button[]>([]);
+  const [remediationButtonId, setRemediationButtonId] = useState('');
+  const [remediationThreshold, setRemediationThreshold] = useState(3);
+  const [remediationCooldown, setRemediationCooldown] = useState(300);
 
   // Process monitoring fields
   const [targetType, setTargetType] = useState<'name' | 'pid'>('name');
@@ -24,48 +35,68 @@ export default function MonitorForm({ monitor, onSave, onCancel }: MonitorFormPr
   const [headers, setHeaders] = useState<Array<{ key: string; value: string }>>([]);
   const [requestBody, setRequestBody] = useState('');
   const [expectedContent, setExpectedContent] = useState('');
+  const [assertions, setAssertions] = useState<ApiAssertion[]>([]);
+  const [timeoutSecs, setTimeoutSecs] = useState(10);
+  const [auth, setAuth] = useState<ApiAuth | null>(null);
+  const [tls, setTls] = useState<TlsOptions>({ accept_invalid_certs: false });
+  const [http, setHttp] = useState<HttpOptions>({ max_redirects: 10, http_version: 'auto' });
 
   // Port monitoring fields
   const [portTarget, setPortTarget] = useState('');
 
+  // Certificate monitoring fields
+  const [certHost, setCertHost] = useState('');
+  const [certPort, setCertPort] = useState(443);
+  const [warnDays, setWarnDays] = useState(14);
+
   const [errors, setErrors] = useState<Record<string, string>>({});
 
+  useEffect(() => {
+    getAllButtons()
+      .then(setButtons)
+      .catch((error) => console.error('Failed to load buttons:', error));
+  }, []);
+
   useEffect(() => {
     if (monitor) {
       setName(monitor.name);
       setMonitorType(monitor.monitor_type);
       setCheckInterval(monitor.check_interval);
       setAlertOnFailure(monitor.alert_on_failure);
+      setDegradedThreshold(monitor.degraded_threshold_ms ? String(monitor.degraded_threshold_ms) : '');
+      setAlertThreshold(monitor.alert_threshold ?? 1);
+      setAlertRenotifyMinutes(monitor.alert_renotify_secs ? String(monitor.alert_renotify_secs / 60) : '');
+      setNotificationsMuted(monitor.notifications_muted ?? false);
+      setRemediationButtonId(monitor.remediation_button_id || '');
+      setRemediationThreshold(monitor.remediation_threshold ?? 3);
+      setRemediationCooldown(monitor.remediation_cooldown_secs ?? 300);
       setExpectedContent(monitor.expected_result || '');
 
-      if (monitor.monitor_type === 'process') {
-        // Parse process target
-        const target = monitor.target;
-        if (/^\d+$/.test(target)) {
+      const config = monitor.config;
+      if (config?.type === 'process') {
+        if (config.pid != null) {
           setTargetType('pid');
-          setProcessPid(target);
+          setProcessPid(String(config.pid));
         } else {
           setTargetType('name');
-          setProcessName(target);
-        }
-      } else if (monitor.monitor_type === 'api') {
-        // Parse API target (could be JSON or simple URL)
-        try {
-          const config = JSON.parse(monitor.target);
-          setApiUrl(config.url || '');
-          setHttpMethod(config.method || 'GET');
-          setRequestBody(config.body || '');
-          if (config.headers && Array.isArray(config.headers)) {
-            setHeaders(config.headers);
-          }
-        } catch {
-          // Simple URL string
-          setApiUrl(monitor.target);
-          setHttpMethod('GET');
+          setProcessName(config.name || '');
         }
-      } else if (monitor.monitor_type === 'port') {
-        // Parse port target
-        setPortTarget(monitor.target);
+      } else if (config?.type === 'api') {
+        setApiUrl(config.url);
+        setHttpMethod(config.method || 'GET');
+        setRequestBody(config.body || '');
+        setHeaders(config.headers || []);
+        setAssertions(config.assertions || []);
+        setTimeoutSecs(config.timeout_secs || 10);
+        setAuth(config.auth ?? null);
+        setTls(config.tls ?? { accept_invalid_certs: false });
+        setHttp(config.http ?? { max_redirects: 10, http_version: 'auto' });
+      } else if (config?.type === 'port') {
+        setPortTarget(`${config.host}:${config.port}`);
+      } else if (config?.type === 'certificate') {
+        setCertHost(config.host);
+        setCertPort(config.port);
+        setWarnDays(config.warn_days);
       }
     }
   }, [monitor]);
@@ -102,6 +133,27 @@ export default function MonitorForm({ monitor, onSave, onCancel }: MonitorFormPr
           newErrors.apiUrl = 'API URL格式不正确';
         }
       }
+      if (auth) {
+        const credential =
+          auth.type === 'bearer'
+            ? auth.token
+            : auth.type === 'basic'
+              ? auth.password
+              : auth.type === 'api_key'
+                ? auth.value
+                : auth.client_secret;
+        if (!credential.includes('${secret:')) {
+          newErrors.auth = '凭据请先保存为密钥，再以 ${secret:NAME} 引用';
+        }
+      }
+      if (!tls.client_cert !== !tls.client_key) {
+        newErrors.tls = '客户端证书和私钥需同时填写';
+      }
+      if (http.proxy?.trim() && !/^https?:\/\//.test(http.proxy.trim()) && !http.proxy.includes('${secret:')) {
+        newErrors.http = '代理地址需以 http:// 或 https:// 开头';
+      } else if (http.max_redirects < 0 || http.max_redirects > 20) {
+        newErrors.http = '重定向次数必须在 0-20 之间';
+      }
     } else if (monitorType === 'port') {
       if (!portTarget.trim()) {
         newErrors.portTarget = '端口目标不能为空';
@@ -127,6 +179,16 @@ export default function MonitorForm({ monitor, onSave, onCancel }: MonitorFormPr
           newErrors.portTarget = '格式错误，应为 "端口" 或 "主机:端口"';
         }
       }
+    } else if (monitorType === 'certificate') {
+      if (!certHost.trim()) {
+        newErrors.certHost = '主机名不能为空';
+      }
+      if (certPort < 1 || certPort > 65535) {
+        newErrors.certHost = '端口号必须在 1-65535 之间';
+      }
+      if (warnDays < 1) {
+        newErrors.warnDays = '提前告警天数至少为 1 天';
+      }
     }
 
     setErrors(newErrors);
@@ -140,31 +202,45 @@ export default function MonitorForm({ monitor, onSave, onCancel }: MonitorFormPr
       return;
     }
 
-    let target = '';
+    let config: MonitorConfig;
     let icon = '📊';
 
     if (monitorType === 'process') {
-      target = targetType === 'pid' ? processPid.trim() : processName.trim();
+      config =
+        targetType === 'pid'
+          ? { type: 'process', pid: parseInt(processPid.trim(), 10) }
+          : { type: 'process', name: processName.trim() };
       icon = '📊';
     } else if (monitorType === 'api') {
-      // Build API config JSON
-      const config: any = {
+      config = {
+        type: 'api',
         url: apiUrl.trim(),
         method: httpMethod,
+        headers: headers.filter((h) => h.key.trim() && h.value.trim()),
+        body: requestBody.trim() && ['POST', 'PUT', 'PATCH'].includes(httpMethod) ? requestBody.trim() : null,
+        assertions,
+        timeout_secs: timeoutSecs,
+        auth,
+        tls: {
+          accept_invalid_certs: tls.accept_invalid_certs,
+          ca_bundle: tls.ca_bundle?.trim() || null,
+          client_cert: tls.client_cert?.trim() || null,
+          client_key: tls.client_key?.trim() || null,
+        },
+        http: { ...http, proxy: http.proxy?.trim() || null },
       };
-
-      if (headers.length > 0) {
-        config.headers = headers.filter(h => h.key.trim() && h.value.trim());
-      }
-
-      if (requestBody.trim() && ['POST', 'PUT', 'PATCH'].includes(httpMethod)) {
-        config.body = requestBody.trim();
-      }
-
-      target = JSON.stringify(config);
       icon = '🌐';
-    } else if (monitorType === 'port') {
-      target = portTarget.trim();
+    } else if (monitorType === 'certificate') {
+      config = { type: 'certificate', host: certHost.trim(), port: certPort, warn_days: warnDays };
+      icon = '🔒';
+    } else {
+      // "端口" 或 "主机:端口"
+      const value = portTarget.trim();
+      const separator = value.lastIndexOf(':');
+      config =
+        separator >= 0
+          ? { type: 'port', host: value.slice(0, separator), port: parseInt(value.slice(separator + 1), 10) }
+          : { type: 'port', host: '127.0.0.1', port: parseInt(value, 10) };
       icon = '🔌';
     }
 
@@ -172,7 +248,8 @@ export default function MonitorForm({ monitor, onSave, onCancel }: MonitorFormPr
       name: name.trim(),
       icon,
       monitor_type: monitorType,
-      target,
+      target: monitor?.target ?? '', // 由后端根据 config 生成
+      config,
       check_interval: checkInterval,
       expected_result: expectedContent.trim() || null,
       alert_on_failure: alertOnFailure,
@@ -181,6 +258,14 @@ export default function MonitorForm({ monitor, onSave, onCancel }: MonitorFormPr
       last_status: null,
       folder_id: monitor?.folder_id ?? null,
       position: monitor?.position ?? 0,
+      remediation_button_id: remediationButtonId || null,
+      remediation_threshold: remediationThreshold,
+      remediation_cooldown_secs: remediationCooldown,
+      degraded_threshold_ms:
+        monitorType !== 'process' && Number(degradedThreshold) > 0 ? Number(degradedThreshold) : null,
+      alert_threshold: Math.max(1, alertThreshold),
+      alert_renotify_secs: Number(alertRenotifyMinutes) > 0 ? Math.round(Number(alertRenotifyMinutes) * 60) : null,
+      notifications_muted: notificationsMuted,
     });
   };
 
@@ -198,6 +283,52 @@ export default function MonitorForm({ monitor, onSave, onCancel }: MonitorFormPr
     setHeaders(newHeaders);
   };
 
+  const newAssertion = (type: ApiAssertion['type']): ApiAssertion => {
+    switch (type) {
+      case 'status':
+        return { type, codes: ['2xx'] };
+      case 'json_path':
+        return { type, path: '$.' };
+      case 'regex':
+        return { type, pattern: '' };
+      case 'header':
+        return { type, name: '' };
+      case 'max_size':
+        return { type, bytes: 1048576 };
+    }
+  };
+
+  const newAuth = (type: ApiAuth['type'] | ''): ApiAuth | null => {
+    switch (type) {
+      case 'bearer':
+        return { type, token: '' };
+      case 'basic':
+        return { type, username: '', password: '' };
+      case 'api_key':
+        return { type, header: 'X-API-Key', value: '' };
+      case 'oauth2':
+        return { type, token_url: '', client_id: '', client_secret: '', scope: null };
+      default:
+        return null;
+    }
+  };
+
+  const updateAssertion = (index: number, assertion: ApiAssertion) => {
+    setAssertions(assertions.map((a, i) => (i === index ? assertion : a)));
+  };
+
+  // 期望值按 JSON 解析，解析失败时作为字符串
+  const parseExpectedValue = (text: string): unknown => {
+    if (!text.trim()) {
+      return undefined;
+    }
+    try {
+      return JSON.parse(text);
+    } catch {
+      return text;
+    }
+  };
+
   return (
     <form onSubmit={handleSubmit} className="space-y-4">
       {/* 基本信息 */}
@@ -260,6 +391,16 @@ export default function MonitorForm({ monitor, onSave, onCancel }: MonitorFormPr
                 />
                 <span className="label-text">🔌 端口监控</span>
               </label>
+              <label className="label cursor-pointer gap-2">
+                <input
+                  type="radio"
+                  name="monitorType"
+                  className="radio radio-primary"
+                  checked={monitorType === 'certificate'}
+                  onChange={() => setMonitorType('certificate')}
+                />
+                <span className="label-text">🔒 证书监控</span>
+              </label>
             </div>
           </div>
 
@@ -283,6 +424,26 @@ export default function MonitorForm({ monitor, onSave, onCancel }: MonitorFormPr
             )}
           </div>
 
+          {/* 延迟阈值（进程监控没有延迟） */}
+          {monitorType !== 'process' && (
+            <div className="form-control">
+              <label className="label">
+                <span className="label-text">延迟告警阈值（毫秒）</span>
+              </label>
+              <input
+                type="number"
+                min={1}
+                placeholder="不设置"
+                className="input input-bordered"
+                value={degradedThreshold}
+                onChange={(e) => setDegradedThreshold(e.target.value)}
+              />
+              <label className="label">
+                <span className="label-text-alt">检查成功但耗时超过该值时显示为"延迟高"</span>
+              </label>
+            </div>
+          )}
+
           {/* 失败时告警 */}
           <div className="form-control">
             <label className="label cursor-pointer">
@@ -295,6 +456,96 @@ export default function MonitorForm({ monitor, onSave, onCancel }: MonitorFormPr
               />
             </label>
           </div>
+
+          {alertOnFailure && (
+            <div className="grid grid-cols-2 gap-2">
+              <div className="form-control">
+                <label className="label">
+                  <span className="label-text">连续失败几次后告警</span>
+                </label>
+                <input
+                  type="number"
+                  min={1}
+                  className="input input-bordered input-sm"
+                  value={alertThreshold}
+                  onChange={(e) => setAlertThreshold(Number(e.target.value))}
+                />
+              </div>
+              <div className="form-control">
+                <label className="label">
+                  <span className="label-text">重复告警间隔（分钟）</span>
+                </label>
+                <input
+                  type="number"
+                  min={1}
+                  placeholder="只告警一次"
+                  className="input input-bordered input-sm"
+                  value={alertRenotifyMinutes}
+                  onChange={(e) => setAlertRenotifyMinutes(e.target.value)}
+                />
+              </div>
+              <div className="form-control col-span-2">
+                <label className="label cursor-pointer justify-start gap-2">
+                  <input
+                    type="checkbox"
+                    className="checkbox checkbox-primary checkbox-sm"
+                    checked={notificationsMuted}
+                    onChange={(e) => setNotificationsMuted(e.target.checked)}
+                  />
+                  <span className="label-text">静音桌面通知</span>
+                </label>
+              </div>
+            </div>
+          )}
+
+          {/* 自动修复 */}
+          <div className="form-control">
+            <label className="label">
+              <span className="label-text">连续失败后自动执行按钮</span>
+            </label>
+            <select
+              className="select select-bordered"
+              value={remediationButtonId}
+              onChange={(e) => setRemediationButtonId(e.target.value)}
+            >
+              <option value="">不执行</option>
+              {buttons.map((button) => (
+                <option key={button.id} value={button.id}>
+                  {button.icon ? `${button.icon} ` : ''}
+                  {button.name}
+                </option>
+              ))}
+            </select>
+          </div>
+
+          {remediationButtonId && (
+            <div className="grid grid-cols-2 gap-2">
+              <div className="form-control">
+                <label className="label">
+                  <span className="label-text">连续失败次数</span>
+                </label>
+                <input
+                  type="number"
+                  min={1}
+                  className="input input-bordered input-sm"
+                  value={remediationThreshold}
+                  onChange={(e) => setRemediationThreshold(Number(e.target.value))}
+                />
+              </div>
+              <div className="form-control">
+                <label className="label">
+                  <span className="label-text">冷却时间（秒）</span>
+                </label>
+                <input
+                  type="number"
+                  min={0}
+                  className="input input-bordered input-sm"
+                  value={remediationCooldown}
+                  onChange={(e) => setRemediationCooldown(Number(e.target.value))}
+                />
+              </div>
+            </div>
+          )}
         </div>
       </div>
 
@@ -421,6 +672,21 @@ export default function MonitorForm({ monitor, onSave, onCancel }: MonitorFormPr
               </select>
             </div>
 
+            {/* 超时 */}
+            <div className="form-control">
+              <label className="label">
+                <span className="label-text">请求超时（秒）</span>
+              </label>
+              <input
+                type="number"
+                min={1}
+                max={300}
+                className="input input-bordered"
+                value={timeoutSecs}
+                onChange={(e) => setTimeoutSecs(parseInt(e.target.value) || 10)}
+              />
+            </div>
+
             {/* 请求头 */}
             <div className="form-control">
               <label className="label">
@@ -458,6 +724,203 @@ export default function MonitorForm({ monitor, onSave, onCancel }: MonitorFormPr
               </div>
             </div>
 
+            {/* 认证 */}
+            <div className="form-control">
+              <label className="label">
+                <span className="label-text">认证方式</span>
+              </label>
+              <select
+                className="select select-bordered"
+                value={auth?.type ?? ''}
+                onChange={(e) => setAuth(newAuth(e.target.value as ApiAuth['type'] | ''))}
+              >
+                <option value="">无</option>
+                <option value="bearer">Bearer Token</option>
+                <option value="basic">Basic Auth</option>
+                <option value="api_key">API Key 请求头</option>
+                <option value="oauth2">OAuth2 Client Credentials</option>
+              </select>
+              {auth?.type === 'bearer' && (
+                <input
+                  type="text"
+                  placeholder="Token，如 ${secret:API_TOKEN}"
+                  className="input input-bordered input-sm font-mono mt-2"
+                  value={auth.token}
+                  onChange={(e) => setAuth({ ...auth, token: e.target.value })}
+                />
+              )}
+              {auth?.type === 'basic' && (
+                <div className="flex gap-2 mt-2">
+                  <input
+                    type="text"
+                    placeholder="用户名"
+                    className="input input-bordered input-sm flex-1"
+                    value={auth.username}
+                    onChange={(e) => setAuth({ ...auth, username: e.target.value })}
+                  />
+                  <input
+                    type="text"
+                    placeholder="密码，如 ${secret:API_PASSWORD}"
+                    className="input input-bordered input-sm flex-1 font-mono"
+                    value={auth.password}
+                    onChange={(e) => setAuth({ ...auth, password: e.target.value })}
+                  />
+                </div>
+              )}
+              {auth?.type === 'api_key' && (
+                <div className="flex gap-2 mt-2">
+                  <input
+                    type="text"
+                    placeholder="请求头名称"
+                    className="input input-bordered input-sm flex-1"
+                    value={auth.header}
+                    onChange={(e) => setAuth({ ...auth, header: e.target.value })}
+                  />
+                  <input
+                    type="text"
+                    placeholder="Key，如 ${secret:API_KEY}"
+                    className="input input-bordered input-sm flex-1 font-mono"
+                    value={auth.value}
+                    onChange={(e) => setAuth({ ...auth, value: e.target.value })}
+                  />
+                </div>
+              )}
+              {auth?.type === 'oauth2' && (
+                <div className="space-y-2 mt-2">
+                  <input
+                    type="text"
+                    placeholder="Token URL"
+                    className="input input-bordered input-sm w-full"
+                    value={auth.token_url}
+                    onChange={(e) => setAuth({ ...auth, token_url: e.target.value })}
+                  />
+                  <div className="flex gap-2">
+                    <input
+                      type="text"
+                      placeholder="Client ID"
+                      className="input input-bordered input-sm flex-1"
+                      value={auth.client_id}
+                      onChange={(e) => setAuth({ ...auth, client_id: e.target.value })}
+                    />
+                    <input
+                      type="text"
+                      placeholder="Client Secret，如 ${secret:CLIENT_SECRET}"
+                      className="input input-bordered input-sm flex-1 font-mono"
+                      value={auth.client_secret}
+                      onChange={(e) => setAuth({ ...auth, client_secret: e.target.value })}
+                    />
+                  </div>
+                  <input
+                    type="text"
+                    placeholder="Scope（可选）"
+                    className="input input-bordered input-sm w-full"
+                    value={auth.scope ?? ''}
+                    onChange={(e) => setAuth({ ...auth, scope: e.target.value || null })}
+                  />
+                </div>
+              )}
+              <label className="label">
+                {errors.auth ? (
+                  <span className="label-text-alt text-error">{errors.auth}</span>
+                ) : (
+                  <span className="label-text-alt">
+                    凭据以 {'${secret:NAME}'} 引用密钥，检查时才解析；OAuth2 令牌会缓存，过期前或返回 401 时自动刷新
+                  </span>
+                )}
+              </label>
+            </div>
+
+            {/* TLS */}
+            <div className="form-control">
+              <label className="label">
+                <span className="label-text">TLS 设置</span>
+              </label>
+              <label className="label cursor-pointer justify-start gap-2">
+                <input
+                  type="checkbox"
+                  className="checkbox checkbox-sm"
+                  checked={tls.accept_invalid_certs}
+                  onChange={(e) => setTls({ ...tls, accept_invalid_certs: e.target.checked })}
+                />
+                <span className="label-text">接受无效证书（自签名、过期或域名不匹配）</span>
+              </label>
+              <div className="space-y-2">
+                <input
+                  type="text"
+                  placeholder="CA 证书文件路径（可选）"
+                  className="input input-bordered input-sm w-full font-mono"
+                  value={tls.ca_bundle ?? ''}
+                  onChange={(e) => setTls({ ...tls, ca_bundle: e.target.value || null })}
+                />
+                <div className="flex gap-2">
+                  <input
+                    type="text"
+                    placeholder="客户端证书文件路径（mTLS）"
+                    className="input input-bordered input-sm flex-1 font-mono"
+                    value={tls.client_cert ?? ''}
+                    onChange={(e) => setTls({ ...tls, client_cert: e.target.value || null })}
+                  />
+                  <input
+                    type="text"
+                    placeholder="客户端私钥文件路径（PKCS#8）"
+                    className="input input-bordered input-sm flex-1 font-mono"
+                    value={tls.client_key ?? ''}
+                    onChange={(e) => setTls({ ...tls, client_key: e.target.value || null })}
+                  />
+                </div>
+              </div>
+              <label className="label">
+                {errors.tls ? (
+                  <span className="label-text-alt text-error">{errors.tls}</span>
+                ) : (
+                  <span className="label-text-alt">文件均为 PEM 格式，CA 文件可包含多个证书</span>
+                )}
+              </label>
+            </div>
+
+            {/* 连接设置 */}
+            <div className="form-control">
+              <label className="label">
+                <span className="label-text">连接设置</span>
+              </label>
+              <input
+                type="text"
+                placeholder="代理地址（可选），如 http://proxy.local:3128"
+                className="input input-bordered input-sm w-full font-mono"
+                value={http.proxy ?? ''}
+                onChange={(e) => setHttp({ ...http, proxy: e.target.value || null })}
+              />
+              <div className="flex gap-2 mt-2">
+                <label className="input input-bordered input-sm flex items-center gap-2 flex-1">
+                  <span className="text-sm opacity-70">最多重定向</span>
+                  <input
+                    type="number"
+                    min={0}
+                    max={20}
+                    className="grow"
+                    value={http.max_redirects}
+                    onChange={(e) => setHttp({ ...http, max_redirects: parseInt(e.target.value) || 0 })}
+                  />
+                </label>
+                <select
+                  className="select select-bordered select-sm flex-1"
+                  value={http.http_version}
+                  onChange={(e) => setHttp({ ...http, http_version: e.target.value as HttpOptions['http_version'] })}
+                >
+                  <option value="auto">HTTP 版本：自动</option>
+                  <option value="http1">仅 HTTP/1.1</option>
+                  <option value="http2">HTTP/2（不协商）</option>
+                </select>
+              </div>
+              <label className="label">
+                {errors.http ? (
+                  <span className="label-text-alt text-error">{errors.http}</span>
+                ) : (
+                  <span className="label-text-alt">代理为空时使用系统代理；重定向为 0 时直接检查 3xx 响应</span>
+                )}
+              </label>
+            </div>
+
             {/* 请求体 */}
             {['POST', 'PUT', 'PATCH'].includes(httpMethod) && (
               <div className="form-control">
@@ -489,6 +952,124 @@ export default function MonitorForm({ monitor, onSave, onCancel }: MonitorFormPr
                 <span className="label-text-alt">如果指定，响应内容必须包含此文本才算成功</span>
               </label>
             </div>
+
+            {/* 响应断言 */}
+            <div className="form-control">
+              <label className="label">
+                <span className="label-text">响应断言（可选）</span>
+              </label>
+              <div className="space-y-2">
+                {assertions.map((assertion, index) => (
+                  <div key={index} className="flex gap-2">
+                    <select
+                      className="select select-bordered select-sm"
+                      value={assertion.type}
+                      onChange={(e) => updateAssertion(index, newAssertion(e.target.value as ApiAssertion['type']))}
+                    >
+                      <option value="status">状态码</option>
+                      <option value="json_path">JSONPath</option>
+                      <option value="regex">正则匹配</option>
+                      <option value="header">响应头</option>
+                      <option value="max_size">最大大小</option>
+                    </select>
+                    {assertion.type === 'status' && (
+                      <input
+                        type="text"
+                        placeholder="200, 2xx, 200-204"
+                        className="input input-bordered input-sm flex-1"
+                        value={assertion.codes.join(', ')}
+                        onChange={(e) =>
+                          updateAssertion(index, {
+                            ...assertion,
+                            codes: e.target.value.split(',').map((code) => code.trim()).filter(Boolean),
+                          })
+                        }
+                      />
+                    )}
+                    {assertion.type === 'json_path' && (
+                      <>
+                        <input
+                          type="text"
+                          placeholder="$.status"
+                          className="input input-bordered input-sm flex-1 font-mono"
+                          value={assertion.path}
+                          onChange={(e) => updateAssertion(index, { ...assertion, path: e.target.value })}
+                        />
+                        <input
+                          type="text"
+                          placeholder="期望值（可选）"
+                          className="input input-bordered input-sm flex-1 font-mono"
+                          value={
+                            assertion.equals === undefined
+                              ? ''
+                              : typeof assertion.equals === 'string'
+                                ? assertion.equals
+                                : JSON.stringify(assertion.equals)
+                          }
+                          onChange={(e) =>
+                            updateAssertion(index, { ...assertion, equals: parseExpectedValue(e.target.value) })
+                          }
+                        />
+                      </>
+                    )}
+                    {assertion.type === 'regex' && (
+                      <input
+                        type="text"
+                        placeholder="正则表达式"
+                        className="input input-bordered input-sm flex-1 font-mono"
+                        value={assertion.pattern}
+                        onChange={(e) => updateAssertion(index, { ...assertion, pattern: e.target.value })}
+                      />
+                    )}
+                    {assertion.type === 'header' && (
+                      <>
+                        <input
+                          type="text"
+                          placeholder="Content-Type"
+                          className="input input-bordered input-sm flex-1"
+                          value={assertion.name}
+                          onChange={(e) => updateAssertion(index, { ...assertion, name: e.target.value })}
+                        />
+                        <input
+                          type="text"
+                          placeholder="包含的值（可选）"
+                          className="input input-bordered input-sm flex-1"
+                          value={assertion.value || ''}
+                          onChange={(e) => updateAssertion(index, { ...assertion, value: e.target.value || null })}
+                        />
+                      </>
+                    )}
+                    {assertion.type === 'max_size' && (
+                      <input
+                        type="number"
+                        min={0}
+                        placeholder="字节"
+                        className="input input-bordered input-sm flex-1"
+                        value={assertion.bytes}
+                        onChange={(e) => updateAssertion(index, { ...assertion, bytes: Number(e.target.value) })}
+                      />
+                    )}
+                    <button
+                      type="button"
+                      className="btn btn-sm btn-ghost"
+                      onClick={() => setAssertions(assertions.filter((_, i) => i !== index))}
+                    >
+                      ✕
+                    </button>
+                  </div>
+                ))}
+                <button
+                  type="button"
+                  className="btn btn-sm btn-ghost"
+                  onClick={() => setAssertions([...assertions, newAssertion('status')])}
+                >
+                  + 添加断言
+                </button>
+              </div>
+              <label className="label">
+                <span className="label-text-alt">所有断言都通过才算成功，未通过的断言会逐条记录</span>
+              </label>
+            </div>
           </div>
         </div>
       )}
@@ -528,6 +1109,64 @@ export default function MonitorForm({ monitor, onSave, onCancel }: MonitorFormPr
         </div>
       )}
 
+      {monitorType === 'certificate' && (
+        <div className="card bg-base-100 shadow-sm">
+          <div className="card-body">
+            <h3 className="card-title text-lg">证书监控配置</h3>
+
+            {/* 主机与端口 */}
+            <div className="form-control">
+              <label className="label">
+                <span className="label-text">主机 *</span>
+              </label>
+              <div className="flex gap-2">
+                <input
+                  type="text"
+                  placeholder="例如: example.com"
+                  className={`input input-bordered flex-1 ${errors.certHost ? 'input-error' : ''}`}
+                  value={certHost}
+                  onChange={(e) => setCertHost(e.target.value)}
+                />
+                <input
+                  type="number"
+                  min={1}
+                  max={65535}
+                  className="input input-bordered w-28"
+                  value={certPort}
+                  onChange={(e) => setCertPort(parseInt(e.target.value) || 0)}
+                />
+              </div>
+              {errors.certHost && (
+                <label className="label">
+                  <span className="label-text-alt text-error">{errors.certHost}</span>
+                </label>
+              )}
+            </div>
+
+            {/* 提前告警天数 */}
+            <div className="form-control">
+              <label className="label">
+                <span className="label-text">提前告警天数</span>
+              </label>
+              <input
+                type="number"
+                min={1}
+                className={`input input-bordered ${errors.warnDays ? 'input-error' : ''}`}
+                value={warnDays}
+                onChange={(e) => setWarnDays(parseInt(e.target.value) || 0)}
+              />
+              <label className="label">
+                {errors.warnDays ? (
+                  <span className="label-text-alt text-error">{errors.warnDays}</span>
+                ) : (
+                  <span className="label-text-alt">连接后读取服务器证书，剩余有效期少于该天数或已过期时检查失败</span>
+                )}
+              </label>
+            </div>
+          </div>
+        </div>
+      )}
+
       {/* 操作按钮 */}
       <div className="flex justify-end gap-2">
         <button type="button" className="btn btn-ghost" onClick={onCancel}>
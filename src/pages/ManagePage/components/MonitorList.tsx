@@ -39,6 +39,8 @@ export default function MonitorList({ monitors, onEdit, onDelete }: MonitorListP
         return '🌐 API监控';
       case 'port':
         return '🔌 端口监控';
+      case 'certificate':
+        return '🔒 证书监控';
       default:
         return type;
     }
@@ -52,6 +54,8 @@ export default function MonitorList({ monitors, onEdit, onDelete }: MonitorListP
     switch (monitor.last_status) {
       case 'running':
         return <span className="badge badge-success">运行中</span>;
+      case 'degraded':
+        return <span className="badge badge-warning">延迟高</span>;
       case 'error':
         return <span className="badge badge-error">异常</span>;
       case 'checking':
@@ -67,17 +71,10 @@ export default function MonitorList({ monitors, onEdit, onDelete }: MonitorListP
   };
 
   const getTargetDisplay = (monitor: Monitor) => {
-    if (monitor.monitor_type === 'process') {
-      return monitor.target;
-    } else {
-      // Try to parse API config
-      try {
-        const config = JSON.parse(monitor.target);
-        return `${config.method || 'GET'} ${config.url}`;
-      } catch {
-        return monitor.target;
-      }
+    if (monitor.config?.type === 'api') {
+      return `${monitor.config.method} ${monitor.config.url}`;
     }
+    return monitor.target;
   };
 
   if (monitors.length === 0) {
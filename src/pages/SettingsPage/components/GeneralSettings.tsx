@@ -50,7 +50,7 @@ export default function GeneralSettings() {
           <div className="flex items-center justify-between">
             <div>
               <p className="font-medium">通知</p>
-              <p className="text-sm opacity-60">执行完成后显示通知</p>
+              <p className="text-sm opacity-60">监控告警、恢复及长时间运行完成时显示桌面通知</p>
             </div>
             <input
               type="checkbox"
@@ -6,6 +6,7 @@ interface LogState {
   isPanelOpen: boolean;
   maxLogs: number;
   addLog: (log: LogEntry) => void;
+  addLogs: (logs: LogEntry[]) => void;
   clearLogs: () => void;
   togglePanel: (open?: boolean) => void;
   setMaxLogs: (max: number) => void;
@@ -24,6 +25,12 @@ export const useLogStore = create<LogState>((set) => ({
       }
       return { logs: newLogs };
     }),
+  addLogs: (logs) =>
+    set((state) => {
+      // 批量追加，只保留最新的 maxLogs 条
+      const newLogs = [...state.logs, ...logs];
+      return { logs: newLogs.length > state.maxLogs ? newLogs.slice(-state.maxLogs) : newLogs };
+    }),
   clearLogs: () => set({ logs: [] }),
   togglePanel: (open) => set((state) => ({ isPanelOpen: open !== undefined ? open : !state.isPanelOpen })),
   setMaxLogs: (max) => set({ maxLogs: max }),
@@ -73,8 +73,8 @@ export const useMonitorStore = create<MonitorState>((set, get) => ({
         throw new Error('Monitor not found');
       }
 
-      const updatedMonitor = { ...monitor, ...updates };
-      await tauriApi.updateMonitor(id, updatedMonitor);
+      // 后端会校验并生成 target 摘要，以返回值为准
+      const updatedMonitor = await tauriApi.updateMonitor(id, { ...monitor, ...updates });
 
       set((state) => ({
         monitors: state.monitors.map((m) => (m.id === id ? updatedMonitor : m)),
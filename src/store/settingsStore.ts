@@ -1,4 +1,10 @@
 import { create } from 'zustand';
+import {
+  getAutoStartMonitors,
+  getNotificationsEnabled,
+  setAutoStartMonitors as saveAutoStartMonitors,
+  setNotificationsEnabled as saveNotificationsEnabled,
+} from '../api/tauri';
 
 export type Theme = 'light' | 'dark' | 'system';
 
@@ -64,6 +70,8 @@ export const useSettingsStore = create<SettingsState>((set) => ({
   setAutoStartMonitors: (enabled) => {
     set({ autoStartMonitors: enabled });
     localStorage.setItem('app-autoStartMonitors', enabled.toString());
+    // 后端在启动时读取此设置来恢复监控
+    saveAutoStartMonitors(enabled).catch((error) => console.error('Failed to save auto-start setting:', error));
   },
 
   setExecuteConfirmation: (enabled) => {
@@ -79,6 +87,8 @@ export const useSettingsStore = create<SettingsState>((set) => ({
   setNotificationsEnabled: (enabled) => {
     set({ notificationsEnabled: enabled });
     localStorage.setItem('app-notificationsEnabled', enabled.toString());
+    // 桌面通知由后端发送
+    saveNotificationsEnabled(enabled).catch((error) => console.error('Failed to save notification setting:', error));
   },
 
   loadSettings: () => {
@@ -101,6 +111,14 @@ export const useSettingsStore = create<SettingsState>((set) => ({
     });
 
     applyTheme(theme);
+
+    // 自动启动监控以后端存储为准
+    getAutoStartMonitors()
+      .then((enabled) => set({ autoStartMonitors: enabled }))
+      .catch((error) => console.error('Failed to load auto-start setting:', error));
+    getNotificationsEnabled()
+      .then((enabled) => set({ notificationsEnabled: enabled }))
+      .catch((error) => console.error('Failed to load notification setting:', error));
   },
 
   resetSettings: () => {
@@ -112,6 +130,12 @@ export const useSettingsStore = create<SettingsState>((set) => ({
     localStorage.removeItem('app-executeConfirmation');
     localStorage.removeItem('app-executeTimeout');
     localStorage.removeItem('app-notificationsEnabled');
+    saveAutoStartMonitors(DEFAULT_SETTINGS.autoStartMonitors).catch((error) =>
+      console.error('Failed to save auto-start setting:', error)
+    );
+    saveNotificationsEnabled(DEFAULT_SETTINGS.notificationsEnabled).catch((error) =>
+      console.error('Failed to save notification setting:', error)
+    );
     applyTheme(DEFAULT_SETTINGS.theme);
   },
 }));
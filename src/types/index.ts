@@ -9,6 +9,65 @@ export interface Button {
   position: number;
   created_at: number;
   updated_at: number;
+  requires_confirmation?: boolean;
+  retry_policy?: RetryPolicy | null;
+  notifications_muted?: boolean; // 长时间运行完成后不弹出桌面通知
+}
+
+// 失败重试策略
+export interface RetryPolicy {
+  max_attempts: number;
+  backoff_ms: number;
+  backoff_multiplier: number;
+  retry_exit_codes: number[];
+}
+
+// 一次按钮运行记录（包含所有重试）
+export interface ExecutionRecord {
+  id: string;
+  button_id: string;
+  trigger: 'manual' | 'schedule' | 'file_watch' | 'remediation';
+  trigger_detail?: string | null;
+  status: 'running' | 'success' | 'failed';
+  attempts: number;
+  exit_code: number | null;
+  error: string | null;
+  started_at: number;
+  finished_at: number | null;
+  duration_ms: number | null;
+}
+
+// 执行前检查发现的风险
+export interface RiskFinding {
+  rule: string;
+  description: string;
+  line: number;
+  snippet: string;
+}
+
+// 执行前检查结果
+export interface PreflightReport {
+  button_id: string;
+  requires_confirmation: boolean;
+  flagged_by_button: boolean;
+  findings: RiskFinding[];
+}
+
+// 环境变量变更
+export interface EnvChange {
+  name: string;
+  value: string;
+  previous: string | null;
+}
+
+// 执行预览（不实际运行）
+export interface ExecutionPreview {
+  button_id: string;
+  interpreter: string;
+  argv: string[];
+  working_dir: string;
+  env: EnvChange[];
+  script: string;
 }
 
 // 文件夹类型
@@ -25,7 +84,7 @@ export interface Monitor {
   id: string;
   name: string;
   icon?: string | null;
-  monitor_type: 'process' | 'api' | 'port';
+  monitor_type: 'process' | 'api' | 'port' | 'certificate';
   target: string;
   check_interval: number;
   expected_result?: string | null;
@@ -36,6 +95,149 @@ export interface Monitor {
   folder_id?: string | null;
   position: number;
   created_at: number;
+  remediation_button_id?: string | null;
+  remediation_threshold?: number;
+  remediation_cooldown_secs?: number;
+  remediation_confirmed_script?: string | null; // 已确认可自动运行的修复脚本指纹
+  degraded_threshold_ms?: number | null; // 检查成功但超过该延迟时状态为 degraded
+  alert_threshold?: number; // 连续失败多少次后告警
+  alert_renotify_secs?: number | null; // 持续异常时重复告警的间隔（为空则只告警一次）
+  notifications_muted?: boolean; // 告警只在应用内显示，不弹出桌面通知
+  config?: MonitorConfig | null; // 监控配置（target 为其摘要，由后端生成）
+}
+
+// 监控配置，type 与 monitor_type 一致
+export type MonitorConfig =
+  | { type: 'process'; name?: string | null; pid?: number | null } // name 与 pid 二选一
+  | {
+      type: 'api';
+      url: string;
+      method: string;
+      headers: Array<{ key: string; value: string }>;
+      body?: string | null;
+      assertions: ApiAssertion[];
+      timeout_secs: number;
+      auth?: ApiAuth | null;
+      tls?: TlsOptions;
+      http?: HttpOptions;
+    }
+  | { type: 'port'; host: string; port: number }
+  | { type: 'certificate'; host: string; port: number; warn_days: number }; // 证书剩余有效期少于 warn_days 天时告警
+
+// API 监控的 TLS 设置，文件均为 PEM 格式
+export interface TlsOptions {
+  accept_invalid_certs: boolean; // 接受自签名、过期或域名不匹配的证书
+  ca_bundle?: string | null; // 额外信任的 CA 证书文件
+  client_cert?: string | null; // mTLS 客户端证书文件
+  client_key?: string | null; // 客户端证书的 PKCS#8 私钥文件
+}
+
+// API 监控的连接设置
+export interface HttpOptions {
+  proxy?: string | null; // http:// 或 https:// 代理，为空时使用系统代理
+  max_redirects: number; // 最多跟随的重定向次数，0 表示不跟随
+  http_version: 'auto' | 'http1' | 'http2'; // http2 为不经协商直接使用 HTTP/2
+}
+
+// API 监控的认证方式，凭据须为 ${secret:NAME} 引用
+export type ApiAuth =
+  | { type: 'bearer'; token: string }
+  | { type: 'basic'; username: string; password: string }
+  | { type: 'api_key'; header: string; value: string } // header 默认 X-API-Key
+  | { type: 'oauth2'; token_url: string; client_id: string; client_secret: string; scope?: string | null }; // client credentials，令牌会缓存并在过期前刷新
+
+// 字段校验错误，field 如 "config.url"
+export interface FieldError {
+  field: string;
+  message: string;
+}
+
+// 创建/更新监控失败时的错误
+export interface MonitorError {
+  message: string;
+  fields: FieldError[];
+}
+
+// 检查失败的原因
+export type CheckErrorKind =
+  | 'config'
+  | 'dns'
+  | 'connection'
+  | 'auth'
+  | 'certificate'
+  | 'timeout'
+  | 'status'
+  | 'content'
+  | 'assertion'
+  | 'body'
+  | 'not_running';
+
+// API 监控的响应断言（保存在 API 目标的 assertions 中）
+export type ApiAssertion =
+  | { type: 'status'; codes: string[] } // 如 "200"、"2xx"、"200-204"，替代默认的 2xx 规则
+  | { type: 'json_path'; path: string; equals?: unknown } // 未设置 equals 时只要求路径存在
+  | { type: 'regex'; pattern: string }
+  | { type: 'header'; name: string; value?: string | null } // value 为响应头需包含的文本
+  | { type: 'max_size'; bytes: number };
+
+// 一次检查的结构化结果
+export interface CheckOutcome {
+  healthy: boolean;
+  latency_ms?: number | null;
+  status_code?: number | null;
+  bytes?: number | null; // 响应体大小
+  error_kind?: CheckErrorKind | null;
+  error?: string | null;
+  failed_assertions?: string[]; // 每个未通过的断言一条说明
+  certificate_expires_at?: number | null; // 证书监控检查到的证书过期时间
+}
+
+// monitor-status-update / monitor-alert 事件内容
+// monitor-alert 的 status: alert | recovered | flapping | stable
+export interface MonitorStatusEvent {
+  monitor_id: string;
+  status: string;
+  last_check_time: number;
+  message?: string | null;
+  outcome?: CheckOutcome | null;
+}
+
+// 一次监控检查的结果
+export interface MonitorCheck {
+  id: number;
+  monitor_id: string;
+  timestamp: number;
+  healthy: boolean;
+  latency_ms?: number | null; // 检查未能执行时为空
+  detail?: string | null; // 失败原因
+}
+
+// 一段时间内的可用率与延迟统计
+export interface MonitorStats {
+  monitor_id: string;
+  from: number;
+  to: number;
+  total_checks: number;
+  healthy_checks: number;
+  uptime_percent?: number | null; // 无检查记录时为空
+  incidents: number; // 连续失败的次数段
+  avg_latency_ms?: number | null;
+  p95_latency_ms?: number | null;
+}
+
+// 按时间段聚合的检查记录
+export interface MonitorCheckBucket {
+  start: number;
+  total_checks: number;
+  healthy_checks: number;
+  avg_latency_ms?: number | null;
+  max_latency_ms?: number | null;
+}
+
+// 降采样后的检查历史
+export interface MonitorCheckHistory {
+  bucket_secs: number;
+  buckets: MonitorCheckBucket[];
 }
 
 // 日志类型
@@ -46,10 +248,130 @@ export interface LogEntry {
   level: 'info' | 'warning' | 'error';
   message: string;
   timestamp: number;
+  execution_id?: string | null;
 }
 
+// 一次执行的批量日志（log-batch 事件）
+export interface LogBatch {
+  execution_id: string;
+  entries: LogEntry[];
+  dropped: number; // 因输出过快被丢弃的行数
+}
+
+// 日志搜索条件（所有条件均可选）
+export interface LogSearchQuery {
+  text?: string; // 按空白分隔的关键词，需全部出现在日志内容中
+  levels?: LogEntry['level'][];
+  from?: number; // 起止时间戳（含）
+  to?: number;
+  button_id?: string;
+  monitor_id?: string;
+  execution_id?: string;
+  cursor?: string; // 上一页的 next_cursor
+  limit?: number; // 默认 100，最大 500
+}
+
+// 一页搜索结果（从新到旧）
+export interface LogSearchPage {
+  entries: LogEntry[];
+  next_cursor?: string | null; // 最后一页为空
+}
+
+// 日志导出格式
+export type LogExportFormat = 'text' | 'jsonl' | 'csv';
+
 // 执行状态
 export type ExecutionStatus = 'idle' | 'running' | 'success' | 'error';
 
 // 监控状态
-export type MonitorStatus = 'stopped' | 'running' | 'checking' | 'alert';
+export type MonitorStatus = 'stopped' | 'running' | 'degraded' | 'checking' | 'alert';
+
+// 凭据（不包含值）
+export interface SecretInfo {
+  name: string;
+  created_at: number;
+  updated_at: number;
+}
+
+// 凭据存储状态
+export interface SecretStatus {
+  locked: boolean;
+  key_source?: 'keyring' | 'file' | 'password' | null;
+  secret_count: number;
+}
+
+// 日志脱敏规则
+export interface RedactionPattern {
+  name: string;
+  pattern: string;
+}
+
+// 日志脱敏配置
+export interface RedactionConfig {
+  builtin_enabled: boolean;
+  custom_patterns: RedactionPattern[];
+}
+
+// 日志保留策略（null 表示不限制）
+export interface RetentionConfig {
+  max_age_days: number | null;
+  max_rows: number | null;            // 日志总行数上限
+  max_rows_per_source: number | null; // 每个按钮 / 监控的行数上限
+  vacuum: 'none' | 'incremental' | 'full'; // incremental 需先调用 enableIncrementalVacuum 转换数据库
+}
+
+// 一次日志清理的结果
+export interface PruneReport {
+  by_age: number;
+  by_source: number;
+  by_total: number;
+  total: number;
+  checks_by_age: number; // 按保留天数清理的监控检查记录（不计入 total）
+  vacuumed: boolean;
+  ran_at: number;
+}
+
+// 告警通知渠道（聊天机器人或 Webhook）
+export interface NotificationChannel {
+  id: string;
+  name: string;
+  channel_type: 'slack' | 'mattermost' | 'feishu' | 'dingtalk' | 'webhook';
+  url: string; // 可包含 ${secret:NAME} 引用
+  template?: string | null; // JSON 消息模板，支持 {{title}} {{message}} {{text}} {{source}} {{event}} {{timestamp}}（为空则使用默认格式）
+  enabled: boolean;
+  created_at: number;
+  updated_at: number;
+}
+
+// 按钮定时运行计划
+export interface Schedule {
+  id: string;
+  button_id: string;
+  cron_expression: string;
+  timezone: string;
+  enabled: boolean;
+  last_run_at: number | null;
+  confirmed_script?: string | null; // 已确认可无人值守运行的脚本指纹
+  created_at: number;
+  updated_at: number;
+}
+
+// 定时计划及下次运行时间
+export interface ScheduleInfo extends Schedule {
+  next_run_at: number | null;
+  error: string | null;
+}
+
+// 文件变更触发器
+export interface FileTrigger {
+  id: string;
+  button_id: string;
+  paths: string[];
+  include: string[];
+  exclude: string[];
+  debounce_ms: number;
+  enabled: boolean;
+  confirmed_script?: string | null; // 已确认可无人值守运行的脚本指纹
+  created_at: number;
+  updated_at: number;
+}